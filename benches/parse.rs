@@ -0,0 +1,67 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::path::{Path, PathBuf};
+
+use rust_architecture::{generate_document, parse_components_with, Config, FileFilter};
+
+const COMPONENTS: usize = 1_000;
+
+/// Builds `n` synthetic README files spread over a handful of categories.
+fn synthetic_inputs(n: usize) -> Vec<(String, PathBuf)> {
+    (0..n)
+        .map(|i| {
+            let content = format!(
+                "---\ncategory: \"Category {}\"\ndependencies: [\"c{}\"]\n---\n\n# Component {}\n\n\
+                 Component {} does something useful for the project.\n\nMore details.\n",
+                i % 10,
+                i.saturating_sub(1),
+                i,
+                i
+            );
+            (content, PathBuf::from(format!("/repo/c{}/README.md", i)))
+        })
+        .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let inputs = synthetic_inputs(COMPONENTS);
+    let base_dir = Path::new("/repo");
+    let config = Config::default();
+
+    c.bench_function("parse_components 1k", |b| {
+        b.iter(|| parse_components_with(black_box(&inputs), base_dir, &config))
+    });
+}
+
+fn bench_generate(c: &mut Criterion) {
+    let config = Config::default();
+    let components: Vec<_> =
+        parse_components_with(&synthetic_inputs(COMPONENTS), Path::new("/repo"), &config)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+    c.bench_function("generate_document 1k", |b| {
+        b.iter(|| generate_document(black_box(&components), &config))
+    });
+}
+
+fn bench_exclude(c: &mut Criterion) {
+    let exclude: Vec<String> = (0..100).map(|i| format!("**/vendor{}/**", i)).collect();
+    let paths: Vec<PathBuf> = (0..COMPONENTS)
+        .map(|i| PathBuf::from(format!("/repo/crates/c{}/README.md", i)))
+        .collect();
+
+    // Patterns are compiled once, outside the loop, as during discovery
+    let filter = FileFilter::new("/repo/**/README.md", &exclude).unwrap();
+    c.bench_function("exclude 100 patterns x 1k paths", |b| {
+        b.iter(|| {
+            black_box(&paths)
+                .iter()
+                .filter(|path| filter.is_match(path))
+                .count()
+        })
+    });
+}
+
+criterion_group!(benches, bench_parse, bench_generate, bench_exclude);
+criterion_main!(benches);
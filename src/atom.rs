@@ -0,0 +1,199 @@
+//! Atom feed generation from parsed components.
+//!
+//! Each component becomes a feed entry, ordered by the modification time of
+//! its README (newest first), so dashboards can follow architecture changes.
+
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::component::Component;
+use crate::config::Config;
+use crate::generator::{entry_description, is_shown};
+use crate::slug::slugify;
+
+/// Generates an Atom feed with one entry per component.
+///
+/// Entries are ordered newest first by `modified`, with components lacking a
+/// modification time last and ties broken by path. Entries without a
+/// modification time omit their `<updated>` element. The feed's `<author>`,
+/// which Atom requires and entries inherit, is the config's `feed_author`.
+pub fn generate_atom_feed(components: &[Component], config: &Config) -> String {
+    let mut entries: Vec<&Component> = components
+        .iter()
+        .filter(|comp| is_shown(comp, config))
+        .collect();
+    entries.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let mut feed = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    writeln!(feed, "  <title>{}</title>", escape_xml(config.title())).unwrap();
+    writeln!(
+        feed,
+        "  <id>urn:rust-architecture:{}</id>",
+        slugify(config.title())
+    )
+    .unwrap();
+    if let Some(updated) = entries.first().and_then(|c| c.modified) {
+        writeln!(feed, "  <updated>{}</updated>", format_rfc3339(updated)).unwrap();
+    }
+    writeln!(
+        feed,
+        "  <author>\n    <name>{}</name>\n  </author>",
+        escape_xml(config.feed_author())
+    )
+    .unwrap();
+
+    for comp in entries {
+        let category = config.canonical_category(&comp.category);
+
+        feed.push_str("  <entry>\n");
+        writeln!(feed, "    <title>{}</title>", escape_xml(&comp.title())).unwrap();
+        writeln!(
+            feed,
+            "    <id>urn:rust-architecture:{}</id>",
+            escape_xml(&comp.path.display().to_string())
+        )
+        .unwrap();
+        if let Some(modified) = comp.modified {
+            writeln!(feed, "    <updated>{}</updated>", format_rfc3339(modified)).unwrap();
+        }
+        writeln!(
+            feed,
+            "    <category term=\"{}\" label=\"{}\"/>",
+            escape_xml(category),
+            escape_xml(config.display_title_for(category))
+        )
+        .unwrap();
+        writeln!(
+            feed,
+            "    <summary>{}</summary>",
+            escape_xml(&entry_description(comp, config).joined())
+        )
+        .unwrap();
+        feed.push_str("  </entry>\n");
+    }
+
+    feed.push_str("</feed>\n");
+    feed
+}
+
+/// Escapes the characters that are special in XML text and attributes.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Formats a timestamp as an RFC 3339 UTC date-time, e.g. `2024-01-31T12:00:00Z`.
+fn format_rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Convert days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn component(name: &str, modified: Option<u64>) -> Component {
+        Component {
+            path: PathBuf::from(format!("crates/{}/README.md", name)),
+            description: format!("The {} <component> & more", name).into(),
+            category: "Core".to_string(),
+            modified: modified.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(format_rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_rfc3339(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            "2024-02-29T12:34:56Z"
+        );
+    }
+
+    #[test]
+    fn test_generate_atom_feed_well_formed() {
+        let components = vec![
+            component("old", Some(1_000)),
+            component("missing", None),
+            component("new", Some(2_000)),
+        ];
+
+        let feed = generate_atom_feed(&components, &Config::default());
+        let document = roxmltree::Document::parse(&feed).unwrap();
+        let root = document.root_element();
+        assert_eq!(root.tag_name().name(), "feed");
+
+        let titles: Vec<&str> = root
+            .children()
+            .filter(|n| n.has_tag_name("entry"))
+            .map(|entry| {
+                entry
+                    .children()
+                    .find(|n| n.has_tag_name("title"))
+                    .and_then(|n| n.text())
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(titles, vec!["new", "old", "missing"]);
+
+        // The feed-level author covers every entry
+        let author = root.children().find(|n| n.has_tag_name("author")).unwrap();
+        let name = author.children().find(|n| n.has_tag_name("name")).unwrap();
+        assert_eq!(name.text(), Some("Architecture Documentation"));
+
+        let config: Config = toml::from_str("feed_author = \"Platform & Web\"").unwrap();
+        assert!(generate_atom_feed(&components, &config)
+            .contains("  <author>\n    <name>Platform &amp; Web</name>\n  </author>\n"));
+    }
+
+    #[test]
+    fn test_generate_atom_feed_omits_missing_updated() {
+        let components = vec![component("missing", None)];
+
+        let feed = generate_atom_feed(&components, &Config::default());
+        assert!(!feed.contains("<updated>"));
+        assert!(feed.contains("<summary>The missing &lt;component&gt; &amp; more</summary>"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Read-through cache of files referenced while parsing components.
+//!
+//! Several READMEs often point at the same `description_from` file or sit
+//! next to the same `Cargo.toml`. A [`FileCache`] lives for one parsing run
+//! and is shared between the worker threads, so each such file is read from
+//! disk once per run. Failed reads are not cached and are retried, so their
+//! errors are reported for every file that references them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// File contents read so far in a run, keyed by canonical path.
+#[derive(Debug, Default)]
+pub(crate) struct FileCache {
+    files: Mutex<HashMap<PathBuf, Arc<str>>>,
+}
+
+impl FileCache {
+    /// Returns the content of the file at `path`, reading it on first use.
+    ///
+    /// Files are keyed by their canonical path, so `a/../docs/x.md` and
+    /// `b/../docs/x.md` share an entry.
+    pub(crate) fn read(&self, path: &Path) -> io::Result<Arc<str>> {
+        let key = fs::canonicalize(path)?;
+        if let Some(content) = self.lock().get(&key) {
+            return Ok(Arc::clone(content));
+        }
+
+        // Read without holding the lock; a concurrent read of the same file
+        // just loses the race and uses the cached copy
+        let content: Arc<str> = fs::read_to_string(&key)?.into();
+        Ok(Arc::clone(self.lock().entry(key).or_insert(content)))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Arc<str>>> {
+        // The map is never left half-updated, so a poisoned lock is still usable
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_cache_reads_each_file_once() {
+        let dir = std::env::temp_dir().join("rust-arch-file-cache");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("shared.md");
+        fs::write(&file, "first").unwrap();
+
+        let cache = FileCache::default();
+        assert_eq!(&*cache.read(&file).unwrap(), "first");
+
+        // Later reads in the run come from the cache, not the disk
+        fs::write(&file, "second").unwrap();
+        assert_eq!(&*cache.read(&file).unwrap(), "first");
+        assert_eq!(&*FileCache::default().read(&file).unwrap(), "second");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_file_cache_retries_failed_reads() {
+        let dir = std::env::temp_dir().join("rust-arch-file-cache-missing");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("late.md");
+
+        let cache = FileCache::default();
+        assert!(cache.read(&file).is_err());
+        fs::write(&file, "now here").unwrap();
+        assert_eq!(&*cache.read(&file).unwrap(), "now here");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
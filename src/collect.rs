@@ -0,0 +1,187 @@
+//! Reading, parsing and checking matched files the way `generate` does.
+//!
+//! [`Collector::read`] reads the files [`glob_patterns`] matched and
+//! [`Collector::parse`] turns them into the components a document lists,
+//! recording every file left out in a [`SkipReport`]. The steps are separate
+//! so a caller can time them or trim the matches in between.
+//!
+//! [`glob_patterns`]: crate::discovery::glob_patterns
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::component::{parse_components_with, Component};
+use crate::config::Config;
+use crate::error::ComponentError;
+use crate::validate::{check_component, Finding, SkipReport};
+
+/// Matched files read into memory, ready to parse.
+#[derive(Default)]
+pub struct Collector {
+    /// Each file's content and path, as [`parse_components_with`] takes them.
+    inputs: Vec<(String, PathBuf)>,
+    /// Each file's modification time and the base dir it is documented under.
+    details: Vec<(Option<SystemTime>, PathBuf)>,
+    report: SkipReport,
+}
+
+/// The components collected from matched files.
+pub struct Collected {
+    /// Each component that parsed and passed its checks, with the file it was
+    /// parsed from. Paths are relative to the file's base dir.
+    pub components: Vec<(Component, PathBuf)>,
+    /// The matched files and those left out, and why.
+    pub report: SkipReport,
+    /// The first error that fails the run: a component failing a check, or an
+    /// empty category with `strict_categories`. Every failing file is in
+    /// `report` either way.
+    pub error: Option<anyhow::Error>,
+}
+
+impl Collector {
+    /// Reads each matched file, given with the base dir of the pattern it is
+    /// documented under. Files that can't be read are skipped as `io_error`.
+    pub fn read(files: Vec<(PathBuf, PathBuf)>) -> Self {
+        let mut collector = Collector {
+            report: SkipReport {
+                total_files: files.len(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        for (file, base) in files {
+            match fs::read_to_string(&file) {
+                Ok(content) => {
+                    let modified = fs::metadata(&file).and_then(|m| m.modified()).ok();
+                    collector.inputs.push((content, file));
+                    collector.details.push((modified, base));
+                }
+                Err(source) => collector.report.skip(Finding::from_error(
+                    &ComponentError::Io { path: file, source }.into(),
+                )),
+            }
+        }
+        collector
+    }
+
+    /// Number of files read.
+    pub fn files(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Total size of the files read, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.inputs.iter().map(|(content, _)| content.len()).sum()
+    }
+
+    /// Parses and checks the files read, following `config`.
+    ///
+    /// `inspect` sees every parse result, failures included, before any are
+    /// left out. Files that fail to parse or to pass [`check_component`] are
+    /// skipped, and each component records its file's modification time.
+    pub fn parse(
+        self,
+        config: &Config,
+        inspect: impl FnOnce(&[anyhow::Result<Component>]),
+    ) -> Collected {
+        let Collector {
+            inputs,
+            details,
+            mut report,
+        } = self;
+        // Parse relative to the first match's base; each component's path is
+        // made relative to its own match's base afterwards
+        let base_dir = details
+            .first()
+            .map_or(Path::new(""), |(_, base)| base.as_path());
+        let results = parse_components_with(&inputs, base_dir, config);
+        inspect(&results);
+
+        let mut error = None;
+        let mut components = Vec::new();
+        for ((result, (_, file)), (modified, base)) in
+            results.into_iter().zip(&inputs).zip(&details)
+        {
+            let mut component = match result {
+                Ok(component) => component,
+                Err(e) => {
+                    report.skip(Finding::from_error(&e));
+                    // Strict mode fails on an empty category as on an unknown one
+                    if config.strict_categories
+                        && matches!(e.downcast_ref(), Some(ComponentError::EmptyCategory { .. }))
+                    {
+                        error.get_or_insert(e);
+                    }
+                    continue;
+                }
+            };
+            component.path = file.strip_prefix(base).unwrap_or(file).to_path_buf();
+            component.modified = *modified;
+            components.push((component, file.clone()));
+        }
+
+        // Every failing check is reported before failing on the first
+        components.retain(|(component, _)| match check_component(component, config) {
+            Ok(()) => true,
+            Err(e) => {
+                report.skip(Finding::from_error(&e));
+                error.get_or_insert(e);
+                false
+            }
+        });
+        report.parsed = components.len();
+
+        Collected {
+            components,
+            report,
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_skips_and_fails_like_generate() {
+        let dir = std::env::temp_dir().join("rust-arch-collect");
+        fs::remove_dir_all(&dir).ok();
+        for (name, category) in [("core", "Core"), ("empty", "")] {
+            fs::create_dir_all(dir.join(name)).unwrap();
+            fs::write(
+                dir.join(name).join("README.md"),
+                format!("---\ncategory: \"{}\"\n---\n\nA component.\n", category),
+            )
+            .unwrap();
+        }
+        let files = ["core", "empty", "missing"]
+            .map(|name| (dir.join(name).join("README.md"), dir.clone()))
+            .to_vec();
+
+        let collector = Collector::read(files.clone());
+        assert_eq!(collector.files(), 2);
+        let collected = collector.parse(&Config::default(), |results| assert_eq!(results.len(), 2));
+        assert_eq!(collected.components.len(), 1);
+        let (component, file) = &collected.components[0];
+        assert_eq!(component.path, PathBuf::from("core/README.md"));
+        assert!(component.modified.is_some());
+        assert_eq!(file, &dir.join("core/README.md"));
+        assert_eq!(collected.report.total_files, 3);
+        assert_eq!(collected.report.parsed, 1);
+        assert_eq!(collected.report.reasons.get("io_error"), Some(&1));
+        assert!(collected.error.is_none());
+
+        // Strict mode fails on the empty category, after reporting it
+        let config = Config {
+            strict_categories: true,
+            ..Default::default()
+        };
+        let collected = Collector::read(files).parse(&config, |_| {});
+        assert_eq!(collected.report.reasons.get("empty_category"), Some(&1));
+        assert!(collected.error.is_some());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -3,27 +3,317 @@
 //! This module handles parsing markdown files with YAML front matter
 //! into structured `Component` data used for architecture documentation.
 
-use anyhow::{Context, Result};
+use anyhow::Result;
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use crate::front_matter::{extract_first_paragraph, extract_front_matter, parse_front_matter};
+use crate::cache::FileCache;
+use crate::config::{Config, DescriptionFallback, FrontMatterFormat};
+use crate::error::{ComponentError, SchemaViolation};
+use crate::front_matter::{
+    deserialize_front_matter, extract_first_paragraph, parse_front_matter, resolve_format,
+    split_front_matter, trim_trailing_links, truncate_sentences, FrontMatter, Scalar,
+    ScalarVisitor,
+};
 
 /// A parsed component from a markdown README file.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Components round-trip through JSON, so a parsed set can be saved and
+/// rendered later without reading the README files again.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub struct Component {
     /// Path to the component's README, relative to the base directory.
     pub path: PathBuf,
     /// Description extracted from front matter or the first paragraph.
-    pub description: String,
-    /// Category for grouping components in the output.
+    pub description: Description,
+    /// Where the description was taken from.
+    #[serde(default)]
+    pub description_source: DescriptionSource,
+    /// Category for grouping components in the output, as written in front
+    /// matter or else the configured `default_category`. Aliases and
+    /// configured spellings are resolved where components are grouped, with
+    /// [`Config::canonical_category`].
     pub category: String,
+    /// Category as written in front matter. `None` when the category was
+    /// defaulted rather than written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_category: Option<String>,
+    /// Title overriding the component's name in stubs and feeds.
+    pub display_title: Option<String>,
+    /// Names of the components this component depends on.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Free-form labels for filtering components.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Teams or people owning the component.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Names or paths of components cross-linked under the entry.
+    #[serde(default)]
+    pub related: Vec<String>,
+    /// Maturity status shown as a badge after the description.
+    pub status: Option<String>,
+    /// Whether the component is listed, struck through, in the deprecated
+    /// section instead of its category.
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Whether the component doesn't exist yet, listed with a marker in the
+    /// planned section instead of its category when shown at all.
+    #[serde(default)]
+    pub planned: bool,
+    /// Version the component was added in, for `group_by = "release"`.
+    pub since: Option<String>,
+    /// Front matter keys not modeled by the other fields.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+    /// Link target for the component's entry in the generated document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Markdown content after the front matter, trimmed. Only read with
+    /// `embed_bodies`, the one output that uses it, so large trees don't hold
+    /// every README in memory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Last modification time of the README, when read from disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<SystemTime>,
+}
+
+/// A component description: a primary line, optionally followed by
+/// continuation lines rendered under the entry.
+///
+/// In front matter, `description` is either a string or a list of strings,
+/// and it serializes back to the same form. Integers and booleans are read as
+/// the text they were written as; floats must be quoted.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(from = "DescriptionRepr", into = "DescriptionRepr")]
+pub struct Description(Vec<String>);
+
+/// The front matter forms of a [`Description`].
+#[derive(Clone, Serialize)]
+#[serde(untagged)]
+enum DescriptionRepr {
+    Line(Scalar),
+    Lines(Vec<Scalar>),
+}
+
+impl<'de> Deserialize<'de> for DescriptionRepr {
+    /// Deserialized by hand rather than as an untagged enum, so a scalar's own
+    /// error, such as for an unquoted float, reaches the user.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ReprVisitor;
+
+        impl<'de> Visitor<'de> for ReprVisitor {
+            type Value = DescriptionRepr;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string, number, boolean or a list of them")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                ScalarVisitor.visit_str(value).map(DescriptionRepr::Line)
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+                ScalarVisitor.visit_string(value).map(DescriptionRepr::Line)
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                ScalarVisitor.visit_bool(value).map(DescriptionRepr::Line)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                ScalarVisitor.visit_i64(value).map(DescriptionRepr::Line)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                ScalarVisitor.visit_u64(value).map(DescriptionRepr::Line)
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                ScalarVisitor.visit_f64(value).map(DescriptionRepr::Line)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut lines = Vec::new();
+                while let Some(line) = seq.next_element()? {
+                    lines.push(line);
+                }
+                Ok(DescriptionRepr::Lines(lines))
+            }
+        }
+
+        deserializer.deserialize_any(ReprVisitor)
+    }
+}
+
+impl Description {
+    /// The primary line, for places that need a single line such as the entry
+    /// itself or a table cell. Empty if there are no lines.
+    pub fn primary(&self) -> &str {
+        self.0.first().map_or("", String::as_str)
+    }
+
+    /// The lines after the primary one.
+    pub fn continuation(&self) -> &[String] {
+        self.0.get(1..).unwrap_or_default()
+    }
+
+    /// All lines, primary first.
+    pub fn lines(&self) -> &[String] {
+        &self.0
+    }
+
+    /// All lines joined by spaces, for places that treat the description as
+    /// one piece of running text.
+    pub fn joined(&self) -> String {
+        self.0.join(" ")
+    }
+
+    /// Whether there are no lines.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for Description {
+    fn from(line: String) -> Self {
+        Description(vec![line])
+    }
+}
+
+impl From<&str> for Description {
+    fn from(line: &str) -> Self {
+        Description::from(line.to_string())
+    }
+}
+
+impl From<Vec<String>> for Description {
+    fn from(lines: Vec<String>) -> Self {
+        Description(lines)
+    }
+}
+
+impl From<DescriptionRepr> for Description {
+    fn from(repr: DescriptionRepr) -> Self {
+        match repr {
+            DescriptionRepr::Line(line) => line.0.into(),
+            DescriptionRepr::Lines(lines) => lines
+                .into_iter()
+                .map(|line| line.0)
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+}
+
+impl From<Description> for DescriptionRepr {
+    fn from(description: Description) -> Self {
+        match <[String; 1]>::try_from(description.0) {
+            Ok([line]) => DescriptionRepr::Line(Scalar(line)),
+            Err(lines) => DescriptionRepr::Lines(lines.into_iter().map(Scalar).collect()),
+        }
+    }
+}
+
+impl PartialEq<str> for Description {
+    /// A description equals a string when it is that single line.
+    fn eq(&self, other: &str) -> bool {
+        matches!(self.0.as_slice(), [line] if line == other)
+    }
+}
+
+impl PartialEq<&str> for Description {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Description {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+/// The source a component's description was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptionSource {
+    /// The front matter `description` field.
+    #[default]
+    FrontMatter,
+    /// The first paragraph of the file named by `description_from`.
+    ReferencedFile,
+    /// The first paragraph of the markdown content.
+    FirstParagraph,
+    /// `package.description` in the `Cargo.toml` next to the README.
+    CargoToml,
+}
+
+impl fmt::Display for DescriptionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DescriptionSource::FrontMatter => "front_matter",
+            DescriptionSource::ReferencedFile => "referenced_file",
+            DescriptionSource::FirstParagraph => "first_paragraph",
+            DescriptionSource::CargoToml => "cargo_toml",
+        })
+    }
+}
+
+impl Component {
+    /// Returns the component's name, used as its identity in the dependency graph.
+    ///
+    /// This is the name of the directory containing the README, or the file
+    /// stem when the README sits directly in the base directory.
+    pub fn name(&self) -> String {
+        self.path
+            .parent()
+            .and_then(|p| p.file_name())
+            .or_else(|| self.path.file_stem())
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Returns an identifier for the component that is stable across runs and
+    /// platforms: its relative path, with `/` between path components.
+    pub fn id(&self) -> String {
+        self.path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .join("/")
+    }
+
+    /// Returns the component's display title: the front matter `display_title`
+    /// if set, otherwise its [`name`](Component::name).
+    pub fn title(&self) -> String {
+        self.display_title.clone().unwrap_or_else(|| self.name())
+    }
+
+    /// Truncates a first-paragraph fallback description to its first
+    /// `sentences` sentences, appending `…` if anything was cut.
+    ///
+    /// Descriptions from front matter or `description_from` are left as is.
+    pub fn truncate_fallback_description(&mut self, sentences: usize) {
+        if self.description_source == DescriptionSource::FirstParagraph {
+            self.description = truncate_sentences(self.description.primary(), sentences).into();
+        }
+    }
 }
 
 /// Parses a markdown file and extracts component information.
 ///
 /// The file must contain YAML front matter with at least a `category` field.
 /// The description is taken from the front matter `description` field if present,
+/// then from the first paragraph of the `description_from` file if set,
 /// otherwise falls back to the first paragraph after the front matter.
 ///
 /// # Arguments
@@ -33,45 +323,316 @@ pub struct Component {
 ///
 /// # Errors
 ///
-/// Returns an error if:
+/// Returns a [`ComponentError`] if:
 /// - The file cannot be read
 /// - No front matter is found
 /// - Front matter is invalid YAML
 /// - No description is found in front matter or content
 pub fn parse_component(path: PathBuf, base_dir: &Path) -> Result<Component> {
-    let content = fs::read_to_string(&path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    parse_component_with(path, base_dir, &Config::default())
+}
+
+/// Parses a markdown file like [`parse_component`], taking the description
+/// from the sources configured in `description_sources`.
+///
+/// # Errors
+///
+/// Returns a [`ComponentError`] under the same conditions as [`parse_component`].
+pub fn parse_component_with(path: PathBuf, base_dir: &Path, config: &Config) -> Result<Component> {
+    let content = fs::read_to_string(&path).map_err(|source| ComponentError::Io {
+        path: path.clone(),
+        source,
+    })?;
 
-    let front_matter_str = extract_front_matter(&content)
-        .with_context(|| format!("No front matter found in: {}", path.display()))?;
+    let mut component = parse_component_content_with(&content, &path, base_dir, config)?;
+    component.modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+    Ok(component)
+}
 
-    let front_matter = parse_front_matter(front_matter_str)
-        .with_context(|| format!("Failed to parse front matter in: {}", path.display()))?;
+/// Resolves a component's description and where it came from.
+///
+/// Each source in `sources` is tried in order. The front matter source is the
+/// `description` field, or else the first paragraph of the `description_from`
+/// file (relative to the README); the Cargo source is `package.description` in
+/// a sibling `Cargo.toml`.
+fn resolve_description(
+    front_matter: &FrontMatter,
+    content: &str,
+    path: &Path,
+    sources: &[DescriptionFallback],
+    cache: &FileCache,
+) -> Result<(Description, DescriptionSource)> {
+    for source in sources {
+        let found = match source {
+            DescriptionFallback::FrontMatter => {
+                front_matter_description(front_matter, path, cache)?
+            }
+            DescriptionFallback::FirstParagraph => extract_first_paragraph(content)
+                .map(|description| (description.into(), DescriptionSource::FirstParagraph)),
+            DescriptionFallback::CargoToml => cargo_toml_description(path, cache)
+                .map(|description| (description.into(), DescriptionSource::CargoToml)),
+        };
+        if let Some(found) = found {
+            return Ok(found);
+        }
+    }
 
-    // Use front matter description, or fall back to first paragraph
-    let description = front_matter
-        .description
-        .or_else(|| extract_first_paragraph(&content))
-        .with_context(|| {
-            format!(
-                "No description found in front matter or content: {}",
-                path.display()
-            )
+    Err(ComponentError::NoDescription {
+        path: path.to_path_buf(),
+        referenced: None,
+    }
+    .into())
+}
+
+/// Takes the description from the front matter `description` field, or else
+/// from the first paragraph of the `description_from` file.
+fn front_matter_description(
+    front_matter: &FrontMatter,
+    path: &Path,
+    cache: &FileCache,
+) -> Result<Option<(Description, DescriptionSource)>> {
+    if let Some(description) = front_matter.description.as_ref().filter(|d| !d.is_empty()) {
+        return Ok(Some((description.clone(), DescriptionSource::FrontMatter)));
+    }
+
+    let Some(reference) = &front_matter.description_from else {
+        return Ok(None);
+    };
+    let referenced = path.parent().unwrap_or(Path::new("")).join(reference);
+    let referenced_content = match cache.read(&referenced) {
+        Ok(content) => content,
+        Err(source) => {
+            return Err(ComponentError::DescriptionFromUnreadable {
+                path: path.to_path_buf(),
+                referenced,
+                source,
+            }
+            .into())
+        }
+    };
+    let description = extract_first_paragraph(&referenced_content).ok_or_else(|| {
+        ComponentError::NoDescription {
+            path: path.to_path_buf(),
+            referenced: Some(referenced),
+        }
+    })?;
+    Ok(Some((
+        description.into(),
+        DescriptionSource::ReferencedFile,
+    )))
+}
+
+/// Reads `package.description` from the `Cargo.toml` next to the README, if
+/// the file exists and sets it.
+fn cargo_toml_description(path: &Path, cache: &FileCache) -> Option<String> {
+    let manifest = path.parent().unwrap_or(Path::new("")).join("Cargo.toml");
+    let manifest: toml::Table = toml::from_str(&cache.read(&manifest).ok()?).ok()?;
+    let description = manifest.get("package")?.get("description")?.as_str()?;
+    Some(description.trim().to_string())
+}
+
+/// Checks front matter text, as its JSON equivalent, against the config's
+/// `front_matter_schema`, reporting every violation at once.
+fn check_schema(
+    text: &str,
+    format: FrontMatterFormat,
+    path: &Path,
+    validator: &jsonschema::Validator,
+) -> Result<()> {
+    // Already deserialized as `FrontMatter`, so this only fails on values
+    // JSON can't represent, such as non-string keys, which the schema can't
+    // check and so are a violation of it
+    let value: serde_json::Value = match deserialize_front_matter(text, format) {
+        Ok(value) => value,
+        Err(e) => {
+            return Err(ComponentError::SchemaViolation {
+                path: path.to_path_buf(),
+                violations: vec![SchemaViolation {
+                    pointer: String::new(),
+                    message: format!("front matter has no JSON equivalent: {}", e.message),
+                }],
+            }
+            .into())
+        }
+    };
+    let violations: Vec<_> = validator
+        .iter_errors(&value)
+        .map(|e| SchemaViolation {
+            pointer: e.instance_path.to_string(),
+            message: e.to_string(),
+        })
+        .collect();
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(ComponentError::SchemaViolation {
+        path: path.to_path_buf(),
+        violations,
+    }
+    .into())
+}
+
+/// Parses already-read markdown content into a component.
+///
+/// Behaves like [`parse_component`], but takes the file content directly.
+/// `path` is used for the component's relative path, error messages, and
+/// resolving `description_from`; the returned component has no `modified` time.
+///
+/// # Errors
+///
+/// Returns a [`ComponentError`] if:
+/// - No front matter is found
+/// - Front matter is invalid YAML
+/// - A `description_from` file cannot be read or has no paragraph
+/// - No description is found in front matter or content
+pub fn parse_component_content(content: &str, path: &Path, base_dir: &Path) -> Result<Component> {
+    parse_component_content_with(content, path, base_dir, &Config::default())
+}
+
+/// Parses already-read markdown content like [`parse_component_content`],
+/// taking the description from the sources configured in `description_sources`.
+///
+/// # Errors
+///
+/// Returns a [`ComponentError`] under the same conditions as [`parse_component_content`],
+/// or if the front matter doesn't match the config's `front_matter_schema`.
+pub fn parse_component_content_with(
+    content: &str,
+    path: &Path,
+    base_dir: &Path,
+    config: &Config,
+) -> Result<Component> {
+    parse_component_content_cached(content, path, base_dir, config, &FileCache::default())
+}
+
+/// Parses already-read markdown content like [`parse_component_content_with`],
+/// reading referenced files through `cache`.
+fn parse_component_content_cached(
+    content: &str,
+    path: &Path,
+    base_dir: &Path,
+    config: &Config,
+    cache: &FileCache,
+) -> Result<Component> {
+    let (front_matter_str, body) =
+        split_front_matter(content).ok_or_else(|| ComponentError::NoFrontMatter {
+            path: path.to_path_buf(),
         })?;
 
+    let format = resolve_format(config.front_matter_format_for(path), content);
+    let front_matter = parse_front_matter(front_matter_str, format).map_err(|e| {
+        ComponentError::InvalidFrontMatter {
+            path: path.to_path_buf(),
+            // The front matter starts on the line after the opening delimiter
+            line: e.line.map(|line| line + 1),
+            message: e.message,
+        }
+    })?;
+    if let Some(validator) = &config.front_matter_validator {
+        check_schema(front_matter_str, format, path, validator)?;
+    }
+
+    let (mut description, description_source) = resolve_description(
+        &front_matter,
+        content,
+        path,
+        config.description_sources(),
+        cache,
+    )?;
+    if config.trim_trailing_links && description_source == DescriptionSource::FirstParagraph {
+        description = trim_trailing_links(description.primary()).into();
+    }
+    let (category, raw_category) = resolve_category(front_matter.category, path, config)?;
+    let deprecated = front_matter.deprecated.unwrap_or(false);
+    let planned = front_matter.planned.unwrap_or(false);
+    if deprecated && planned {
+        return Err(ComponentError::DeprecatedAndPlanned {
+            path: path.to_path_buf(),
+        }
+        .into());
+    }
+
     // Make path relative to base_dir
     let relative_path = path
         .strip_prefix(base_dir)
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|_| path.clone());
+        .unwrap_or_else(|_| path.to_path_buf());
 
     Ok(Component {
         path: relative_path,
         description,
-        category: front_matter.category,
+        description_source,
+        category,
+        raw_category,
+        display_title: front_matter.display_title,
+        dependencies: front_matter.dependencies,
+        tags: front_matter.tags,
+        owners: front_matter.owners,
+        related: front_matter.related,
+        status: front_matter.status,
+        deprecated,
+        planned,
+        since: front_matter.since,
+        extra: front_matter.extra,
+        link: None,
+        body: config.embed_bodies.then(|| body.trim().to_string()),
+        modified: None,
     })
 }
 
+/// Returns the component's category and the category as written in front
+/// matter, if it was.
+///
+/// Rejects an empty or whitespace-only category, or replaces it with the
+/// configured `default_category` outside strict mode, with no written category.
+fn resolve_category(
+    category: String,
+    path: &Path,
+    config: &Config,
+) -> Result<(String, Option<String>)> {
+    if !category.trim().is_empty() {
+        return Ok((category.clone(), Some(category)));
+    }
+
+    match &config.default_category {
+        Some(default) if !config.strict_categories => Ok((default.clone(), None)),
+        _ => Err(ComponentError::EmptyCategory {
+            path: path.to_path_buf(),
+        }
+        .into()),
+    }
+}
+
+/// Parses a batch of already-read markdown files in parallel.
+///
+/// Each input is a file's content and path, parsed as by
+/// [`parse_component_content`]. Results are returned in input order, so
+/// downstream grouping is deterministic regardless of scheduling.
+pub fn parse_components(inputs: &[(String, PathBuf)], base_dir: &Path) -> Vec<Result<Component>> {
+    parse_components_with(inputs, base_dir, &Config::default())
+}
+
+/// Parses a batch of already-read markdown files in parallel like
+/// [`parse_components`], following `config`.
+///
+/// Each input is parsed as by [`parse_component_content_with`]. Files
+/// referenced by several inputs, like a shared `description_from` file, are
+/// read once per call.
+pub fn parse_components_with(
+    inputs: &[(String, PathBuf)],
+    base_dir: &Path,
+    config: &Config,
+) -> Vec<Result<Component>> {
+    // Shared by all files, so a file several READMEs reference is read once
+    let cache = FileCache::default();
+    inputs
+        .par_iter()
+        .map(|(content, path)| {
+            parse_component_content_cached(content, path, base_dir, config, &cache)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +660,7 @@ category: "Testing"
         assert_eq!(component.description, "Test component");
         assert_eq!(component.category, "Testing");
         assert_eq!(component.path, PathBuf::from("test_component.md"));
+        assert!(component.modified.is_some());
 
         fs::remove_file(&test_file).ok();
     }
@@ -176,7 +738,509 @@ More content here."#;
             component.description,
             "This description comes from the first paragraph."
         );
+        assert_eq!(
+            component.description_source,
+            DescriptionSource::FirstParagraph
+        );
+        assert_eq!(component.category, "Testing");
+
+        fs::remove_file(&test_file).ok();
+    }
+
+    #[test]
+    fn test_parse_component_content_description_list() {
+        let content = "---\ncategory: \"Testing\"\ndescription:\n  - \"Tagline\"\n  - \"A longer blurb.\"\n---\n";
+        let component =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap();
+        assert_eq!(
+            component.description.lines(),
+            &["Tagline".to_string(), "A longer blurb.".to_string()]
+        );
+        assert_eq!(component.description.primary(), "Tagline");
+        assert_eq!(component.description.continuation(), &["A longer blurb."]);
+        assert_eq!(component.description_source, DescriptionSource::FrontMatter);
+    }
+
+    #[test]
+    fn test_parse_component_content_description_string() {
+        let content = "---\ncategory: \"Testing\"\ndescription: \"Tagline\"\n---\n";
+        let component =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap();
+        assert_eq!(component.description.lines(), &["Tagline".to_string()]);
+        assert!(component.description.continuation().is_empty());
+    }
+
+    #[test]
+    fn test_parse_component_content_empty_description_list_falls_back() {
+        let content = "---\ncategory: \"Testing\"\ndescription: []\n---\n\nFrom content.\n";
+        let component =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap();
+        assert_eq!(component.description, "From content.");
+    }
+
+    fn parse_with_format(content: &str, format: &str) -> Result<Component> {
+        let config: Config =
+            toml::from_str(&format!("front_matter_format = \"{}\"", format)).unwrap();
+        parse_component_content_with(
+            content,
+            Path::new("/repo/README.md"),
+            Path::new("/repo"),
+            &config,
+        )
+    }
+
+    #[test]
+    fn test_parse_component_content_toml_front_matter() {
+        let content = "+++\ncategory = \"Core\"\ndescription = \"Core lib\"\n+++\n\n# Core\n";
+        let component = parse_with_format(content, "auto").unwrap();
+        assert_eq!(component.category, "Core");
+        assert_eq!(component.description, "Core lib");
+
+        // Forcing YAML parses the `+++` block as YAML, which this isn't
+        let err = parse_with_format(content, "yaml").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("invalid_front_matter")
+        );
+    }
+
+    #[test]
+    fn test_parse_component_content_forced_front_matter_format() {
+        let content = "---\ncategory = \"Core\"\ntags = [\"async\"]\n---\n\nFrom content.\n";
+        assert!(parse_with_format(content, "auto").is_err());
+        let component = parse_with_format(content, "toml").unwrap();
+        assert_eq!(component.category, "Core");
+        assert_eq!(component.tags, vec!["async"]);
+        assert_eq!(component.description, "From content.");
+
+        let content = "+++\ncategory: Core\ndescription: Core lib\n+++\n";
+        let component = parse_with_format(content, "yaml").unwrap();
+        assert_eq!(component.category, "Core");
+        assert_eq!(component.description, "Core lib");
+    }
+
+    #[test]
+    fn test_parse_component_content_toml_error_line() {
+        let content = "+++\ncategory = \"Core\"\ndescription = \n+++\n";
+        let err = parse_with_format(content, "auto").unwrap_err();
+        let err = err.downcast_ref::<ComponentError>().unwrap();
+        assert_eq!(err.kind(), "invalid_front_matter");
+        assert_eq!(err.line(), Some(3));
+    }
+
+    #[test]
+    fn test_component_json_round_trip() {
+        let content = "---\ncategory: \"Core\"\ndescription: [\"Core lib\", \"More\"]\n\
+                       tags: [\"async\"]\nowner: \"platform\"\n---\n\n# Core\n";
+        let mut component = parse_component_content(
+            content,
+            Path::new("/repo/core/README.md"),
+            Path::new("/repo"),
+        )
+        .unwrap();
+        component.link = Some("core.md".to_string());
+        component.modified = Some(SystemTime::UNIX_EPOCH);
+
+        let json = serde_json::to_string(&vec![component.clone()]).unwrap();
+        let parsed: Vec<Component> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![component]);
+
+        // Fields with defaults may be left out
+        let parsed: Component = serde_json::from_str(
+            r#"{"path": "a/README.md", "description": "A", "category": "Core"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.description, "A");
+        assert!(parsed.extra.is_empty());
+    }
+
+    #[test]
+    fn test_description_serializes_in_front_matter_form() {
+        let line = Description::from("Tagline");
+        assert_eq!(serde_json::to_string(&line).unwrap(), r#""Tagline""#);
+
+        let lines = Description::from(vec!["Tagline".to_string(), "Blurb".to_string()]);
+        assert_eq!(
+            serde_json::to_string(&lines).unwrap(),
+            r#"["Tagline","Blurb"]"#
+        );
+        assert_eq!(lines.primary(), "Tagline");
+        assert_eq!(lines.joined(), "Tagline Blurb");
+        assert_ne!(lines, "Tagline");
+    }
+
+    #[test]
+    fn test_parse_component_content_empty_category() {
+        let content = "---\ncategory: \"  \"\ndescription: \"Core\"\n---\n";
+        let err =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap_err();
+        assert_eq!(err.to_string(), "Empty category in: /repo/README.md");
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("empty_category")
+        );
+    }
+
+    #[test]
+    fn test_parse_component_content_deprecated_and_planned() {
+        let content = "---\ncategory: \"Core\"\ndescription: \"Core\"\nplanned: true\n---\n";
+        let component =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap();
+        assert!(component.planned && !component.deprecated);
+
+        let content = "---\ncategory: \"Core\"\ndeprecated: true\ndescription: \"Core\"\nplanned: true\n---\n";
+        let err =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "A component can't be both deprecated and planned: /repo/README.md"
+        );
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("deprecated_and_planned")
+        );
+    }
+
+    #[test]
+    fn test_parse_component_content_front_matter_schema() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["team"],
+            "properties": { "team": { "type": "string" } }
+        });
+        let config = Config {
+            front_matter_validator: Some(std::sync::Arc::new(
+                jsonschema::validator_for(&schema).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let parse = |content: &str| {
+            parse_component_content_with(
+                content,
+                Path::new("/repo/README.md"),
+                Path::new("/repo"),
+                &config,
+            )
+        };
+
+        let component =
+            parse("---\ncategory: \"Core\"\nteam: \"platform\"\n---\n\nCore.\n").unwrap();
+        assert_eq!(component.extra["team"], "platform");
+
+        let err = parse("---\ncategory: \"Core\"\n---\n\nCore.\n").unwrap_err();
+        let Some(ComponentError::SchemaViolation { violations, .. }) = err.downcast_ref() else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].pointer, "");
+        assert_eq!(
+            err.to_string(),
+            "Front matter doesn't match schema in: /repo/README.md: \"team\" is a required property"
+        );
+
+        let err = parse("---\ncategory: \"Core\"\nteam: 3\n---\n\nCore.\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Front matter doesn't match schema in: /repo/README.md: /team: 3 is not of type \"string\""
+        );
+
+        // A nested non-string key has no JSON equivalent to check
+        let content =
+            "---\ncategory: \"Core\"\nteam: \"platform\"\nlinks:\n  ? [a, b]\n  : pair\n---\n";
+        let err = parse(content).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("schema_violation"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_component_id() {
+        let component = Component {
+            path: ["crates", "core", "README.md"].iter().collect(),
+            ..Default::default()
+        };
+        assert_eq!(component.id(), "crates/core/README.md");
+        assert_eq!(component.clone().id(), component.id());
+    }
+
+    #[test]
+    fn test_parse_component_content_keeps_raw_category() {
+        let content = "---\ncategory: \"cli\"\ndescription: \"Entry point\"\n---\n";
+        let config: Config = toml::from_str("[aliases]\ncli = \"Interfaces\"").unwrap();
+        let component = parse_component_content_with(
+            content,
+            Path::new("/repo/README.md"),
+            Path::new("/repo"),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(component.category, "cli");
+        assert_eq!(component.raw_category.as_deref(), Some("cli"));
+        assert_eq!(config.canonical_category(&component.category), "Interfaces");
+    }
+
+    #[test]
+    fn test_parsed_category_resolves_aliases_once() {
+        let content = "---\ncategory: \"a\"\ndescription: \"Entry point\"\n---\n";
+        let config: Config = toml::from_str("[aliases]\na = \"b\"\nb = \"c\"").unwrap();
+        let component = parse_component_content_with(
+            content,
+            Path::new("/repo/README.md"),
+            Path::new("/repo"),
+            &config,
+        )
+        .unwrap();
+        assert!(crate::generate_document(&[component], &config).contains("\n## b\n"));
+    }
+
+    #[test]
+    fn test_parse_component_content_empty_category_default() {
+        let content = "---\ncategory: \"\"\ndescription: \"Core\"\n---\n";
+        let config: Config = toml::from_str(r#"default_category = "Misc""#).unwrap();
+        let component = parse_component_content_with(
+            content,
+            Path::new("/repo/README.md"),
+            Path::new("/repo"),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(component.category, "Misc");
+        assert_eq!(component.raw_category, None);
+
+        // Strict mode rejects it even with a default
+        let config: Config =
+            toml::from_str("default_category = \"Misc\"\nstrict_categories = true").unwrap();
+        assert!(parse_component_content_with(
+            content,
+            Path::new("/repo/README.md"),
+            Path::new("/repo"),
+            &config,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_component_content() {
+        let content = r#"---
+category: "Testing"
+---
+
+# Test Component
+
+From content."#;
+
+        let component = parse_component_content(
+            content,
+            Path::new("/repo/crates/test/README.md"),
+            Path::new("/repo"),
+        )
+        .unwrap();
+        assert_eq!(component.path, PathBuf::from("crates/test/README.md"));
+        assert_eq!(component.description, "From content.");
         assert_eq!(component.category, "Testing");
+        // The body is only kept for embedding
+        assert_eq!(component.body, None);
+
+        let config: Config = toml::from_str("embed_bodies = true").unwrap();
+        let component = parse_component_content_with(
+            content,
+            Path::new("/repo/crates/test/README.md"),
+            Path::new("/repo"),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            component.body.as_deref(),
+            Some("# Test Component\n\nFrom content.")
+        );
+    }
+
+    #[test]
+    fn test_truncate_fallback_description() {
+        let mut component = Component {
+            description: "One. Two. Three.".into(),
+            description_source: DescriptionSource::FirstParagraph,
+            ..Default::default()
+        };
+        component.truncate_fallback_description(2);
+        assert_eq!(component.description, "One. Two. …");
+
+        let mut component = Component {
+            description: "One. Two. Three.".into(),
+            ..Default::default()
+        };
+        component.truncate_fallback_description(1);
+        assert_eq!(component.description, "One. Two. Three.");
+    }
+
+    fn sources_config(sources: &str) -> Config {
+        toml::from_str(&format!("description_sources = {}", sources)).unwrap()
+    }
+
+    /// Writes a README with the given front matter, a first paragraph, and a
+    /// sibling Cargo.toml, returning the README path.
+    fn write_description_fixture(name: &str, front_matter: &str) -> PathBuf {
+        let dir = env::temp_dir().join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"core\"\ndescription = \"From Cargo.toml\"\n",
+        )
+        .unwrap();
+        let readme = dir.join("README.md");
+        fs::write(
+            &readme,
+            format!(
+                "---\n{}---\n\n# Core\n\nFrom the first paragraph.\n",
+                front_matter
+            ),
+        )
+        .unwrap();
+        readme
+    }
+
+    #[test]
+    fn test_description_sources_front_matter_wins() {
+        let readme = write_description_fixture(
+            "test_sources_front_matter",
+            "category: \"Testing\"\ndescription: \"From front matter\"\n",
+        );
+        let config = sources_config(r#"["front_matter", "first_paragraph", "cargo_toml"]"#);
+
+        let component = parse_component_with(readme.clone(), &env::temp_dir(), &config).unwrap();
+        assert_eq!(component.description, "From front matter");
+        assert_eq!(component.description_source, DescriptionSource::FrontMatter);
+
+        fs::remove_dir_all(readme.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_description_sources_first_paragraph_wins() {
+        let readme =
+            write_description_fixture("test_sources_first_paragraph", "category: \"Testing\"\n");
+        let config = sources_config(r#"["front_matter", "first_paragraph", "cargo_toml"]"#);
+
+        let component = parse_component_with(readme.clone(), &env::temp_dir(), &config).unwrap();
+        assert_eq!(component.description, "From the first paragraph.");
+        assert_eq!(
+            component.description_source,
+            DescriptionSource::FirstParagraph
+        );
+
+        fs::remove_dir_all(readme.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_trim_trailing_links_only_affects_first_paragraph() {
+        let config: Config = toml::from_str("trim_trailing_links = true").unwrap();
+        let parse = |front_matter: &str| {
+            let content = format!(
+                "---\ncategory: \"Core\"\n{}---\n\nParses input. [Guide](guide.md)\n",
+                front_matter
+            );
+            parse_component_content_with(&content, Path::new("README.md"), Path::new(""), &config)
+                .unwrap()
+                .description
+        };
+
+        assert_eq!(parse(""), "Parses input.");
+        assert_eq!(
+            parse("description: \"Core [docs](docs.md)\"\n"),
+            "Core [docs](docs.md)"
+        );
+    }
+
+    #[test]
+    fn test_description_sources_cargo_toml_wins() {
+        let readme = write_description_fixture(
+            "test_sources_cargo_toml",
+            "category: \"Testing\"\ndescription: \"From front matter\"\n",
+        );
+        let config = sources_config(r#"["cargo_toml", "front_matter"]"#);
+
+        let component = parse_component_with(readme.clone(), &env::temp_dir(), &config).unwrap();
+        assert_eq!(component.description, "From Cargo.toml");
+        assert_eq!(component.description_source, DescriptionSource::CargoToml);
+
+        fs::remove_dir_all(readme.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_description_sources_all_missing() {
+        let content = "---\ncategory: \"Testing\"\n---\n\n# Core\n\nA paragraph.\n";
+        let config = sources_config(r#"["front_matter", "cargo_toml"]"#);
+
+        let err = parse_component_content_with(
+            content,
+            Path::new("/nonexistent/README.md"),
+            Path::new("/nonexistent"),
+            &config,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("no_description")
+        );
+    }
+
+    #[test]
+    fn test_parse_components_preserves_input_order() {
+        let inputs: Vec<(String, PathBuf)> = (0..100)
+            .map(|i| {
+                let content = if i % 7 == 0 {
+                    "# No front matter".to_string()
+                } else {
+                    format!(
+                        "---\ncategory: \"Testing\"\ndescription: \"Component {}\"\n---\n",
+                        i
+                    )
+                };
+                (content, PathBuf::from(format!("/repo/c{}/README.md", i)))
+            })
+            .collect();
+
+        let results = parse_components(&inputs, Path::new("/repo"));
+        assert_eq!(results.len(), 100);
+        for (i, result) in results.iter().enumerate() {
+            match result {
+                Ok(component) => {
+                    assert_eq!(component.path, PathBuf::from(format!("c{}/README.md", i)));
+                    assert_eq!(component.description, format!("Component {}", i));
+                }
+                Err(_) => assert_eq!(i % 7, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_component_retains_extra_keys() {
+        let temp_dir = env::temp_dir();
+        let test_file = temp_dir.join("test_extra_keys.md");
+
+        let content = r#"---
+description: "Test component"
+category: "Testing"
+jira: "TEST"
+---
+
+# Test Component"#;
+
+        fs::write(&test_file, content).unwrap();
+
+        let component = parse_component(test_file.clone(), &temp_dir).unwrap();
+        assert_eq!(
+            component.extra.get("jira"),
+            Some(&serde_yaml::Value::from("TEST"))
+        );
 
         fs::remove_file(&test_file).ok();
     }
@@ -202,7 +1266,116 @@ This paragraph should be ignored."#;
 
         let component = result.unwrap();
         assert_eq!(component.description, "From front matter");
+        assert_eq!(component.description_source, DescriptionSource::FrontMatter);
 
         fs::remove_file(&test_file).ok();
     }
+
+    #[test]
+    fn test_parse_component_description_from_referenced_file() {
+        let temp_dir = env::temp_dir().join("test_description_from");
+        fs::create_dir_all(temp_dir.join("docs")).unwrap();
+        let test_file = temp_dir.join("README.md");
+
+        fs::write(
+            temp_dir.join("docs/overview.md"),
+            "# Overview\n\nDescribed in the overview doc.\n\nMore.",
+        )
+        .unwrap();
+        let content = r#"---
+category: "Testing"
+description_from: "docs/overview.md"
+---
+
+# Test Component
+
+This paragraph should be ignored."#;
+        fs::write(&test_file, content).unwrap();
+
+        let component = parse_component(test_file, &temp_dir).unwrap();
+        assert_eq!(component.description, "Described in the overview doc.");
+        assert_eq!(
+            component.description_source,
+            DescriptionSource::ReferencedFile
+        );
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_parse_components_reads_shared_description_from_once() {
+        let temp_dir = env::temp_dir().join("test_description_from_shared");
+        for dir in ["a", "b", "docs"] {
+            fs::create_dir_all(temp_dir.join(dir)).unwrap();
+        }
+        let shared = temp_dir.join("docs/shared.md");
+        fs::write(&shared, "Shared description.\n").unwrap();
+
+        let content = "---\ncategory: \"Testing\"\ndescription_from: \"../docs/shared.md\"\n---\n";
+        let cache = FileCache::default();
+        let parse = |name: &str| {
+            let path = temp_dir.join(name).join("README.md");
+            parse_component_content_cached(content, &path, &temp_dir, &Config::default(), &cache)
+                .unwrap()
+        };
+
+        assert_eq!(parse("a").description, "Shared description.");
+        // The second reference is served from the run's cache
+        fs::write(&shared, "Changed on disk.\n").unwrap();
+        assert_eq!(parse("b").description, "Shared description.");
+
+        fs::remove_dir_all(&temp_dir).ok();
+    }
+
+    #[test]
+    fn test_parse_component_inline_description_beats_description_from() {
+        let content = r#"---
+description: "Inline wins"
+category: "Testing"
+description_from: "missing.md"
+---
+
+# Test Component"#;
+
+        let component =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap();
+        assert_eq!(component.description, "Inline wins");
+        assert_eq!(component.description_source, DescriptionSource::FrontMatter);
+    }
+
+    #[test]
+    fn test_parse_component_description_from_missing_file() {
+        let content = r#"---
+category: "Testing"
+description_from: "missing.md"
+---
+
+# Test Component
+
+Fallback paragraph."#;
+
+        let err = parse_component_content(
+            content,
+            Path::new("/nonexistent/README.md"),
+            Path::new("/nonexistent"),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Failed to read description_from file /nonexistent/missing.md"));
+    }
+
+    #[test]
+    fn test_parse_component_invalid_front_matter_line() {
+        let content = "---\ncategory: \"Testing\"\ndependencies: [unclosed\n---\n\n# Test";
+
+        let err =
+            parse_component_content(content, Path::new("/repo/README.md"), Path::new("/repo"))
+                .unwrap_err();
+        let err = err.downcast_ref::<ComponentError>().unwrap();
+        assert_eq!(err.kind(), "invalid_front_matter");
+        assert_eq!(err.path(), Path::new("/repo/README.md"));
+        assert!(err.line().is_some());
+    }
 }
@@ -1,13 +1,32 @@
-use anyhow::{Context, Result};
-use serde::Deserialize;
+use anyhow::{bail, Context, Result};
+use globset::{GlobSet, GlobSetBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+use crate::discovery::compile_glob;
+use crate::front_matter::{extract_first_paragraph, extract_title};
 
 /// Default document title when none is specified in config
 pub(crate) const DEFAULT_TITLE: &str = "Architecture Documentation";
+pub(crate) const DEFAULT_SEPARATOR: &str = ": ";
+pub(crate) const DEFAULT_CATEGORY_SEPARATOR: &str = "/";
+pub(crate) const DEFAULT_DEPRECATED_TITLE: &str = "Deprecated";
+pub(crate) const DEFAULT_PLANNED_TITLE: &str = "Planned";
+/// Group key of the section deprecated components are collected under. No
+/// category can be spelled this way, so the section never merges with one.
+pub(crate) const DEPRECATED_SECTION: &str = "\u{0}deprecated";
+/// Group key of the section planned components are collected under, apart
+/// from every category like [`DEPRECATED_SECTION`].
+pub(crate) const PLANNED_SECTION: &str = "\u{0}planned";
 
 /// Configuration for the architecture documentation generator
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct Config {
     /// Document title (default: "Architecture Documentation")
@@ -18,10 +37,226 @@ pub struct Config {
 
     /// Ordered list of category configurations
     pub categories: Vec<CategoryConfig>,
+
+    /// How components are ordered within each category (default: "path")
+    pub sort: SortOrder,
+
+    /// Template for each component entry, e.g. "- `{path}`: {description}"
+    pub entry_template: Option<String>,
+
+    /// Text between the path and description of entries when no
+    /// `entry_template` is set, inserted as-is as raw markdown (default ": ").
+    /// Only the markdown and HTML outputs use it
+    pub separator: Option<String>,
+
+    /// Trailing filename, e.g. "README.md", to drop from displayed paths so
+    /// entries show the component's directory. Only the markdown and HTML
+    /// outputs use it; the others keep full paths
+    pub strip_filename: Option<String>,
+
+    /// Delimiter between the levels of a nested category, e.g. "/" to nest
+    /// `Backend/Storage` (default: none, so categories don't nest)
+    pub category_separator: Option<String>,
+
+    /// Maps raw front matter category values to a canonical category
+    pub aliases: BTreeMap<String, String>,
+
+    /// Reject components whose category is not declared in `categories`
+    pub strict_categories: bool,
+
+    /// Reject components whose description falls back to their first
+    /// paragraph instead of coming from front matter
+    pub require_front_matter_description: bool,
+
+    /// How categories are ordered in the document (default: "config")
+    pub order: CategoryOrder,
+
+    /// Match front matter categories to configured ones, and group them with
+    /// each other, ignoring case
+    pub case_insensitive_categories: bool,
+
+    /// Embed each component's README body in a `<details>` block under its entry
+    pub embed_bodies: bool,
+
+    /// Demote headings in embedded bodies so they nest under the category heading
+    pub demote_headings: bool,
+
+    /// Sort paths with numeric runs compared by value, e.g. `module2` before `module10`
+    pub natural_sort: bool,
+
+    /// Sort paths ignoring case, e.g. `apache` before `Zlib`, breaking ties on the exact path
+    pub case_insensitive_paths: bool,
+
+    /// Number of levels to shift all generated headings down by (default: 0)
+    pub heading_offset: usize,
+
+    /// Truncate first-paragraph fallback descriptions to this many sentences
+    pub description_sentences: Option<usize>,
+
+    /// Drop links and images that end a first-paragraph fallback description
+    pub trim_trailing_links: bool,
+
+    /// Add a sentence counting components and categories before the first category
+    pub overview: bool,
+
+    /// Skeleton template for the whole document, relative to the config file
+    pub template_file: Option<PathBuf>,
+
+    /// Contents of `template_file`, read when the config is loaded
+    #[serde(skip)]
+    pub template: Option<String>,
+
+    /// Sources to take a component's description from, tried in order
+    /// (default: front_matter, first_paragraph)
+    pub description_sources: Vec<DescriptionFallback>,
+
+    /// Whether to list the categories as a table of contents before them
+    pub toc: bool,
+
+    /// Whether to emit an explicit anchor for each category heading and point
+    /// links at it, instead of relying on renderer-generated heading slugs
+    pub explicit_anchors: bool,
+
+    /// Syntax of the anchors emitted by `explicit_anchors`
+    pub anchor_style: AnchorStyle,
+
+    /// Config file (relative to this one) to load first and layer this one on top of
+    pub extends: Option<PathBuf>,
+
+    /// Glob patterns for matched files to skip
+    pub exclude: Vec<String>,
+
+    /// Whether to wrap each category in a collapsible `<details>` block
+    pub collapsible: bool,
+
+    /// Whether collapsible categories start closed instead of open
+    pub collapsed_by_default: bool,
+
+    /// Category for components whose front matter `category` is empty (ignored
+    /// with `strict_categories`, where an empty category is an error)
+    pub default_category: Option<String>,
+
+    /// Whether to show each component's tags after its description
+    pub show_tags: bool,
+
+    /// Legend descriptions of statuses, keyed by status
+    pub statuses: BTreeMap<String, String>,
+
+    /// Legend descriptions of tags, keyed by tag
+    pub tag_descriptions: BTreeMap<String, String>,
+
+    /// Where to place the legend of the statuses and tags shown in the document
+    pub legend: LegendPosition,
+
+    /// Whether to end the document with an index of every component by title
+    pub index: bool,
+
+    /// Whether to show the full path of nested categories, e.g.
+    /// "Backend › Storage", under their headings and in the index
+    pub breadcrumbs: bool,
+
+    /// Layout of the index: one list, or grouped by first letter
+    pub index_style: IndexStyle,
+
+    /// Syntax to parse front matter as, regardless of its delimiter (default: by delimiter)
+    pub front_matter_format: FrontMatterFormat,
+
+    /// Additional glob patterns to document, each with its own settings
+    pub sources: Vec<SourceConfig>,
+
+    /// Whether to document deprecated components at all (default: true)
+    pub show_deprecated: Option<bool>,
+
+    /// Title of the section deprecated components are collected under, after
+    /// every category (default: "Deprecated")
+    pub deprecated_title: Option<String>,
+
+    /// Whether to document planned components, which don't exist yet
+    /// (default: false)
+    pub show_planned: Option<bool>,
+
+    /// Title of the section planned components are collected under, after
+    /// every category but before deprecated components (default: "Planned")
+    pub planned_title: Option<String>,
+
+    /// Author named at the top of the Atom feed, which every entry inherits
+    /// (default: the document title)
+    pub feed_author: Option<String>,
+
+    /// What the document's sections are: categories, release cohorts by
+    /// each component's `since` version, or owners (default: "category")
+    pub group_by: GroupBy,
+
+    /// Owners whose sections come first with `group_by = "owner"`, in this
+    /// order; other owners follow alphabetically
+    pub owner_order: Vec<String>,
+
+    /// Whether generated output ends with a newline; it never ends with more
+    /// than one (default: true)
+    pub final_newline: Option<bool>,
+
+    /// Project README, relative to the config file, whose `# heading` and first
+    /// paragraph are the title and description when the config sets neither
+    pub inherit_from_readme: Option<PathBuf>,
+
+    /// JSON Schema file, relative to the config file, that every component's
+    /// front matter must match
+    pub front_matter_schema: Option<PathBuf>,
+
+    /// Compiled `front_matter_schema`, built when the config is loaded
+    #[serde(skip)]
+    pub(crate) front_matter_validator: Option<Arc<jsonschema::Validator>>,
+
+    /// Compiled `[[sources]]` patterns, built on first use
+    #[serde(skip)]
+    pub(crate) source_matcher: OnceLock<SourceMatcher>,
+}
+
+/// The `[[sources]]` patterns compiled into one set, with the index of the
+/// source each glob came from. Invalid patterns are left out; globbing them
+/// reports the error.
+#[derive(Debug, Clone)]
+pub(crate) struct SourceMatcher {
+    globs: GlobSet,
+    sources: Vec<usize>,
+}
+
+impl SourceMatcher {
+    fn new(sources: &[SourceConfig]) -> Self {
+        let mut globs = GlobSetBuilder::new();
+        let mut indices = Vec::new();
+        for (i, source) in sources.iter().enumerate() {
+            if let Ok(glob) = compile_glob(&source.pattern) {
+                globs.add(glob);
+                indices.push(i);
+            }
+        }
+        SourceMatcher {
+            globs: globs.build().unwrap_or_else(|_| GlobSet::empty()),
+            sources: indices,
+        }
+    }
+
+    /// The index of the first source whose pattern matches `path`.
+    fn first_match(&self, path: &Path) -> Option<usize> {
+        let first = self.globs.matches(path).into_iter().min()?;
+        Some(self.sources[first])
+    }
+}
+
+/// A glob pattern of component files with settings for the files it matches
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceConfig {
+    /// Glob pattern matching markdown files, like the `generate` pattern but
+    /// relative to the config file (required)
+    pub pattern: String,
+
+    /// Overrides the document-level `front_matter_format` for matched files
+    pub front_matter_format: Option<FrontMatterFormat>,
 }
 
 /// Configuration for a single category
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CategoryConfig {
     /// Category name as it appears in front matter (required)
     pub category: String,
@@ -31,20 +266,488 @@ pub struct CategoryConfig {
 
     /// Description rendered under the category heading
     pub description: Option<String>,
+
+    /// Overrides the document-level `sort` for this category
+    pub sort: Option<SortOrder>,
+
+    /// Pins the category to the top or bottom of the document
+    pub pin: Option<Pin>,
+
+    /// Overrides the document-level `collapsible` for this category
+    pub collapsible: Option<bool>,
+
+    /// Overrides the document-level `entry_template` for this category
+    pub entry_template: Option<String>,
+
+    /// Whether components in this category must set `description` in front
+    /// matter, rather than fall back to their first paragraph
+    #[serde(default)]
+    pub require_description: bool,
+
+    /// Any other keys, e.g. an owning team, retained for use in templates
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, toml::Value>,
+}
+
+/// The keys of a `[[categories]]` entry that [`CategoryConfig`] models, for
+/// telling typos of them apart from extra keys.
+pub(crate) const CATEGORY_KEYS: &[&str] = &[
+    "category",
+    "title",
+    "description",
+    "sort",
+    "pin",
+    "collapsible",
+    "entry_template",
+    "require_description",
+];
+
+/// Position a category is pinned to, regardless of the category order
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Pin {
+    /// Before all unpinned categories
+    Top,
+    /// After all unpinned categories, including the alphabetical tail
+    Bottom,
+}
+
+/// A source for a component's description
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DescriptionFallback {
+    /// The front matter `description`, or the file named by `description_from`
+    FrontMatter,
+    /// The first paragraph of the README content
+    FirstParagraph,
+    /// `package.description` in a `Cargo.toml` next to the README
+    CargoToml,
+}
+
+/// Description sources used when `description_sources` is empty
+const DEFAULT_DESCRIPTION_SOURCES: [DescriptionFallback; 2] = [
+    DescriptionFallback::FrontMatter,
+    DescriptionFallback::FirstParagraph,
+];
+
+/// Syntax of an explicit heading anchor
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorStyle {
+    /// An `<a name="slug"></a>` line before the heading
+    #[default]
+    Html,
+    /// A `{#slug}` attribute after the heading text
+    Attribute,
+}
+
+/// Syntax of the front matter block
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontMatterFormat {
+    /// TOML between `+++` lines, YAML otherwise
+    #[default]
+    Auto,
+    /// YAML, whatever the delimiter
+    Yaml,
+    /// TOML, whatever the delimiter
+    Toml,
+}
+
+impl FromStr for FrontMatterFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(FrontMatterFormat::Auto),
+            "yaml" => Ok(FrontMatterFormat::Yaml),
+            "toml" => Ok(FrontMatterFormat::Toml),
+            _ => Err(format!(
+                "unknown front matter format '{}', expected one of: auto, yaml, toml",
+                s
+            )),
+        }
+    }
+}
+
+/// Placement of the legend in the document
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LegendPosition {
+    /// Before the first category
+    Top,
+    /// After the last category
+    #[default]
+    Bottom,
+}
+
+/// Layout of the component index
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexStyle {
+    /// A single list sorted by title
+    #[default]
+    List,
+    /// Grouped under a heading per first letter, with a bar linking to each
+    Alphabetical,
+}
+
+/// Ordering of categories in the document
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CategoryOrder {
+    /// Config-listed categories first, then remaining ones alphabetically
+    #[default]
+    Config,
+    /// Categories with the most components first, ties alphabetically
+    Count,
+}
+
+/// How components are grouped into the document's sections
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// A section per category
+    #[default]
+    Category,
+    /// A section per `since` version, newest first, then unversioned components
+    Release,
+    /// A section per owner, then unowned components
+    Owner,
+}
+
+/// Ordering of components within a category
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Sort components by path
+    #[default]
+    Path,
+    /// Sort components so each appears after its dependencies, then by path
+    Dependencies,
+}
+
+/// Where a loaded [`Config`] came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The config file at this path
+    File(PathBuf),
+    /// The built-in defaults, as no config file exists
+    Default,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::File(path) => write!(f, "{}", path.display()),
+            ConfigSource::Default => f.write_str("built-in defaults"),
+        }
+    }
 }
 
 impl Config {
     /// Load config from a TOML file, returns default config if file doesn't exist
     pub fn load(path: &Path) -> Result<Self> {
+        Self::load_profile(path, None)
+    }
+
+    /// Load config like [`Config::load`], also returning whether it came from
+    /// the file or, as the file doesn't exist, from the defaults
+    ///
+    /// This tells a missing config file apart from an empty one.
+    pub fn load_or(path: &Path) -> Result<(Self, ConfigSource)> {
+        Self::load_profile_or(path, None)
+    }
+
+    /// Load config from a TOML file, applying a named profile on top of the base config
+    ///
+    /// Each `[profiles.<name>]` table overrides the top-level keys it sets; keys
+    /// like `categories` are replaced wholesale rather than merged. Without a
+    /// profile this behaves like [`Config::load`].
+    pub fn load_profile(path: &Path, profile: Option<&str>) -> Result<Self> {
+        Self::load_profile_or(path, profile).map(|(config, _)| config)
+    }
+
+    /// Load config like [`Config::load_profile`], also returning where it came from
+    pub fn load_profile_or(path: &Path, profile: Option<&str>) -> Result<(Self, ConfigSource)> {
         if !path.exists() {
-            return Ok(Config::default());
+            if let Some(profile) = profile {
+                bail!(
+                    "Unknown profile '{}': config file not found: {}",
+                    profile,
+                    path.display()
+                );
+            }
+            return Ok((Config::default(), ConfigSource::Default));
+        }
+
+        let mut table = Self::load_table(path, &mut vec![canonical_config_path(path)?])?;
+        interpolate_env(&mut table)
+            .with_context(|| format!("Failed to load config file: {}", path.display()))?;
+        let mut config = Self::from_table_profile(table, profile)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        let config_dir = path.parent().unwrap_or(Path::new(""));
+        for source in &mut config.sources {
+            source.pattern = config_dir.join(&source.pattern).display().to_string();
+        }
+        if let Some(template_file) = &config.template_file {
+            let template_file = config_dir.join(template_file);
+            config.template = Some(fs::read_to_string(&template_file).with_context(|| {
+                format!("Failed to read template file: {}", template_file.display())
+            })?);
         }
 
+        if let Some(schema_file) = &config.front_matter_schema {
+            let schema_file = config_dir.join(schema_file);
+            config.front_matter_validator = Some(Arc::new(load_schema(&schema_file)?));
+        }
+
+        if let Some(readme) = &config.inherit_from_readme {
+            let readme = config_dir.join(readme);
+            let content = fs::read_to_string(&readme).with_context(|| {
+                format!(
+                    "Failed to read inherit_from_readme file: {}",
+                    readme.display()
+                )
+            })?;
+            if config.title.is_none() {
+                config.title = extract_title(&content);
+            }
+            if config.description.is_none() {
+                config.description = extract_first_paragraph(&content);
+            }
+        }
+
+        Ok((config, ConfigSource::File(path.to_path_buf())))
+    }
+
+    /// Read a config file as a TOML table, layered over the file named by its
+    /// `extends` key (relative to the file), recursively
+    ///
+    /// `chain` holds the canonical paths of the files being loaded, outermost
+    /// first, to detect `extends` cycles.
+    fn load_table(path: &Path, chain: &mut Vec<PathBuf>) -> Result<toml::Table> {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let table: toml::Table = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        let Some(extends) = table.get("extends") else {
+            return Ok(table);
+        };
+        let Some(extends) = extends.as_str() else {
+            bail!("`extends` must be a file path in: {}", path.display());
+        };
+
+        let base_path =
+            canonical_config_path(&path.parent().unwrap_or(Path::new("")).join(extends))?;
+        if chain.contains(&base_path) {
+            let cycle: Vec<_> = chain
+                .iter()
+                .chain([&base_path])
+                .map(|p| p.display().to_string())
+                .collect();
+            bail!("Config extends cycle: {}", cycle.join(" -> "));
+        }
+
+        chain.push(base_path.clone());
+        let mut base = Self::load_table(&base_path, chain)?;
+        chain.pop();
+
+        // Keep the base's file paths relative to the base file, not this one
+        let base_dir = base_path.parent().unwrap_or(Path::new(""));
+        for key in [
+            "template_file",
+            "inherit_from_readme",
+            "front_matter_schema",
+        ] {
+            if let Some(toml::Value::String(file)) = base.get_mut(key) {
+                *file = base_dir.join(&*file).display().to_string();
+            }
+        }
+        if let Some(toml::Value::Array(sources)) = base.get_mut("sources") {
+            for source in sources {
+                if let Some(toml::Value::String(pattern)) = source.get_mut("pattern") {
+                    *pattern = base_dir.join(&*pattern).display().to_string();
+                }
+            }
+        }
+
+        merge_tables(&mut base, table);
+        Ok(base)
+    }
+
+    /// Parse config from TOML content, applying a named profile if given
+    #[cfg(test)]
+    fn from_toml_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        Self::from_table_profile(toml::from_str(content)?, profile)
+    }
+
+    /// Build config from a TOML table, applying a named profile if given
+    fn from_table_profile(mut table: toml::Table, profile: Option<&str>) -> Result<Self> {
+        let profiles = match table.remove("profiles") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => bail!("`profiles` must be a table of named profiles"),
+            None => toml::Table::new(),
+        };
+
+        if let Some(name) = profile {
+            match profiles.get(name) {
+                Some(toml::Value::Table(overrides)) => table.extend(overrides.clone()),
+                Some(_) => bail!("Profile '{}' must be a table", name),
+                None => {
+                    let names: Vec<_> = profiles.keys().map(String::as_str).collect();
+                    bail!(
+                        "Unknown profile '{}', expected one of: {}",
+                        name,
+                        names.join(", ")
+                    );
+                }
+            }
+        }
+
+        Ok(table.try_into()?)
+    }
+
+    /// Override a top-level config field by key, e.g. from `--set title=...`
+    ///
+    /// Boolean fields accept `true`/`false`, enum fields accept their config
+    /// spellings, and string fields take the value verbatim.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "title" => self.title = Some(value.to_string()),
+            "description" => self.description = Some(value.to_string()),
+            "entry_template" => self.entry_template = Some(value.to_string()),
+            "separator" => self.separator = Some(value.to_string()),
+            "strip_filename" => self.strip_filename = Some(value.to_string()),
+            "category_separator" => self.category_separator = Some(value.to_string()),
+            "sort" => self.sort = parse_value(key, value)?,
+            "order" => self.order = parse_value(key, value)?,
+            "strict_categories" => self.strict_categories = parse_value(key, value)?,
+            "require_front_matter_description" => {
+                self.require_front_matter_description = parse_value(key, value)?
+            }
+            "case_insensitive_categories" => {
+                self.case_insensitive_categories = parse_value(key, value)?
+            }
+            "embed_bodies" => self.embed_bodies = parse_value(key, value)?,
+            "demote_headings" => self.demote_headings = parse_value(key, value)?,
+            "natural_sort" => self.natural_sort = parse_value(key, value)?,
+            "case_insensitive_paths" => self.case_insensitive_paths = parse_value(key, value)?,
+            "heading_offset" => self.heading_offset = parse_value(key, value)?,
+            "description_sentences" => self.description_sentences = Some(parse_value(key, value)?),
+            "trim_trailing_links" => self.trim_trailing_links = parse_value(key, value)?,
+            "toc" => self.toc = parse_value(key, value)?,
+            "explicit_anchors" => self.explicit_anchors = parse_value(key, value)?,
+            "anchor_style" => self.anchor_style = parse_value(key, value)?,
+            "show_tags" => self.show_tags = parse_value(key, value)?,
+            "legend" => self.legend = parse_value(key, value)?,
+            "index" => self.index = parse_value(key, value)?,
+            "breadcrumbs" => self.breadcrumbs = parse_value(key, value)?,
+            "index_style" => self.index_style = parse_value(key, value)?,
+            "front_matter_format" => self.front_matter_format = parse_value(key, value)?,
+            "collapsible" => self.collapsible = parse_value(key, value)?,
+            "collapsed_by_default" => self.collapsed_by_default = parse_value(key, value)?,
+            "default_category" => self.default_category = Some(value.to_string()),
+            "show_deprecated" => self.show_deprecated = Some(parse_value(key, value)?),
+            "group_by" => self.group_by = parse_value(key, value)?,
+            "final_newline" => self.final_newline = Some(parse_value(key, value)?),
+            "deprecated_title" => self.deprecated_title = Some(value.to_string()),
+            "show_planned" => self.show_planned = Some(parse_value(key, value)?),
+            "planned_title" => self.planned_title = Some(value.to_string()),
+            "feed_author" => self.feed_author = Some(value.to_string()),
+            _ => bail!("Unknown config key: {}", key),
+        }
+        Ok(())
+    }
+
+    /// Get the description sources to try in order, with fallback to the default chain
+    pub(crate) fn description_sources(&self) -> &[DescriptionFallback] {
+        if self.description_sources.is_empty() {
+            &DEFAULT_DESCRIPTION_SOURCES
+        } else {
+            &self.description_sources
+        }
+    }
 
-        toml::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))
+    /// Get the entry separator, with fallback to default
+    pub(crate) fn separator(&self) -> &str {
+        self.separator.as_deref().unwrap_or(DEFAULT_SEPARATOR)
+    }
+
+    /// Get the category nesting delimiter to join levels with, with fallback
+    /// to default when categories don't nest
+    pub(crate) fn category_separator(&self) -> &str {
+        self.category_separator
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .unwrap_or(DEFAULT_CATEGORY_SEPARATOR)
+    }
+
+    /// Split a category into its nesting levels, outermost first, each trimmed
+    ///
+    /// A category without the separator is a single level, as is every
+    /// category unless `category_separator` is set.
+    pub(crate) fn category_segments<'a>(&self, name: &'a str) -> Vec<&'a str> {
+        let Some(separator) = self.category_separator.as_deref().filter(|s| !s.is_empty()) else {
+            return vec![name.trim()];
+        };
+        let segments: Vec<&str> = name
+            .split(separator)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        if segments.is_empty() {
+            vec![name.trim()]
+        } else {
+            segments
+        }
+    }
+
+    /// Get the front matter syntax for a file: that of the first source whose
+    /// pattern matches the path, falling back to the document-level setting
+    pub(crate) fn front_matter_format_for(&self, path: &Path) -> FrontMatterFormat {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        self.source_matcher
+            .get_or_init(|| SourceMatcher::new(&self.sources))
+            .first_match(path)
+            .and_then(|i| self.sources[i].front_matter_format)
+            .unwrap_or(self.front_matter_format)
+    }
+
+    /// Whether deprecated components are documented, with fallback to default
+    pub(crate) fn show_deprecated(&self) -> bool {
+        self.show_deprecated.unwrap_or(true)
+    }
+
+    /// Whether planned components are documented, with fallback to default
+    pub(crate) fn show_planned(&self) -> bool {
+        self.show_planned.unwrap_or(false)
+    }
+
+    /// Whether generated output ends with a newline, with fallback to default
+    pub(crate) fn final_newline(&self) -> bool {
+        self.final_newline.unwrap_or(true)
+    }
+
+    /// Get the title of the deprecated section, with fallback to default
+    pub(crate) fn deprecated_title(&self) -> &str {
+        self.deprecated_title
+            .as_deref()
+            .unwrap_or(DEFAULT_DEPRECATED_TITLE)
+    }
+
+    /// Get the title of the planned section, with fallback to default
+    pub(crate) fn planned_title(&self) -> &str {
+        self.planned_title
+            .as_deref()
+            .unwrap_or(DEFAULT_PLANNED_TITLE)
+    }
+
+    /// Get the Atom feed's author, with fallback to the document title
+    pub(crate) fn feed_author(&self) -> &str {
+        self.feed_author.as_deref().unwrap_or_else(|| self.title())
     }
 
     /// Get the document title, with fallback to default
@@ -52,16 +755,85 @@ impl Config {
         self.title.as_deref().unwrap_or(DEFAULT_TITLE)
     }
 
-    /// Get category config by name
+    /// Resolve a front matter category to its canonical name
+    ///
+    /// Surrounding whitespace is trimmed and `aliases` applied; a name matching
+    /// a configured category resolves to that category's spelling.
+    pub fn canonical_category<'a>(&'a self, name: &'a str) -> &'a str {
+        let name = name.trim();
+        let name = self.aliases.get(name).map(String::as_str).unwrap_or(name);
+        self.get_category(name)
+            .map(|c| c.category.as_str())
+            .unwrap_or(name)
+    }
+
+    /// Whether a front matter category (after alias resolution) is declared in `categories`
+    pub fn is_known_category(&self, name: &str) -> bool {
+        self.get_category(self.canonical_category(name)).is_some()
+    }
+
+    /// Get category config by name, ignoring whitespace around the name and
+    /// its nesting levels (and case, with `case_insensitive_categories`)
     pub(crate) fn get_category(&self, name: &str) -> Option<&CategoryConfig> {
-        self.categories.iter().find(|c| c.category == name)
+        let name = self.category_segments(name);
+        self.categories.iter().find(|c| {
+            let category = self.category_segments(&c.category);
+            category.len() == name.len()
+                && category.iter().zip(&name).all(|(a, b)| {
+                    if self.case_insensitive_categories {
+                        a.to_lowercase() == b.to_lowercase()
+                    } else {
+                        a == b
+                    }
+                })
+        })
     }
 
-    /// Get display title for a category, falling back to the raw category name
+    /// Get display title for a category, falling back to the trimmed category name
+    /// (the innermost level of a nested one)
     pub(crate) fn display_title_for<'a>(&'a self, category_name: &'a str) -> &'a str {
+        match category_name {
+            DEPRECATED_SECTION => return self.deprecated_title(),
+            PLANNED_SECTION => return self.planned_title(),
+            _ => {}
+        }
         self.get_category(category_name)
             .and_then(|c| c.title.as_deref())
-            .unwrap_or(category_name)
+            .unwrap_or_else(|| {
+                self.category_segments(category_name)
+                    .last()
+                    .copied()
+                    .unwrap_or_default()
+            })
+    }
+
+    /// Whether a category is wrapped in a `<details>` block, falling back to
+    /// the document-level setting
+    pub(crate) fn is_collapsible(&self, category_name: &str) -> bool {
+        self.get_category(category_name)
+            .and_then(|c| c.collapsible)
+            .unwrap_or(self.collapsible)
+    }
+
+    /// Get the entry template for a front matter category, falling back to the
+    /// document-level template
+    pub(crate) fn entry_template_for(&self, name: &str) -> Option<&str> {
+        self.get_category(self.canonical_category(name))
+            .and_then(|c| c.entry_template.as_deref())
+            .or(self.entry_template.as_deref())
+    }
+
+    /// Get an extra key set on a front matter category's `[[categories]]` entry
+    pub(crate) fn category_extra(&self, name: &str, key: &str) -> Option<&toml::Value> {
+        self.get_category(self.canonical_category(name))
+            .and_then(|c| c.extra.get(key))
+    }
+
+    /// Get the component sort order for a category, falling back to the document-level order
+    pub(crate) fn sort_for(&self, category_name: &str) -> SortOrder {
+        self.get_category(category_name)
+            .and_then(|c| c.sort)
+            .unwrap_or(self.sort)
     }
 
     /// Get ordered list of category names from config
@@ -73,6 +845,115 @@ impl Config {
     }
 }
 
+/// Reads and compiles the JSON Schema at `path`.
+fn load_schema(path: &Path) -> Result<jsonschema::Validator> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read front matter schema: {}", path.display()))?;
+    let schema: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse front matter schema: {}", path.display()))?;
+    jsonschema::validator_for(&schema)
+        .with_context(|| format!("Invalid front matter schema: {}", path.display()))
+}
+
+/// Canonicalizes a config file path, failing with a read error if it doesn't exist
+fn canonical_config_path(path: &Path) -> Result<PathBuf> {
+    fs::canonicalize(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))
+}
+
+/// Layers `overlay` over `base`: tables merge key by key, `categories` merge
+/// by their `category` name, and any other value in `overlay` replaces the base's
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(table)) => {
+                merge_tables(base_table, table)
+            }
+            (Some(toml::Value::Array(base_categories)), toml::Value::Array(categories))
+                if key == "categories" =>
+            {
+                merge_categories(base_categories, categories)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Merges `overlay` categories into `base`: a category already in `base` takes
+/// the overlay's fields over its own, and new categories are appended in order
+fn merge_categories(base: &mut Vec<toml::Value>, overlay: Vec<toml::Value>) {
+    for category in overlay {
+        let name = category.get("category").cloned();
+        let existing = base
+            .iter_mut()
+            .find(|c| name.is_some() && c.get("category") == name.as_ref());
+        match (existing, category) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(category)) => {
+                existing.extend(category)
+            }
+            (_, category) => base.push(category),
+        }
+    }
+}
+
+/// Replaces `${NAME}` in every string value of `table`, at any depth, with the
+/// environment variable `NAME`, failing if it isn't set
+fn interpolate_env(table: &mut toml::Table) -> Result<()> {
+    fn visit(value: &mut toml::Value) -> Result<()> {
+        match value {
+            toml::Value::String(s) => *s = expand_env(s)?,
+            toml::Value::Array(values) => values.iter_mut().try_for_each(visit)?,
+            toml::Value::Table(table) => table.iter_mut().try_for_each(|(_, v)| visit(v))?,
+            _ => {}
+        }
+        Ok(())
+    }
+    table.iter_mut().try_for_each(|(_, value)| visit(value))
+}
+
+/// Expands `${NAME}` references in `text` from the environment; `$${` is an
+/// escaped, literal `${`
+fn expand_env(text: &str) -> Result<String> {
+    let mut expanded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if let Some(before) = rest[..start].strip_suffix('$') {
+            expanded.push_str(before);
+            expanded.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let value = std::env::var(name).with_context(|| {
+            format!(
+                "Environment variable {} referenced in config is not set",
+                name
+            )
+        })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        rest = &rest[start + 3 + len..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Parses a `--set` value for a non-string field, accepting bare or quoted strings.
+fn parse_value<T: DeserializeOwned>(key: &str, value: &str) -> Result<T> {
+    let parsed = value
+        .parse::<toml::Value>()
+        .unwrap_or_else(|_| toml::Value::String(value.to_string()));
+
+    parsed
+        .try_into()
+        .with_context(|| format!("Invalid value for config key '{}': {}", key, value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,9 +1048,444 @@ category = "a-first"
         assert_eq!(order, vec!["z-last", "a-first"]);
     }
 
+    #[test]
+    fn test_sort_for_category_override() {
+        let toml = r#"
+sort = "dependencies"
+
+[[categories]]
+category = "core"
+sort = "path"
+
+[[categories]]
+category = "api"
+"#;
+        let config = config_from_str(toml).unwrap();
+        assert_eq!(config.sort_for("core"), SortOrder::Path);
+        assert_eq!(config.sort_for("api"), SortOrder::Dependencies);
+        assert_eq!(config.sort_for("unknown"), SortOrder::Dependencies);
+    }
+
+    #[test]
+    fn test_canonical_category() {
+        let toml = r#"
+[aliases]
+cli = "CLI"
+Cli = "CLI"
+"#;
+        let config = config_from_str(toml).unwrap();
+        assert_eq!(config.canonical_category("cli"), "CLI");
+        assert_eq!(config.canonical_category("Cli"), "CLI");
+        assert_eq!(config.canonical_category("CLI"), "CLI");
+        assert_eq!(config.canonical_category("core"), "core");
+    }
+
+    #[test]
+    fn test_is_known_category() {
+        let toml = r#"
+[aliases]
+cli = "CLI"
+
+[[categories]]
+category = "CLI"
+"#;
+        let config = config_from_str(toml).unwrap();
+        assert!(config.is_known_category("CLI"));
+        assert!(config.is_known_category("cli"));
+        assert!(!config.is_known_category("core"));
+    }
+
+    #[test]
+    fn test_category_matching_nested_levels() {
+        for separator in ["/", ".", "::"] {
+            let toml = format!(
+                "category_separator = \"{0}\"\n\n[[categories]]\ncategory = \"Backend{0}Storage\"\n",
+                separator
+            );
+            let config = config_from_str(&toml).unwrap();
+            let spaced = format!("Backend {} Storage", separator);
+            assert_eq!(
+                config.canonical_category(&spaced),
+                format!("Backend{}Storage", separator)
+            );
+            assert_eq!(config.display_title_for(&spaced), "Storage");
+            assert_eq!(
+                config.category_segments(&format!("{0}Backend{0}Api{0}", separator)),
+                vec!["Backend", "Api"]
+            );
+            assert!(config.get_category("Backend").is_none());
+        }
+        assert_eq!(
+            Config::default().category_segments("Backend.Storage"),
+            vec!["Backend.Storage"]
+        );
+    }
+
+    #[test]
+    fn test_category_matching_trims_whitespace() {
+        let toml = r#"
+[[categories]]
+category = "core"
+title = "Core Systems"
+"#;
+        let config = config_from_str(toml).unwrap();
+        assert_eq!(config.canonical_category(" core "), "core");
+        assert_eq!(config.display_title_for(" core "), "Core Systems");
+        assert_eq!(config.display_title_for(" other "), "other");
+        assert!(config.get_category("Core").is_none());
+    }
+
+    #[test]
+    fn test_category_matching_case_insensitive() {
+        let toml = r#"
+case_insensitive_categories = true
+
+[[categories]]
+category = "core"
+title = "Core Systems"
+"#;
+        let config = config_from_str(toml).unwrap();
+        assert_eq!(config.canonical_category(" Core "), "core");
+        assert_eq!(config.display_title_for("CORE"), "Core Systems");
+        assert!(config.is_known_category("Core"));
+    }
+
+    #[test]
+    fn test_set_title() {
+        let mut config = Config::default();
+        config.set("title", "Nightly Arch").unwrap();
+        assert_eq!(config.title(), "Nightly Arch");
+    }
+
+    #[test]
+    fn test_set_boolean() {
+        let mut config = Config::default();
+        config.set("strict_categories", "true").unwrap();
+        assert!(config.strict_categories);
+        config.set("strict_categories", "false").unwrap();
+        assert!(!config.strict_categories);
+        assert!(config.set("strict_categories", "yes").is_err());
+    }
+
+    #[test]
+    fn test_set_enum() {
+        let mut config = Config::default();
+        config.set("order", "count").unwrap();
+        assert_eq!(config.order, CategoryOrder::Count);
+        assert!(config.set("order", "random").is_err());
+    }
+
+    #[test]
+    fn test_set_unknown_key() {
+        let mut config = Config::default();
+        let err = config.set("titel", "Oops").unwrap_err();
+        assert_eq!(err.to_string(), "Unknown config key: titel");
+    }
+
+    #[test]
+    fn test_category_pin() {
+        let toml = r#"
+[[categories]]
+category = "Deprecated"
+pin = "bottom"
+
+[[categories]]
+category = "core"
+"#;
+        let config = config_from_str(toml).unwrap();
+        assert_eq!(config.categories[0].pin, Some(Pin::Bottom));
+        assert_eq!(config.categories[1].pin, None);
+        assert!(config_from_str("[[categories]]\ncategory = \"x\"\npin = \"middle\"").is_err());
+    }
+
+    const PROFILES_TOML: &str = r#"
+title = "Architecture"
+
+[[categories]]
+category = "core"
+
+[profiles.internal]
+title = "Internal Architecture"
+
+[[profiles.internal.categories]]
+category = "core"
+
+[[profiles.internal.categories]]
+category = "ops"
+title = "Operations"
+
+[profiles.public]
+"#;
+
+    #[test]
+    fn test_profile_base_config() {
+        let config = Config::from_toml_profile(PROFILES_TOML, None).unwrap();
+        assert_eq!(config.title(), "Architecture");
+        assert_eq!(config.category_order(), vec!["core"]);
+    }
+
+    #[test]
+    fn test_profile_overrides_title_and_categories() {
+        let config = Config::from_toml_profile(PROFILES_TOML, Some("internal")).unwrap();
+        assert_eq!(config.title(), "Internal Architecture");
+        assert_eq!(config.category_order(), vec!["core", "ops"]);
+        assert_eq!(config.display_title_for("ops"), "Operations");
+
+        let config = Config::from_toml_profile(PROFILES_TOML, Some("public")).unwrap();
+        assert_eq!(config.title(), "Architecture");
+        assert_eq!(config.category_order(), vec!["core"]);
+    }
+
+    #[test]
+    fn test_profile_unknown() {
+        let err = Config::from_toml_profile(PROFILES_TOML, Some("secret")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown profile 'secret', expected one of: internal, public"
+        );
+        assert!(Config::from_toml_profile("title = \"x\"", Some("public")).is_err());
+    }
+
+    #[test]
+    fn test_expand_env() {
+        std::env::set_var("RUST_ARCH_TEST_TEAM", "Platform");
+        assert_eq!(
+            expand_env("${RUST_ARCH_TEST_TEAM} Architecture").unwrap(),
+            "Platform Architecture"
+        );
+        assert_eq!(
+            expand_env("- `{path}`: {description}").unwrap(),
+            "- `{path}`: {description}"
+        );
+        assert_eq!(
+            expand_env("cost: $5, ${unclosed").unwrap(),
+            "cost: $5, ${unclosed"
+        );
+        assert_eq!(
+            expand_env("$${RUST_ARCH_TEST_UNSET} and $${RUST_ARCH_TEST_TEAM}").unwrap(),
+            "${RUST_ARCH_TEST_UNSET} and ${RUST_ARCH_TEST_TEAM}"
+        );
+
+        let err = expand_env("${RUST_ARCH_TEST_UNSET}").unwrap_err();
+        assert!(err.to_string().contains("RUST_ARCH_TEST_UNSET"));
+    }
+
+    #[test]
+    fn test_load_extends_base_config() {
+        let dir = std::env::temp_dir().join("rust-arch-config-extends");
+        fs::create_dir_all(dir.join("shared")).unwrap();
+        fs::write(
+            dir.join("shared/architecture.base.toml"),
+            r#"
+title = "Shared Architecture"
+strict_categories = true
+
+[[categories]]
+category = "core"
+title = "Core"
+
+[[categories]]
+category = "services"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("architecture.toml"),
+            r#"
+extends = "shared/architecture.base.toml"
+title = "Project Architecture"
+
+[[categories]]
+category = "core"
+description = "Shared building blocks"
+
+[[categories]]
+category = "ops"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&dir.join("architecture.toml")).unwrap();
+        assert_eq!(config.title(), "Project Architecture");
+        assert!(config.strict_categories);
+        assert_eq!(config.category_order(), vec!["core", "services", "ops"]);
+        let core = config.get_category("core").unwrap();
+        assert_eq!(core.title.as_deref(), Some("Core"));
+        assert_eq!(core.description.as_deref(), Some("Shared building blocks"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_extends_cycle() {
+        let dir = std::env::temp_dir().join("rust-arch-config-extends-cycle");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.toml"), "extends = \"b.toml\"").unwrap();
+        fs::write(dir.join("b.toml"), "extends = \"a.toml\"").unwrap();
+        fs::write(dir.join("self.toml"), "extends = \"self.toml\"").unwrap();
+
+        let err = Config::load(&dir.join("a.toml")).unwrap_err();
+        assert!(err.to_string().starts_with("Config extends cycle: "));
+        assert!(err.to_string().ends_with("a.toml"));
+        assert!(Config::load(&dir.join("self.toml")).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_reads_template_file() {
+        let dir = std::env::temp_dir().join("rust-arch-config-template");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("skeleton.md"), "# {{title}}\n").unwrap();
+        fs::write(
+            dir.join("architecture.toml"),
+            "template_file = \"skeleton.md\"",
+        )
+        .unwrap();
+
+        let config = Config::load(&dir.join("architecture.toml")).unwrap();
+        assert_eq!(config.template.as_deref(), Some("# {{title}}\n"));
+
+        fs::write(
+            dir.join("architecture.toml"),
+            "template_file = \"missing.md\"",
+        )
+        .unwrap();
+        assert!(Config::load(&dir.join("architecture.toml")).is_err());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_compiles_front_matter_schema() {
+        let dir = std::env::temp_dir().join("rust-arch-config-schema");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("schema.json"), r#"{"required": ["team"]}"#).unwrap();
+        fs::write(
+            dir.join("architecture.toml"),
+            "front_matter_schema = \"schema.json\"",
+        )
+        .unwrap();
+
+        let config = Config::load(&dir.join("architecture.toml")).unwrap();
+        let validator = config.front_matter_validator.unwrap();
+        assert!(validator.is_valid(&serde_json::json!({"team": "web"})));
+        assert!(!validator.is_valid(&serde_json::json!({})));
+
+        fs::write(dir.join("schema.json"), r#"{"type": 5}"#).unwrap();
+        let err = Config::load(&dir.join("architecture.toml")).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid front matter schema: "),
+            "{}",
+            err
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_load_inherits_from_readme() {
+        let dir = std::env::temp_dir().join("rust-arch-config-inherit-readme");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("README.md"),
+            "[![CI](https://ci.example/badge.svg)](https://ci.example)\n\n\
+             # Payments Platform\n\nMoves money\nbetween accounts.\n\n## Usage\n",
+        )
+        .unwrap();
+
+        fs::write(
+            dir.join("architecture.toml"),
+            "inherit_from_readme = \"README.md\"",
+        )
+        .unwrap();
+        let config = Config::load(&dir.join("architecture.toml")).unwrap();
+        assert_eq!(config.title(), "Payments Platform");
+        assert_eq!(
+            config.description.as_deref(),
+            Some("Moves money between accounts.")
+        );
+
+        // Values set in the config win
+        fs::write(
+            dir.join("architecture.toml"),
+            "inherit_from_readme = \"README.md\"\ntitle = \"Architecture\"",
+        )
+        .unwrap();
+        let config = Config::load(&dir.join("architecture.toml")).unwrap();
+        assert_eq!(config.title(), "Architecture");
+        assert_eq!(
+            config.description.as_deref(),
+            Some("Moves money between accounts.")
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_description_sources() {
+        assert_eq!(
+            Config::default().description_sources(),
+            &DEFAULT_DESCRIPTION_SOURCES
+        );
+        let config =
+            config_from_str(r#"description_sources = ["cargo_toml", "front_matter"]"#).unwrap();
+        assert_eq!(
+            config.description_sources(),
+            &[
+                DescriptionFallback::CargoToml,
+                DescriptionFallback::FrontMatter
+            ]
+        );
+        assert!(config_from_str(r#"description_sources = ["readme"]"#).is_err());
+    }
+
+    #[test]
+    fn test_front_matter_format_for_sources() {
+        let config = config_from_str(
+            r#"
+front_matter_format = "yaml"
+
+[[sources]]
+pattern = "legacy/**/*.md"
+front_matter_format = "toml"
+
+[[sources]]
+pattern = "tools/**/*.md"
+"#,
+        )
+        .unwrap();
+
+        let format_for = |path: &str| config.front_matter_format_for(Path::new(path));
+        assert_eq!(format_for("legacy/db/README.md"), FrontMatterFormat::Toml);
+        assert_eq!(format_for("./legacy/db/README.md"), FrontMatterFormat::Toml);
+        // Sources without a format, and unmatched files, use the document-level one
+        assert_eq!(format_for("tools/README.md"), FrontMatterFormat::Yaml);
+        assert_eq!(format_for("core/README.md"), FrontMatterFormat::Yaml);
+    }
+
     #[test]
     fn test_load_nonexistent_file_returns_default() {
         let config = Config::load(Path::new("/nonexistent/path/config.toml")).unwrap();
         assert_eq!(config.title(), DEFAULT_TITLE);
     }
+
+    #[test]
+    fn test_load_or_reports_source() {
+        let (config, source) = Config::load_or(Path::new("/nonexistent/path/config.toml")).unwrap();
+        assert_eq!(config.title(), DEFAULT_TITLE);
+        assert_eq!(source, ConfigSource::Default);
+
+        // An empty file is still a file
+        let dir = std::env::temp_dir().join("rust-arch-config-load-or");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("architecture.toml");
+        fs::write(&path, "").unwrap();
+        let (config, source) = Config::load_or(&path).unwrap();
+        assert_eq!(config.title(), DEFAULT_TITLE);
+        assert_eq!(source, ConfigSource::File(path.clone()));
+        assert_eq!(source.to_string(), path.display().to_string());
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
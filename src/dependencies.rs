@@ -0,0 +1,234 @@
+//! Dependency graph analysis between components.
+//!
+//! Components declare the components they depend on in the `dependencies`
+//! front matter field, by name, README path or directory, as `related`
+//! references are resolved. Each component is a node of its own, identified by
+//! its path, so components that share a name in different trees stay apart;
+//! a name several components share refers to the first of them. Dependencies
+//! on unknown components are ignored.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::component::Component;
+
+/// Finds a dependency cycle among the given components.
+///
+/// Returns the names along the cycle, starting and ending with the same
+/// component (e.g. `["a", "b", "a"]`), or `None` if the graph is acyclic.
+/// Nodes are visited in name order, then path order, so the reported cycle is
+/// deterministic.
+pub fn find_dependency_cycle(components: &[Component]) -> Option<Vec<String>> {
+    let components: Vec<&Component> = components.iter().collect();
+    let edges = dependency_edges(&components);
+    let mut order: Vec<usize> = (0..components.len()).collect();
+    order.sort_by_cached_key(|&i| (components[i].name(), &components[i].path));
+
+    let mut visited = vec![false; components.len()];
+    for node in order {
+        let mut stack = Vec::new();
+        if let Some(cycle) = visit(node, &edges, &mut visited, &mut stack) {
+            return Some(cycle.into_iter().map(|i| components[i].name()).collect());
+        }
+    }
+
+    None
+}
+
+/// Orders components so that each appears after the components it depends on.
+///
+/// Only dependencies between the given components are considered. Components
+/// that are free to be placed keep their relative input order, so an input
+/// sorted by path yields path order for independent components and ties.
+/// Returns `None` if the dependencies form a cycle.
+pub(crate) fn sort_by_dependencies<'a>(components: &[&'a Component]) -> Option<Vec<&'a Component>> {
+    let edges = dependency_edges(components);
+
+    // Count, for each component, the dependencies that are still unplaced
+    let mut pending: Vec<usize> = edges.iter().map(BTreeSet::len).collect();
+
+    let mut placed = vec![false; components.len()];
+    let mut sorted = Vec::with_capacity(components.len());
+
+    while sorted.len() < components.len() {
+        let next = (0..components.len()).find(|&i| !placed[i] && pending[i] == 0)?;
+        placed[next] = true;
+        sorted.push(components[next]);
+
+        for (i, deps) in edges.iter().enumerate() {
+            if !placed[i] && deps.contains(&next) {
+                pending[i] -= 1;
+            }
+        }
+    }
+
+    Some(sorted)
+}
+
+/// The indices of the components each of `components` depends on.
+fn dependency_edges(components: &[&Component]) -> Vec<BTreeSet<usize>> {
+    let mut by_name = HashMap::new();
+    let mut by_path = HashMap::new();
+    // The first component with a key wins, as a search in order would find
+    for (i, comp) in components.iter().enumerate() {
+        by_name.entry(comp.name()).or_insert(i);
+        by_path.entry(comp.path.as_path()).or_insert(i);
+        if let Some(parent) = comp.path.parent() {
+            by_path.entry(parent).or_insert(i);
+        }
+    }
+
+    components
+        .iter()
+        .map(|comp| {
+            comp.dependencies
+                .iter()
+                .filter_map(|dependency| {
+                    let id = dependency.trim().trim_end_matches('/');
+                    let by_name = by_name.get(id);
+                    let by_path = by_path.get(Path::new(id));
+                    by_name.into_iter().chain(by_path).min().copied()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Depth-first visit, tracking the current path in `stack` to detect back edges.
+fn visit(
+    node: usize,
+    edges: &[BTreeSet<usize>],
+    visited: &mut [bool],
+    stack: &mut Vec<usize>,
+) -> Option<Vec<usize>> {
+    if let Some(start) = stack.iter().position(|&n| n == node) {
+        let mut cycle = stack[start..].to_vec();
+        cycle.push(node);
+        return Some(cycle);
+    }
+
+    if visited[node] {
+        return None;
+    }
+    visited[node] = true;
+
+    stack.push(node);
+    for &dep in &edges[node] {
+        if let Some(cycle) = visit(dep, edges, visited, stack) {
+            return Some(cycle);
+        }
+    }
+    stack.pop();
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn component(name: &str, dependencies: &[&str]) -> Component {
+        Component {
+            path: PathBuf::from(format!("crates/{}/README.md", name)),
+            description: name.into(),
+            category: "Test".to_string(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_dependency_cycle_acyclic() {
+        let components = vec![
+            component("cli", &["api", "core"]),
+            component("api", &["core"]),
+            component("core", &[]),
+        ];
+
+        assert_eq!(find_dependency_cycle(&components), None);
+    }
+
+    #[test]
+    fn test_find_dependency_cycle_three_nodes() {
+        let components = vec![
+            component("a", &["b"]),
+            component("b", &["c"]),
+            component("c", &["a"]),
+        ];
+
+        assert_eq!(
+            find_dependency_cycle(&components),
+            Some(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+                "a".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_sort_by_dependencies_linear_chain() {
+        let components = [
+            component("a", &["b"]),
+            component("b", &["c"]),
+            component("c", &[]),
+        ];
+        let refs: Vec<&Component> = components.iter().collect();
+
+        let sorted = sort_by_dependencies(&refs).unwrap();
+        let names: Vec<String> = sorted.iter().map(|c| c.name()).collect();
+        assert_eq!(names, vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_sort_by_dependencies_independent_keeps_input_order() {
+        let components = [
+            component("a", &[]),
+            component("b", &[]),
+            component("c", &[]),
+        ];
+        let refs: Vec<&Component> = components.iter().collect();
+
+        let sorted = sort_by_dependencies(&refs).unwrap();
+        let names: Vec<String> = sorted.iter().map(|c| c.name()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_sort_by_dependencies_cycle() {
+        let components = [component("a", &["b"]), component("b", &["a"])];
+        let refs: Vec<&Component> = components.iter().collect();
+
+        assert!(sort_by_dependencies(&refs).is_none());
+    }
+
+    #[test]
+    fn test_dependencies_key_on_path() {
+        // Two `core` components in different trees are separate nodes, so
+        // neither closes a cycle through the other
+        let components = vec![
+            Component {
+                path: PathBuf::from("services/core/README.md"),
+                ..component("core", &["tools/core"])
+            },
+            Component {
+                path: PathBuf::from("tools/core/README.md"),
+                ..component("core", &[])
+            },
+        ];
+        assert_eq!(find_dependency_cycle(&components), None);
+
+        let refs: Vec<&Component> = components.iter().collect();
+        let sorted = sort_by_dependencies(&refs).unwrap();
+        assert_eq!(sorted[0].path, PathBuf::from("tools/core/README.md"));
+    }
+
+    #[test]
+    fn test_find_dependency_cycle_ignores_unknown_dependencies() {
+        let components = vec![component("a", &["missing"])];
+
+        assert_eq!(find_dependency_cycle(&components), None);
+    }
+}
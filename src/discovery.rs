@@ -0,0 +1,839 @@
+//! Discovery of component files from glob patterns.
+//!
+//! Matching walks the pattern's base directory, following directory symlinks
+//! but skipping symlink cycles, and yields each canonical file at most once.
+//! The include patterns and any exclude patterns are compiled once into a
+//! [`FileFilter`] that each walked file is checked against, so several
+//! patterns under one directory share a single walk of it (see
+//! [`matching_files_in`]). With `respect_gitignore`, the walk also skips files
+//! that git ignore rules exclude.
+
+use anyhow::{bail, Context, Result};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::component::{parse_component, parse_component_with, Component};
+use crate::config::Config;
+use crate::config::FrontMatterFormat;
+use crate::error::ComponentError;
+use crate::front_matter::{deserialize_front_matter, resolve_format, split_front_matter};
+
+/// Returns the longest leading part of `pattern` without wildcards.
+///
+/// For example, `/path/to/fixtures/**/README.md` gives `/path/to/fixtures`.
+/// A pattern without wildcards names a single file, so its parent directory
+/// is the base: `crates/core/README.md` gives `crates/core`.
+pub fn base_dir_from_pattern(pattern: &str) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        let comp_str = component.as_os_str().to_string_lossy();
+        if comp_str.contains('*') || comp_str.contains('?') || comp_str.contains('[') {
+            return base;
+        }
+        base.push(component);
+    }
+    base.pop();
+    base
+}
+
+/// Returns the deepest directory containing all of `files`.
+///
+/// A single file gives its parent directory. Returns `None` if `files` is
+/// empty.
+pub fn common_base_dir(files: &[PathBuf]) -> Option<PathBuf> {
+    let (first, rest) = files.split_first()?;
+    let mut base = first.parent().unwrap_or(Path::new("")).to_path_buf();
+    for file in rest {
+        while !file.starts_with(&base) {
+            if !base.pop() {
+                break;
+            }
+        }
+    }
+    Some(base)
+}
+
+/// Merges the files matched by several patterns so each file appears once.
+///
+/// `matches` holds each match as (file, base dir of the pattern that matched
+/// it), in pattern order. Files are identified by their canonical path, and a
+/// file matched by several patterns keeps the match with the most specific
+/// (longest) base dir, so its relative path doesn't depend on pattern order.
+/// Files keep the position of their first match.
+pub fn merge_matches(matches: Vec<(PathBuf, PathBuf)>) -> Vec<(PathBuf, PathBuf)> {
+    let mut merged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut positions: HashMap<PathBuf, usize> = HashMap::new();
+    for (file, base) in matches {
+        let identity = fs::canonicalize(&file).unwrap_or_else(|_| file.clone());
+        match positions.get(&identity) {
+            Some(&i) => {
+                if depth(&base) > depth(&merged[i].1) {
+                    merged[i] = (file, base);
+                }
+            }
+            None => {
+                positions.insert(identity, merged.len());
+                merged.push((file, base));
+            }
+        }
+    }
+    merged
+}
+
+/// The number of components in `dir` as an absolute path.
+fn depth(dir: &Path) -> usize {
+    std::path::absolute(dir)
+        .unwrap_or_else(|_| dir.to_path_buf())
+        .components()
+        .count()
+}
+
+/// Include glob patterns and exclude glob patterns, compiled once.
+///
+/// A path matches if it matches an include pattern and no exclude pattern.
+/// Exclude patterns are tried against both the path as globbed and the path
+/// relative to the matching include pattern's base directory, so `vendor/**`
+/// and `**/vendor/**` both work. As with the include patterns, `*` and `?`
+/// never match a `/`.
+#[derive(Debug, Clone)]
+pub struct FileFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+    base_dirs: Vec<PathBuf>,
+}
+
+impl FileFilter {
+    /// Compiles the include `pattern` and the `exclude` patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any pattern is invalid.
+    pub fn new(pattern: &str, exclude: &[String]) -> Result<Self> {
+        Self::for_patterns(&[pattern], exclude)
+    }
+
+    /// Compiles several include `patterns` and the `exclude` patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any pattern is invalid.
+    pub fn for_patterns(patterns: &[&str], exclude: &[String]) -> Result<Self> {
+        let mut include = GlobSetBuilder::new();
+        for pattern in patterns {
+            include.add(
+                compile_glob(pattern)
+                    .with_context(|| format!("Failed to read glob pattern: {}", pattern))?,
+            );
+        }
+
+        let mut excludes = GlobSetBuilder::new();
+        for pattern in exclude {
+            excludes.add(
+                compile_glob(pattern)
+                    .with_context(|| format!("Failed to read exclude pattern: {}", pattern))?,
+            );
+        }
+
+        Ok(FileFilter {
+            include: include.build()?,
+            exclude: excludes.build()?,
+            base_dirs: patterns
+                .iter()
+                .map(|pattern| base_dir_from_pattern(pattern))
+                .collect(),
+        })
+    }
+
+    /// Whether `path` matches an include pattern and no exclude pattern.
+    pub fn is_match(&self, path: &Path) -> bool {
+        self.matching_base(path).is_some()
+    }
+
+    /// The base dir of the most specific include pattern that `path` matches
+    /// without an exclude pattern matching it, if any.
+    fn matching_base(&self, path: &Path) -> Option<&Path> {
+        if self.exclude.is_match(path) {
+            return None;
+        }
+        self.include
+            .matches(path)
+            .into_iter()
+            .map(|i| self.base_dirs[i].as_path())
+            .filter(|base| {
+                !path
+                    .strip_prefix(base)
+                    .is_ok_and(|relative| self.exclude.is_match(relative))
+            })
+            .max_by_key(|base| depth(base))
+    }
+}
+
+/// Compiles a glob pattern in which `*` and `?` don't match a path separator.
+pub(crate) fn compile_glob(pattern: &str) -> Result<Glob, globset::Error> {
+    GlobBuilder::new(pattern).literal_separator(true).build()
+}
+
+/// Lazily finds the files matching `pattern`.
+///
+/// Files are yielded in file-name order at each directory level. A file
+/// reachable through several symlinked paths is yielded once, under the first
+/// path found; symlink cycles are skipped rather than followed. Each item is a
+/// matched path as the user globbed it (not its canonical target), or a
+/// [`ComponentError::Io`] naming the path that could not be read.
+///
+/// # Errors
+///
+/// Returns an error if the glob pattern is invalid.
+pub fn matching_files(pattern: &str) -> Result<impl Iterator<Item = Result<PathBuf>>> {
+    matching_files_with(pattern, &[], false)
+}
+
+/// Lazily finds the files matching `pattern` but none of the `exclude`
+/// patterns, like [`matching_files`]. See [`FileFilter`] for how patterns are
+/// matched.
+///
+/// With `respect_gitignore`, files ignored by `.gitignore`, `.ignore` and
+/// git's global and `info/exclude` rules are skipped too. Ignore files in the
+/// pattern's base directory, its subdirectories and its parents apply,
+/// whether or not the directory is in a git repository. Hidden files are not
+/// skipped unless ignored.
+///
+/// # Errors
+///
+/// Returns an error if a glob pattern is invalid.
+pub fn matching_files_with(
+    pattern: &str,
+    exclude: &[String],
+    respect_gitignore: bool,
+) -> Result<impl Iterator<Item = Result<PathBuf>>> {
+    Ok(matching_files_in(&[pattern], exclude, respect_gitignore)?
+        .map(|entry| entry.map(|(file, _)| file)))
+}
+
+/// Lazily finds the files matching any of `patterns` but none of the
+/// `exclude` patterns, skipping those ignore files exclude if
+/// `respect_gitignore` is set, as [`matching_files_with`] does.
+///
+/// Each directory is walked once however many patterns it holds: patterns
+/// whose base directories nest are matched in a single walk of the outermost
+/// one, against all of them at once. Each item is a matched file with the
+/// base dir of the most specific pattern matching it, or an error naming the
+/// path that could not be read. Within a walk, a file is yielded once, as with
+/// [`matching_files`]; a file reached from two separate base directories is
+/// yielded for each, for [`merge_matches`] to resolve.
+///
+/// # Errors
+///
+/// Returns an error if a glob pattern is invalid.
+pub fn matching_files_in(
+    patterns: &[&str],
+    exclude: &[String],
+    respect_gitignore: bool,
+) -> Result<impl Iterator<Item = Result<(PathBuf, PathBuf)>>> {
+    let filter = FileFilter::for_patterns(patterns, exclude)?;
+
+    let bases: Vec<PathBuf> = patterns
+        .iter()
+        .map(|pattern| base_dir_from_pattern(pattern))
+        .collect();
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for base in &bases {
+        let covered = |root: &PathBuf| {
+            base.starts_with(root) && (!root.as_os_str().is_empty() || base.is_relative())
+        };
+        if !bases.iter().any(|other| other != base && covered(other)) && !roots.contains(base) {
+            roots.push(base.clone());
+        }
+    }
+
+    Ok(roots.into_iter().flat_map(move |base_dir| {
+        let filter = filter.clone();
+        let relative = base_dir.as_os_str().is_empty();
+        let root = if relative {
+            PathBuf::from(".")
+        } else {
+            base_dir
+        };
+        let mut seen = HashSet::new();
+        walk_files(root, respect_gitignore).filter_map(move |walked| match walked {
+            Ok(walked) => unseen_match(&walked, relative, &filter, &mut seen).map(Ok),
+            Err(e) => Some(Err(e)),
+        })
+    }))
+}
+
+/// Walks `root`, following directory symlinks but skipping symlink cycles and
+/// a missing `root`, and yields each file found in file-name order at each
+/// directory level, or an error for a path that could not be read. With
+/// `respect_gitignore`, files that ignore rules exclude are skipped.
+fn walk_files(root: PathBuf, respect_gitignore: bool) -> Box<dyn Iterator<Item = Result<PathBuf>>> {
+    if respect_gitignore {
+        let walker = ignore::WalkBuilder::new(&root)
+            .follow_links(true)
+            .hidden(false)
+            .require_git(false)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+        return Box::new(walker.filter_map(move |entry| {
+            match entry {
+                Ok(entry) => entry
+                    .file_type()
+                    .is_some_and(|t| t.is_file())
+                    .then(|| Ok(entry.into_path())),
+                Err(e) if is_loop_or_missing(&e) => None,
+                Err(e) => {
+                    let path = walk_error_path(&e).unwrap_or(&root).to_path_buf();
+                    Some(Err(ComponentError::Io {
+                        path,
+                        source: io::Error::other(e),
+                    }
+                    .into()))
+                }
+            }
+        }));
+    }
+
+    let walker = WalkDir::new(&root)
+        .follow_links(true)
+        .sort_by_file_name()
+        .into_iter();
+    Box::new(walker.filter_map(move |entry| match entry {
+        Ok(entry) => entry.file_type().is_file().then(|| Ok(entry.into_path())),
+        // Symlink cycles and a missing base dir just mean fewer matches
+        Err(e) if e.loop_ancestor().is_some() => None,
+        Err(e) if e.io_error().map(io::Error::kind) == Some(io::ErrorKind::NotFound) => None,
+        Err(e) => {
+            let path = e.path().unwrap_or(&root).to_path_buf();
+            Some(Err(ComponentError::Io {
+                path,
+                source: e.into(),
+            }
+            .into()))
+        }
+    }))
+}
+
+/// The path an ignore-aware walk error is about, if it names one.
+fn walk_error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            walk_error_path(err)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a walk error is a symlink cycle or a missing path, which just mean
+/// fewer matches.
+fn is_loop_or_missing(error: &ignore::Error) -> bool {
+    match error {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::Io(e) => e.kind() == io::ErrorKind::NotFound,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_loop_or_missing(err),
+        _ => false,
+    }
+}
+
+/// The path of a walked file as the user globbed it, with the base dir of the
+/// pattern it matches, if it matches `filter` and its canonical path is not in
+/// `seen` yet, recording it there.
+fn unseen_match(
+    walked: &Path,
+    relative: bool,
+    filter: &FileFilter,
+    seen: &mut HashSet<PathBuf>,
+) -> Option<(PathBuf, PathBuf)> {
+    let path = if relative {
+        walked.strip_prefix(".").unwrap_or(walked)
+    } else {
+        walked
+    };
+    let base_dir = filter.matching_base(path)?;
+
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    seen.insert(canonical)
+        .then(|| (path.to_path_buf(), base_dir.to_path_buf()))
+}
+
+/// Lazily globs `pattern` and parses each matched file into a component.
+///
+/// Files are matched and parsed one at a time as the iterator advances, so
+/// consumers can stream components or stop early without parsing the rest.
+/// Each item is the result of [`parse_component`] for one matched file, or an
+/// error if the matched path could not be read.
+///
+/// # Errors
+///
+/// Returns an error if the glob pattern is invalid.
+pub fn components(
+    pattern: &str,
+    base_dir: &Path,
+) -> Result<impl Iterator<Item = Result<Component>>> {
+    let base_dir = base_dir.to_path_buf();
+
+    Ok(matching_files(pattern)?.map(move |entry| parse_component(entry?, &base_dir)))
+}
+
+/// Lazily globs `pattern` and parses each matched file into a component like
+/// [`components`], following `config`.
+///
+/// Files matching the config's `exclude` patterns are skipped, and each item
+/// is the result of [`parse_component_with`] for one matched file.
+///
+/// # Errors
+///
+/// Returns an error if the glob pattern is invalid.
+pub fn components_with<'a>(
+    pattern: &str,
+    base_dir: &Path,
+    config: &'a Config,
+) -> Result<impl Iterator<Item = Result<Component>> + 'a> {
+    let base_dir = base_dir.to_path_buf();
+
+    Ok(matching_files_with(pattern, &config.exclude, false)?
+        .map(move |entry| parse_component_with(entry?, &base_dir, config)))
+}
+
+/// Finds markdown files under `root` that look like components but are not
+/// among `matched`: READMEs that no pattern picks up, so they silently never
+/// appear in the document.
+///
+/// A file looks like a component if its front matter has a `category` key.
+/// Hidden directories such as `.git` are skipped, and files are compared by
+/// canonical path, so `matched` may use different but equivalent paths.
+pub fn find_orphans(root: &Path, matched: &[PathBuf]) -> Vec<PathBuf> {
+    let matched: HashSet<PathBuf> = matched
+        .iter()
+        .map(|file| fs::canonicalize(file).unwrap_or_else(|_| file.clone()))
+        .collect();
+
+    WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "md")
+        })
+        .map(|entry| entry.into_path())
+        .filter(|file| {
+            let canonical = fs::canonicalize(file).unwrap_or_else(|_| file.clone());
+            !matched.contains(&canonical) && has_category(file)
+        })
+        .collect()
+}
+
+/// The files matched by a set of patterns.
+pub struct Globbed {
+    /// Each matched file with the base dir of the pattern it is documented
+    /// under, merged with [`merge_matches`].
+    pub files: Vec<(PathBuf, PathBuf)>,
+    /// Errors for paths that could not be read.
+    pub errors: Vec<anyhow::Error>,
+}
+
+/// Globs `patterns` in one walk of each base directory, skipping files matching
+/// `exclude` and, with `respect_gitignore`, those ignore files exclude.
+///
+/// # Errors
+///
+/// Returns an error if a glob pattern is invalid, or as soon as more than
+/// `max_files` distinct files have matched, without walking the rest of the
+/// tree.
+pub fn glob_patterns(
+    patterns: &[&str],
+    exclude: &[String],
+    respect_gitignore: bool,
+    max_files: Option<usize>,
+) -> Result<Globbed> {
+    let mut matches = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for entry in matching_files_in(patterns, exclude, respect_gitignore)? {
+        match entry {
+            Ok((file, base_dir)) => {
+                seen.insert(file.clone());
+                if let Some(max_files) = max_files.filter(|&max| seen.len() > max) {
+                    bail!(
+                        "Pattern {} matched more than --max-files {} files; \
+                         narrow the pattern or skip directories with --exclude",
+                        patterns.join(" "),
+                        max_files
+                    );
+                }
+                matches.push((file, base_dir));
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+    Ok(Globbed {
+        files: merge_matches(matches),
+        errors,
+    })
+}
+
+/// `patterns` followed by the patterns of the config's `[[sources]]`.
+pub fn with_sources<'a>(patterns: &[&'a str], config: &'a Config) -> Vec<&'a str> {
+    patterns
+        .iter()
+        .copied()
+        .chain(config.sources.iter().map(|source| source.pattern.as_str()))
+        .collect()
+}
+
+/// Whether the file's front matter has a `category` key.
+fn has_category(file: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(file) else {
+        return false;
+    };
+    let format = resolve_format(FrontMatterFormat::Auto, &content);
+    split_front_matter(&content)
+        .and_then(|(text, _)| {
+            deserialize_front_matter::<BTreeMap<String, serde_yaml::Value>>(text, format).ok()
+        })
+        .is_some_and(|front_matter| front_matter.contains_key("category"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_components_yields_ok_and_err_results() {
+        let base_dir = fixture_dir("no-front-matter");
+        let pattern = base_dir.join("**/README.md");
+
+        let results: Vec<_> = components(&pattern.display().to_string(), &base_dir)
+            .unwrap()
+            .collect();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert_eq!(results.iter().filter(|r| r.is_err()).count(), 1);
+    }
+
+    #[test]
+    fn test_components_is_lazy() {
+        let base_dir = fixture_dir("multiple-categories");
+        let pattern = base_dir.join("**/README.md");
+
+        let first = components_with(
+            &pattern.display().to_string(),
+            &base_dir,
+            &Config::default(),
+        )
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+        assert_eq!(first.path, PathBuf::from("crates/api/README.md"));
+    }
+
+    #[test]
+    fn test_base_dir_from_pattern() {
+        assert_eq!(
+            base_dir_from_pattern("/repo/crates/**/README.md"),
+            PathBuf::from("/repo/crates")
+        );
+        assert_eq!(base_dir_from_pattern("**/README.md"), PathBuf::new());
+        assert_eq!(base_dir_from_pattern("docs/a?.md"), PathBuf::from("docs"));
+    }
+
+    #[test]
+    fn test_base_dir_from_pattern_without_wildcards() {
+        assert_eq!(
+            base_dir_from_pattern("crates/core/README.md"),
+            PathBuf::from("crates/core")
+        );
+        assert_eq!(base_dir_from_pattern("README.md"), PathBuf::new());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_matching_files_dedupes_symlinks_and_skips_cycles() {
+        use std::os::unix::fs::symlink;
+
+        let dir = std::env::temp_dir().join("rust-arch-discovery-symlinks");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        fs::write(dir.join("a/README.md"), "").unwrap();
+        symlink("../a/README.md", dir.join("b/README.md")).unwrap();
+        symlink("..", dir.join("a/loop")).unwrap();
+
+        let files: Vec<PathBuf> = matching_files(&format!("{}/**/README.md", dir.display()))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(files, vec![dir.join("a/README.md")]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_matching_files_missing_base_dir() {
+        assert_eq!(
+            matching_files("/nonexistent/**/README.md").unwrap().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_matching_files_with_respect_gitignore() {
+        use std::os::unix::fs::symlink;
+
+        let dir = std::env::temp_dir().join("rust-arch-discovery-gitignore");
+        fs::remove_dir_all(&dir).ok();
+        for sub in [
+            "core",
+            "target/debug",
+            "node_modules/pkg",
+            ".github",
+            "docs",
+        ] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+            fs::write(dir.join(sub).join("README.md"), "").unwrap();
+        }
+        fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+        fs::write(dir.join(".ignore"), "node_modules/\n").unwrap();
+        fs::write(dir.join("docs/.gitignore"), "README.md\n").unwrap();
+        symlink("..", dir.join("core/loop")).unwrap();
+
+        let pattern = format!("{}/**/README.md", dir.display());
+        let files: Vec<PathBuf> = matching_files_with(&pattern, &[], true)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            files,
+            vec![dir.join(".github/README.md"), dir.join("core/README.md")]
+        );
+
+        // Exclude patterns still apply
+        let files: Vec<PathBuf> = matching_files_with(&pattern, &[".github/**".to_string()], true)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(files, vec![dir.join("core/README.md")]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_walk_error_path() {
+        let error = ignore::Error::WithDepth {
+            depth: 2,
+            err: Box::new(ignore::Error::WithPath {
+                path: PathBuf::from("repo/private"),
+                err: Box::new(ignore::Error::Io(io::Error::from(
+                    io::ErrorKind::PermissionDenied,
+                ))),
+            }),
+        };
+        assert_eq!(walk_error_path(&error), Some(Path::new("repo/private")));
+        assert_eq!(
+            walk_error_path(&ignore::Error::Io(io::Error::from(io::ErrorKind::Other))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_common_base_dir() {
+        let files = [
+            PathBuf::from("/repo/crates/core/README.md"),
+            PathBuf::from("/repo/crates/cli/README.md"),
+            PathBuf::from("/repo/crates/cli/sub/README.md"),
+        ];
+        assert_eq!(common_base_dir(&files), Some(PathBuf::from("/repo/crates")));
+
+        let files = [
+            PathBuf::from("/repo/crates/core/README.md"),
+            PathBuf::from("/repo/tools/README.md"),
+        ];
+        assert_eq!(common_base_dir(&files), Some(PathBuf::from("/repo")));
+
+        // Components are compared whole, not as string prefixes
+        let files = [
+            PathBuf::from("crates/core/README.md"),
+            PathBuf::from("crates/core-macros/README.md"),
+        ];
+        assert_eq!(common_base_dir(&files), Some(PathBuf::from("crates")));
+    }
+
+    #[test]
+    fn test_merge_matches_keeps_most_specific_base() {
+        let root = std::env::temp_dir().join("rust-arch-merge-matches");
+        let services = root.join("services");
+        fs::create_dir_all(services.join("api")).unwrap();
+        fs::create_dir_all(root.join("tools")).unwrap();
+        fs::write(services.join("api/README.md"), "").unwrap();
+        fs::write(root.join("tools/README.md"), "").unwrap();
+
+        // The same file, spelled differently, is one file
+        let matches = vec![
+            (root.join("services/api/README.md"), root.clone()),
+            (root.join("tools/README.md"), root.clone()),
+            (services.join("../services/api/README.md"), services.clone()),
+        ];
+        assert_eq!(
+            merge_matches(matches),
+            vec![
+                (services.join("../services/api/README.md"), services),
+                (root.join("tools/README.md"), root.clone()),
+            ]
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_matching_files_in_walks_nested_bases_once() {
+        let root = std::env::temp_dir().join("rust-arch-matching-files-in");
+        let services = root.join("services");
+        for dir in ["services/api", "services/vendor/lib", "tools"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("README.md"), "").unwrap();
+        }
+
+        let outer = root.join("**/README.md").display().to_string();
+        let inner = services.join("**/README.md").display().to_string();
+        let files: Vec<(PathBuf, PathBuf)> =
+            matching_files_in(&[&outer, &inner], &["**/vendor/**".to_string()], false)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+
+        // Each file once, under the most specific base
+        assert_eq!(
+            files,
+            vec![
+                (services.join("api/README.md"), services.clone()),
+                (root.join("tools/README.md"), root.clone()),
+            ]
+        );
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_common_base_dir_no_shared_ancestor() {
+        let files = [
+            PathBuf::from("crates/core/README.md"),
+            PathBuf::from("tools/README.md"),
+        ];
+        assert_eq!(common_base_dir(&files), Some(PathBuf::new()));
+    }
+
+    #[test]
+    fn test_common_base_dir_single_file() {
+        assert_eq!(
+            common_base_dir(&[PathBuf::from("./crates/core/README.md")]),
+            Some(PathBuf::from("./crates/core"))
+        );
+        assert_eq!(
+            common_base_dir(&[PathBuf::from("README.md")]),
+            Some(PathBuf::new())
+        );
+        assert_eq!(common_base_dir(&[]), None);
+    }
+
+    #[test]
+    fn test_find_orphans() {
+        let dir = std::env::temp_dir().join("rust-arch-discovery-orphans");
+        fs::remove_dir_all(&dir).ok();
+        for sub in ["crates/core", "tools/lint", "docs", ".git"] {
+            fs::create_dir_all(dir.join(sub)).unwrap();
+        }
+        let component = "---\ncategory: Core\n---\n\n# Component\n";
+        fs::write(dir.join("crates/core/README.md"), component).unwrap();
+        fs::write(dir.join("tools/lint/README.md"), component).unwrap();
+        fs::write(dir.join(".git/README.md"), component).unwrap();
+        fs::write(dir.join("docs/guide.md"), "---\ntitle: Guide\n---\n").unwrap();
+        fs::write(dir.join("docs/notes.md"), "# Notes\n").unwrap();
+
+        let matched: Vec<PathBuf> =
+            matching_files(&format!("{}/crates/**/README.md", dir.display()))
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+        assert_eq!(matched, vec![dir.join("crates/core/README.md")]);
+        assert_eq!(
+            find_orphans(&dir, &matched),
+            vec![dir.join("tools/lint/README.md")]
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_components_invalid_pattern() {
+        assert!(components("[", Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_file_filter_matches_like_glob() {
+        let filter = FileFilter::new("crates/*/README.md", &[]).unwrap();
+        assert!(filter.is_match(Path::new("crates/core/README.md")));
+        assert!(!filter.is_match(Path::new("crates/core/sub/README.md")));
+        assert!(!filter.is_match(Path::new("crates/core/readme.md")));
+
+        let filter = FileFilter::new("**/README.md", &[]).unwrap();
+        assert!(filter.is_match(Path::new("README.md")));
+        assert!(filter.is_match(Path::new("a/b/README.md")));
+    }
+
+    #[test]
+    fn test_file_filter_invalid_exclude() {
+        let err = FileFilter::new("**/README.md", &["[".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "Failed to read exclude pattern: [");
+    }
+
+    #[test]
+    fn test_matching_files_large_exclude_set() {
+        let dir = std::env::temp_dir().join("rust-arch-discovery-excludes");
+        fs::remove_dir_all(&dir).ok();
+        for i in 0..50 {
+            fs::create_dir_all(dir.join(format!("crates/c{}", i))).unwrap();
+            fs::write(dir.join(format!("crates/c{}/README.md", i)), "").unwrap();
+        }
+        fs::create_dir_all(dir.join("vendor/dep")).unwrap();
+        fs::write(dir.join("vendor/dep/README.md"), "").unwrap();
+
+        // Exclude every odd crate by name, plus the vendored tree relative to the base dir
+        let mut exclude: Vec<String> = (1..50)
+            .step_by(2)
+            .map(|i| format!("**/c{}/README.md", i))
+            .collect();
+        exclude.push("vendor/**".to_string());
+
+        let files: Vec<PathBuf> =
+            matching_files_with(&format!("{}/**/README.md", dir.display()), &exclude, false)
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+        let mut expected: Vec<PathBuf> = (0..50)
+            .step_by(2)
+            .map(|i| dir.join(format!("crates/c{}/README.md", i)))
+            .collect();
+        expected.sort();
+        assert_eq!(files, expected);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,463 @@
+//! Documentation health metrics across all matched components.
+//!
+//! The [`HealthReport`] aggregates several individual checks (parse failures,
+//! fallback and short descriptions, unknown and near-miss categories, duplicate paths,
+//! orphaned READMEs) into a single summary used by the `doctor` subcommand.
+//! [`DocStats`] condenses a report into the few numbers worth charting over time.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::component::{Component, DescriptionSource};
+use crate::config::Config;
+use crate::error::ComponentError;
+use crate::validate::{near_miss_category, near_miss_message};
+
+/// A single documentation health check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthCheck {
+    /// Files that failed to parse as components.
+    ParseErrors,
+    /// Components relying on the first-paragraph description fallback.
+    FallbackDescriptions,
+    /// Components whose description is below the word-count threshold.
+    ShortDescriptions,
+    /// Components whose category is not declared in the config.
+    UnknownCategories,
+    /// Unknown categories that are close to a declared one.
+    NearMissCategories,
+    /// Paths shared by more than one component.
+    DuplicatePaths,
+    /// Component-like files that the pattern doesn't match.
+    Orphans,
+}
+
+impl HealthCheck {
+    /// All checks, in report order.
+    pub const ALL: [HealthCheck; 7] = [
+        HealthCheck::ParseErrors,
+        HealthCheck::FallbackDescriptions,
+        HealthCheck::ShortDescriptions,
+        HealthCheck::UnknownCategories,
+        HealthCheck::NearMissCategories,
+        HealthCheck::DuplicatePaths,
+        HealthCheck::Orphans,
+    ];
+
+    /// The checks that fail the report unless configured otherwise.
+    pub const DEFAULT_ERRORS: [HealthCheck; 2] =
+        [HealthCheck::ParseErrors, HealthCheck::DuplicatePaths];
+
+    /// The kebab-case name of the check, as accepted by [`FromStr`].
+    pub fn name(self) -> &'static str {
+        match self {
+            HealthCheck::ParseErrors => "parse-errors",
+            HealthCheck::FallbackDescriptions => "fallback-descriptions",
+            HealthCheck::ShortDescriptions => "short-descriptions",
+            HealthCheck::UnknownCategories => "unknown-categories",
+            HealthCheck::NearMissCategories => "near-miss-categories",
+            HealthCheck::DuplicatePaths => "duplicate-paths",
+            HealthCheck::Orphans => "orphans",
+        }
+    }
+}
+
+impl fmt::Display for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HealthCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HealthCheck::ALL
+            .into_iter()
+            .find(|check| check.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = HealthCheck::ALL.iter().map(|c| c.name()).collect();
+                format!(
+                    "unknown check '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Aggregated documentation health metrics.
+#[derive(Debug, Default)]
+pub struct HealthReport {
+    /// Number of successfully parsed components.
+    pub components: usize,
+    /// Resolved categories of the parsed components.
+    pub categories: BTreeSet<String>,
+    /// Number of components whose description is written in front matter or
+    /// in the file named by `description_from`.
+    pub front_matter_descriptions: usize,
+    /// Total number of description words across parsed components.
+    pub description_words: usize,
+    /// Error messages for files that failed to parse.
+    pub parse_errors: Vec<String>,
+    /// Paths of files that failed to parse because no description was found.
+    pub missing_descriptions: Vec<PathBuf>,
+    /// Paths of components using the first-paragraph description fallback.
+    pub fallback_descriptions: Vec<PathBuf>,
+    /// Paths of components with fewer description words than the threshold.
+    pub short_descriptions: Vec<PathBuf>,
+    /// Categories not declared in the config, with the paths using them.
+    pub unknown_categories: BTreeMap<String, Vec<PathBuf>>,
+    /// Unknown categories close to a declared one, with that declared category.
+    pub near_miss_categories: BTreeMap<String, String>,
+    /// Paths shared by more than one component.
+    pub duplicate_paths: Vec<PathBuf>,
+    /// Component-like files the pattern doesn't match, when searched for.
+    pub orphans: Vec<PathBuf>,
+}
+
+impl HealthReport {
+    /// Builds a report from parse results.
+    ///
+    /// Descriptions with fewer than `min_words` words count as short. Unknown
+    /// categories are only reported when the config declares categories.
+    ///
+    /// Results may be owned or borrowed, so a caller that still needs the
+    /// components can pass `results.iter().map(Result::as_ref)`.
+    pub fn new<C, E>(
+        results: impl IntoIterator<Item = Result<C, E>>,
+        config: &Config,
+        min_words: usize,
+    ) -> Self
+    where
+        C: Borrow<Component>,
+        E: Borrow<anyhow::Error>,
+    {
+        let mut report = HealthReport::default();
+        let mut path_counts: BTreeMap<PathBuf, usize> = BTreeMap::new();
+
+        for result in results {
+            let component = match &result {
+                Ok(component) => component.borrow(),
+                Err(e) => {
+                    let e = e.borrow();
+                    if let Some(ComponentError::NoDescription { path, .. }) = e.downcast_ref() {
+                        report.missing_descriptions.push(path.clone());
+                    }
+                    report.parse_errors.push(format!("{:#}", e));
+                    continue;
+                }
+            };
+
+            report.components += 1;
+            report.categories.insert(component.category.clone());
+            *path_counts.entry(component.path.clone()).or_default() += 1;
+
+            match component.description_source {
+                DescriptionSource::FrontMatter | DescriptionSource::ReferencedFile => {
+                    report.front_matter_descriptions += 1;
+                }
+                DescriptionSource::FirstParagraph => {
+                    report.fallback_descriptions.push(component.path.clone());
+                }
+                DescriptionSource::CargoToml => {}
+            }
+            let words = component.description.joined().split_whitespace().count();
+            report.description_words += words;
+            if words < min_words {
+                report.short_descriptions.push(component.path.clone());
+            }
+            if !config.categories.is_empty() && !config.is_known_category(&component.category) {
+                report
+                    .unknown_categories
+                    .entry(component.category.clone())
+                    .or_default()
+                    .push(component.path.clone());
+            }
+            if let Some(configured) = near_miss_category(&component.category, config) {
+                report.near_miss_categories.insert(
+                    component.category.trim().to_string(),
+                    configured.to_string(),
+                );
+            }
+        }
+
+        report.duplicate_paths = path_counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(path, _)| path)
+            .collect();
+        report.fallback_descriptions.sort();
+        report.short_descriptions.sort();
+        report.missing_descriptions.sort();
+
+        report
+    }
+
+    /// The report's headline numbers, for tracking documentation coverage
+    /// over time.
+    pub fn stats(&self) -> DocStats {
+        let avg_word_count = if self.components == 0 {
+            0.0
+        } else {
+            // Two decimals are plenty for a trend line
+            let avg = self.description_words as f64 / self.components as f64;
+            (avg * 100.0).round() / 100.0
+        };
+        DocStats {
+            components: self.components,
+            categories: self.categories.len(),
+            with_front_matter_description: self.front_matter_descriptions,
+            fallback_description: self.components - self.front_matter_descriptions,
+            missing: self.missing_descriptions.len(),
+            avg_word_count,
+        }
+    }
+
+    /// Number of findings for a check.
+    pub fn count(&self, check: HealthCheck) -> usize {
+        match check {
+            HealthCheck::ParseErrors => self.parse_errors.len(),
+            HealthCheck::FallbackDescriptions => self.fallback_descriptions.len(),
+            HealthCheck::ShortDescriptions => self.short_descriptions.len(),
+            HealthCheck::UnknownCategories => self.unknown_categories.len(),
+            HealthCheck::NearMissCategories => self.near_miss_categories.len(),
+            HealthCheck::DuplicatePaths => self.duplicate_paths.len(),
+            HealthCheck::Orphans => self.orphans.len(),
+        }
+    }
+
+    /// The checks among `error_checks` that have findings.
+    pub fn failures(&self, error_checks: &[HealthCheck]) -> Vec<HealthCheck> {
+        HealthCheck::ALL
+            .into_iter()
+            .filter(|check| error_checks.contains(check) && self.count(*check) > 0)
+            .collect()
+    }
+
+    /// Human-readable detail lines for a check's findings.
+    pub fn details(&self, check: HealthCheck) -> Vec<String> {
+        let paths = |paths: &[PathBuf]| paths.iter().map(|p| p.display().to_string()).collect();
+        match check {
+            HealthCheck::ParseErrors => self.parse_errors.clone(),
+            HealthCheck::FallbackDescriptions => paths(&self.fallback_descriptions),
+            HealthCheck::ShortDescriptions => paths(&self.short_descriptions),
+            HealthCheck::UnknownCategories => self
+                .unknown_categories
+                .iter()
+                .map(|(category, paths)| {
+                    let paths: Vec<_> = paths.iter().map(|p| p.display().to_string()).collect();
+                    format!("'{}': {}", category, paths.join(", "))
+                })
+                .collect(),
+            HealthCheck::NearMissCategories => self
+                .near_miss_categories
+                .iter()
+                .map(|(category, configured)| near_miss_message(category, configured))
+                .collect(),
+            HealthCheck::DuplicatePaths => paths(&self.duplicate_paths),
+            HealthCheck::Orphans => paths(&self.orphans),
+        }
+    }
+}
+
+/// Documentation coverage numbers from a [`HealthReport`], written as JSON
+/// by `--emit-stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DocStats {
+    /// Number of successfully parsed components.
+    pub components: usize,
+    /// Number of distinct resolved categories.
+    pub categories: usize,
+    /// Components whose description is written in front matter or in the file
+    /// named by `description_from`.
+    pub with_front_matter_description: usize,
+    /// Components whose description comes from a fallback: the first
+    /// paragraph or `Cargo.toml`.
+    pub fallback_description: usize,
+    /// Files that failed to parse because no description was found.
+    pub missing: usize,
+    /// Average number of words per description, to two decimals.
+    pub avg_word_count: f64,
+}
+
+impl fmt::Display for HealthReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "components: {}", self.components)?;
+        for check in HealthCheck::ALL {
+            writeln!(f, "{}: {}", check, self.count(check))?;
+            for detail in self.details(check) {
+                writeln!(f, "  {}", detail)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn component(path: &str, description: &str, category: &str) -> Component {
+        Component {
+            path: PathBuf::from(path),
+            description: description.into(),
+            category: category.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn report() -> HealthReport {
+        let config: Config = toml::from_str(
+            r#"
+[[categories]]
+category = "Utilities"
+"#,
+        )
+        .unwrap();
+
+        let results = vec![
+            Ok(component(
+                "a/README.md",
+                "A well described component",
+                "Utilities",
+            )),
+            Ok(Component {
+                description_source: DescriptionSource::FirstParagraph,
+                ..component(
+                    "b/README.md",
+                    "Described by its first paragraph",
+                    "Utilities",
+                )
+            }),
+            Ok(component("c/README.md", "Short", "Services")),
+            Ok(component("c/README.md", "Duplicate of c", "Utilities")),
+            Err(anyhow!("No front matter found in: d/README.md")),
+        ];
+
+        HealthReport::new(results, &config, 3)
+    }
+
+    #[test]
+    fn test_health_report_counts() {
+        let report = report();
+        assert_eq!(report.components, 4);
+        assert_eq!(report.count(HealthCheck::ParseErrors), 1);
+        assert_eq!(
+            report.fallback_descriptions,
+            vec![PathBuf::from("b/README.md")]
+        );
+        assert_eq!(
+            report.short_descriptions,
+            vec![PathBuf::from("c/README.md")]
+        );
+        assert_eq!(
+            report.unknown_categories.get("Services"),
+            Some(&vec![PathBuf::from("c/README.md")])
+        );
+        assert_eq!(report.duplicate_paths, vec![PathBuf::from("c/README.md")]);
+    }
+
+    #[test]
+    fn test_health_report_near_miss_categories() {
+        let config: Config = toml::from_str(
+            r#"
+[[categories]]
+category = "Utilities"
+
+[[categories]]
+category = "CLI"
+"#,
+        )
+        .unwrap();
+        let results: Vec<Result<Component>> = vec![
+            Ok(component(
+                "a/README.md",
+                "Near miss of Utilities",
+                "utilites",
+            )),
+            Ok(component("b/README.md", "Short and unrelated", "UI")),
+        ];
+
+        let report = HealthReport::new(results, &config, 3);
+        assert_eq!(
+            report.near_miss_categories,
+            BTreeMap::from([("utilites".to_string(), "Utilities".to_string())])
+        );
+        assert!(report.to_string().contains(
+            "near-miss-categories: 1\n  category 'utilites' is close to configured 'Utilities' — did you mean it?\n"
+        ));
+    }
+
+    #[test]
+    fn test_health_report_stats() {
+        let config = Config::default();
+        let results = [
+            Ok(component("a/README.md", "One two three", "Core")),
+            Ok(Component {
+                description_source: DescriptionSource::FirstParagraph,
+                ..component("b/README.md", "One two three four", "Core")
+            }),
+            Ok(Component {
+                description_source: DescriptionSource::ReferencedFile,
+                ..component("c/README.md", "One two three four five", "CLI")
+            }),
+            Err(ComponentError::NoDescription {
+                path: PathBuf::from("d/README.md"),
+                referenced: None,
+            }
+            .into()),
+            Err(anyhow!("No front matter found in: e/README.md")),
+        ];
+
+        let report = HealthReport::new(results.iter().map(Result::as_ref), &config, 3);
+        assert_eq!(
+            report.stats(),
+            DocStats {
+                components: 3,
+                categories: 2,
+                with_front_matter_description: 2,
+                fallback_description: 1,
+                missing: 1,
+                avg_word_count: 4.0,
+            }
+        );
+        assert_eq!(HealthReport::default().stats().avg_word_count, 0.0);
+    }
+
+    #[test]
+    fn test_health_report_failures() {
+        let report = report();
+        assert_eq!(
+            report.failures(&HealthCheck::DEFAULT_ERRORS),
+            vec![HealthCheck::ParseErrors, HealthCheck::DuplicatePaths]
+        );
+        assert!(HealthReport::default()
+            .failures(&HealthCheck::ALL)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_health_check_from_str() {
+        assert_eq!(
+            "short-descriptions".parse::<HealthCheck>(),
+            Ok(HealthCheck::ShortDescriptions)
+        );
+        assert!("bogus".parse::<HealthCheck>().is_err());
+    }
+
+    #[test]
+    fn test_health_report_display() {
+        let output = report().to_string();
+        assert!(output.contains("components: 4\n"));
+        assert!(output.contains("parse-errors: 1\n  No front matter found in: d/README.md\n"));
+        assert!(output.contains("unknown-categories: 1\n  'Services': c/README.md\n"));
+        assert!(output.contains("near-miss-categories: 0\n"));
+    }
+}
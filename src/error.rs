@@ -0,0 +1,295 @@
+//! Structured errors for component parsing and validation.
+//!
+//! Parsing functions still return [`anyhow::Result`], but the underlying
+//! error is a [`ComponentError`] that callers can recover with
+//! `err.downcast_ref::<ComponentError>()` to get a machine-friendly kind.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An error parsing or validating a single component file.
+#[derive(Debug)]
+pub enum ComponentError {
+    /// The file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The file has no front matter block.
+    NoFrontMatter { path: PathBuf },
+    /// The front matter is not valid YAML or is missing required fields.
+    InvalidFrontMatter {
+        path: PathBuf,
+        /// Line in the file where the error was found, if known.
+        line: Option<usize>,
+        message: String,
+    },
+    /// The file named by `description_from` could not be read.
+    DescriptionFromUnreadable {
+        path: PathBuf,
+        referenced: PathBuf,
+        source: io::Error,
+    },
+    /// No description was found in front matter or content.
+    NoDescription {
+        path: PathBuf,
+        /// The `description_from` file that was searched, if any.
+        referenced: Option<PathBuf>,
+    },
+    /// The category is empty or whitespace-only.
+    EmptyCategory { path: PathBuf },
+    /// The front matter doesn't match the config's `front_matter_schema`.
+    SchemaViolation {
+        path: PathBuf,
+        violations: Vec<SchemaViolation>,
+    },
+    /// The front matter marks the component both `deprecated` and `planned`.
+    DeprecatedAndPlanned { path: PathBuf },
+    /// The config requires front matter descriptions, but the component's
+    /// description falls back to its first paragraph.
+    FallbackDescription { path: PathBuf },
+    /// The component's category requires a front matter `description`, but
+    /// the component has none.
+    DescriptionRequired { path: PathBuf, category: String },
+    /// The category is not declared in the config (strict mode).
+    UnknownCategory {
+        path: PathBuf,
+        category: String,
+        /// The closest configured category, if any.
+        suggestion: Option<String>,
+    },
+}
+
+impl ComponentError {
+    /// A stable, machine-friendly name for the kind of error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ComponentError::Io { .. } => "io_error",
+            ComponentError::NoFrontMatter { .. } => "no_front_matter",
+            ComponentError::InvalidFrontMatter { .. } => "invalid_front_matter",
+            ComponentError::DescriptionFromUnreadable { .. } => "description_from_unreadable",
+            ComponentError::NoDescription { .. } => "no_description",
+            ComponentError::EmptyCategory { .. } => "empty_category",
+            ComponentError::SchemaViolation { .. } => "schema_violation",
+            ComponentError::DeprecatedAndPlanned { .. } => "deprecated_and_planned",
+            ComponentError::FallbackDescription { .. } => "fallback_description",
+            ComponentError::DescriptionRequired { .. } => "description_required",
+            ComponentError::UnknownCategory { .. } => "unknown_category",
+        }
+    }
+
+    /// The path of the component file the error is about.
+    pub fn path(&self) -> &Path {
+        match self {
+            ComponentError::Io { path, .. }
+            | ComponentError::NoFrontMatter { path }
+            | ComponentError::InvalidFrontMatter { path, .. }
+            | ComponentError::DescriptionFromUnreadable { path, .. }
+            | ComponentError::NoDescription { path, .. }
+            | ComponentError::EmptyCategory { path }
+            | ComponentError::SchemaViolation { path, .. }
+            | ComponentError::DeprecatedAndPlanned { path }
+            | ComponentError::FallbackDescription { path }
+            | ComponentError::DescriptionRequired { path, .. }
+            | ComponentError::UnknownCategory { path, .. } => path,
+        }
+    }
+
+    /// The line in the file the error refers to, if known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ComponentError::InvalidFrontMatter { line, .. } => *line,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentError::Io { path, .. } => {
+                write!(f, "Failed to read file: {}", path.display())
+            }
+            ComponentError::NoFrontMatter { path } => {
+                write!(f, "No front matter found in: {}", path.display())
+            }
+            ComponentError::InvalidFrontMatter { path, message, .. } => {
+                write!(
+                    f,
+                    "Failed to parse front matter in: {}: {}",
+                    path.display(),
+                    message
+                )
+            }
+            ComponentError::DescriptionFromUnreadable {
+                path, referenced, ..
+            } => write!(
+                f,
+                "Failed to read description_from file {} referenced in: {}",
+                referenced.display(),
+                path.display()
+            ),
+            ComponentError::NoDescription {
+                path,
+                referenced: Some(referenced),
+            } => write!(
+                f,
+                "No description found in description_from file {} referenced in: {}",
+                referenced.display(),
+                path.display()
+            ),
+            ComponentError::NoDescription {
+                path,
+                referenced: None,
+            } => write!(
+                f,
+                "No description found in front matter or content: {}",
+                path.display()
+            ),
+            ComponentError::EmptyCategory { path } => {
+                write!(f, "Empty category in: {}", path.display())
+            }
+            ComponentError::SchemaViolation { path, violations } => {
+                write!(
+                    f,
+                    "Front matter doesn't match schema in: {}",
+                    path.display()
+                )?;
+                for (i, violation) in violations.iter().enumerate() {
+                    let separator = if i == 0 { ": " } else { "; " };
+                    write!(f, "{}{}", separator, violation)?;
+                }
+                Ok(())
+            }
+            ComponentError::DeprecatedAndPlanned { path } => write!(
+                f,
+                "A component can't be both deprecated and planned: {}",
+                path.display()
+            ),
+            ComponentError::FallbackDescription { path } => write!(
+                f,
+                "No description in front matter, and the first paragraph fallback is \
+                 disabled by require_front_matter_description: {}",
+                path.display()
+            ),
+            ComponentError::DescriptionRequired { path, category } => write!(
+                f,
+                "Category '{}' requires a description in front matter: {}",
+                category,
+                path.display()
+            ),
+            ComponentError::UnknownCategory {
+                path,
+                category,
+                suggestion,
+            } => {
+                write!(f, "Unknown category '{}' in: {}", category, path.display())?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " (did you mean '{}'?)", suggestion)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A single way front matter fails to match the `front_matter_schema`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the failing value, empty for the front matter as a whole.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ComponentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ComponentError::Io { source, .. }
+            | ComponentError::DescriptionFromUnreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_error_kind_and_line() {
+        let err = ComponentError::InvalidFrontMatter {
+            path: PathBuf::from("a/README.md"),
+            line: Some(3),
+            message: "invalid type".to_string(),
+        };
+        assert_eq!(err.kind(), "invalid_front_matter");
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.path(), Path::new("a/README.md"));
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse front matter in: a/README.md: invalid type"
+        );
+    }
+
+    #[test]
+    fn test_component_error_path_on_every_variant() {
+        let path = || PathBuf::from("a/README.md");
+        let io_error = || io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let errors = [
+            ComponentError::Io {
+                path: path(),
+                source: io_error(),
+            },
+            ComponentError::NoFrontMatter { path: path() },
+            ComponentError::InvalidFrontMatter {
+                path: path(),
+                line: None,
+                message: "invalid".to_string(),
+            },
+            ComponentError::DescriptionFromUnreadable {
+                path: path(),
+                referenced: PathBuf::from("a/docs/overview.md"),
+                source: io_error(),
+            },
+            ComponentError::NoDescription {
+                path: path(),
+                referenced: None,
+            },
+            ComponentError::EmptyCategory { path: path() },
+            ComponentError::UnknownCategory {
+                path: path(),
+                category: "Misc".to_string(),
+                suggestion: None,
+            },
+        ];
+
+        for err in errors {
+            assert_eq!(err.path(), Path::new("a/README.md"), "{}", err.kind());
+            assert!(err.to_string().contains("a/README.md"), "{}", err.kind());
+        }
+    }
+
+    #[test]
+    fn test_component_error_io_source() {
+        let err = anyhow::Error::from(ComponentError::Io {
+            path: PathBuf::from("a/README.md"),
+            source: io::Error::new(io::ErrorKind::NotFound, "not found"),
+        });
+        assert_eq!(
+            format!("{:#}", err),
+            "Failed to read file: a/README.md: not found"
+        );
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("io_error")
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Step-by-step trace of how a single component file is parsed.
+//!
+//! [`explain_component`] runs the same pipeline as `generate` on one file and
+//! records each step: the front matter block and the syntax it is read as,
+//! the parsed fields, where the description came from, the resolved category
+//! and the entry the component renders to.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::component::{parse_component_content_with, Component};
+use crate::config::{Config, FrontMatterFormat};
+use crate::front_matter::{parse_front_matter, resolve_format, split_front_matter};
+use crate::generator::render_entry;
+
+/// Appends a trace of parsing `content` as the component at `path` to `out`.
+///
+/// The trace is written step by step, so when parsing fails `out` holds
+/// everything up to the failing step and the error is returned.
+///
+/// # Errors
+///
+/// Returns the error [`parse_component_content_with`] would for the file.
+pub fn explain_component(
+    content: &str,
+    path: &Path,
+    config: &Config,
+    out: &mut String,
+) -> Result<Component> {
+    writeln!(out, "File: {}", path.display()).unwrap();
+
+    if let Some((front_matter, _)) = split_front_matter(content) {
+        let opening = content.lines().next().unwrap_or_default().trim();
+        let format = resolve_format(config.front_matter_format_for(path), content);
+        writeln!(
+            out,
+            "Front matter: {} between `{}` lines",
+            format_name(format),
+            opening
+        )
+        .unwrap();
+        for line in front_matter.lines() {
+            writeln!(out, "  {}", line).unwrap();
+        }
+        if let Ok(parsed) = parse_front_matter(front_matter, format) {
+            writeln!(out, "Parsed: {:#?}", parsed).unwrap();
+        }
+    }
+
+    let component = parse_component_content_with(content, path, Path::new(""), config)?;
+
+    writeln!(out, "Description source: {}", component.description_source).unwrap();
+    writeln!(out, "Description: {}", component.description.joined()).unwrap();
+    let category = config.canonical_category(&component.category);
+    let raw = match &component.raw_category {
+        Some(raw) => format!("'{}'", raw),
+        None => "default_category".to_string(),
+    };
+    writeln!(
+        out,
+        "Category: {} (from {}, shown as '{}')",
+        category,
+        raw,
+        config.display_title_for(category)
+    )
+    .unwrap();
+    writeln!(out, "Title: {}", component.title()).unwrap();
+    write!(out, "Entry:\n{}", render_entry(&component, config)).unwrap();
+
+    Ok(component)
+}
+
+/// The config spelling of a resolved front matter syntax.
+fn format_name(format: FrontMatterFormat) -> &'static str {
+    match format {
+        FrontMatterFormat::Auto => "auto",
+        FrontMatterFormat::Yaml => "yaml",
+        FrontMatterFormat::Toml => "toml",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explain(content: &str, config: &Config) -> (String, Result<Component>) {
+        let mut out = String::new();
+        let result = explain_component(content, Path::new("core/README.md"), config, &mut out);
+        (out, result)
+    }
+
+    #[test]
+    fn test_explain_first_paragraph_fallback() {
+        let config: Config =
+            toml::from_str("[[categories]]\ncategory = \"Core\"\ntitle = \"Core Systems\"")
+                .unwrap();
+        let (out, result) = explain(
+            "---\ncategory: \"core\"\n---\n\n# Core\n\nParses input.\n",
+            &Config {
+                case_insensitive_categories: true,
+                ..config
+            },
+        );
+        assert!(result.is_ok());
+
+        assert!(out.starts_with(
+            "File: core/README.md\nFront matter: yaml between `---` lines\n  category: \"core\"\n"
+        ));
+        assert!(
+            out.contains("\nDescription source: first_paragraph\n"),
+            "{}",
+            out
+        );
+        assert!(out.contains("\nDescription: Parses input.\n"), "{}", out);
+        assert!(
+            out.contains("\nCategory: Core (from 'core', shown as 'Core Systems')\n"),
+            "{}",
+            out
+        );
+        assert!(out.ends_with("\nEntry:\n- `core/README.md`: Parses input.\n"));
+    }
+
+    #[test]
+    fn test_explain_stops_at_parse_error() {
+        let config: Config = toml::from_str("front_matter_format = \"toml\"").unwrap();
+        let (out, result) = explain("---\ncategory: \"Core\"\n---\n\nText.\n", &config);
+
+        assert!(result.is_err());
+        assert!(
+            out.contains("Front matter: toml between `---` lines\n"),
+            "{}",
+            out
+        );
+        assert!(
+            !out.contains("Parsed:") && !out.contains("Entry:"),
+            "{}",
+            out
+        );
+    }
+}
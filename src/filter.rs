@@ -0,0 +1,194 @@
+//! Selection of components by category and tag.
+//!
+//! A [`ComponentFilter`] narrows the parsed components before they are
+//! grouped, as done by the `--only`, `--tag` and `--not-tag` options of the
+//! `generate` subcommand. Deprecated components are also dropped when the
+//! config sets `show_deprecated = false`, and planned components unless it
+//! sets `show_planned = true`.
+
+use crate::component::Component;
+use crate::config::Config;
+
+/// Criteria a component must meet to be documented.
+///
+/// Empty criteria match every component.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentFilter {
+    /// Categories to keep; a component is kept if it is in any of them.
+    pub categories: Vec<String>,
+    /// Tags a component must have, all of them.
+    pub tags: Vec<String>,
+    /// Tags a component must not have, none of them.
+    pub not_tags: Vec<String>,
+}
+
+impl ComponentFilter {
+    /// Whether `component` meets every criterion.
+    ///
+    /// Categories are compared after resolving aliases and configured
+    /// spellings with [`Config::canonical_category`]; tags are compared exactly.
+    /// Deprecated and planned components never match unless the config shows them.
+    pub fn matches(&self, component: &Component, config: &Config) -> bool {
+        if component.deprecated && !config.show_deprecated() {
+            return false;
+        }
+        if component.planned && !config.show_planned() {
+            return false;
+        }
+
+        let category = config.canonical_category(&component.category);
+        let in_category = self.categories.is_empty()
+            || self
+                .categories
+                .iter()
+                .any(|only| config.canonical_category(only) == category);
+
+        in_category
+            && self.tags.iter().all(|tag| component.tags.contains(tag))
+            && !self.not_tags.iter().any(|tag| component.tags.contains(tag))
+    }
+
+    /// Keeps only the components in `components` that meet every criterion.
+    pub fn apply(&self, components: &mut Vec<Component>, config: &Config) {
+        components.retain(|component| self.matches(component, config));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(path: &str, category: &str, tags: &[&str]) -> Component {
+        Component {
+            path: path.into(),
+            category: category.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn components() -> Vec<Component> {
+        vec![
+            component("a/README.md", "Core", &["async"]),
+            component("b/README.md", "Core", &["async", "internal"]),
+            component("c/README.md", "CLI", &[]),
+            component("d/README.md", "CLI", &["async"]),
+        ]
+    }
+
+    fn kept(filter: &ComponentFilter, config: &Config) -> Vec<String> {
+        let mut components = components();
+        filter.apply(&mut components, config);
+        components
+            .iter()
+            .map(|c| c.path.display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_empty_keeps_all() {
+        assert_eq!(
+            kept(&ComponentFilter::default(), &Config::default()).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_filter_tag() {
+        let filter = ComponentFilter {
+            tags: vec!["async".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            kept(&filter, &Config::default()),
+            vec!["a/README.md", "b/README.md", "d/README.md"]
+        );
+
+        // Every tag is required
+        let filter = ComponentFilter {
+            tags: vec!["async".to_string(), "internal".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(kept(&filter, &Config::default()), vec!["b/README.md"]);
+    }
+
+    #[test]
+    fn test_filter_not_tag() {
+        let filter = ComponentFilter {
+            not_tags: vec!["internal".to_string(), "missing".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            kept(&filter, &Config::default()),
+            vec!["a/README.md", "c/README.md", "d/README.md"]
+        );
+    }
+
+    #[test]
+    fn test_filter_combined() {
+        let filter = ComponentFilter {
+            tags: vec!["async".to_string()],
+            not_tags: vec!["internal".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            kept(&filter, &Config::default()),
+            vec!["a/README.md", "d/README.md"]
+        );
+
+        let filter = ComponentFilter {
+            categories: vec!["Core".to_string()],
+            tags: vec!["async".to_string()],
+            not_tags: vec!["internal".to_string()],
+        };
+        assert_eq!(kept(&filter, &Config::default()), vec!["a/README.md"]);
+
+        let filter = ComponentFilter {
+            tags: vec!["missing".to_string()],
+            ..Default::default()
+        };
+        assert!(kept(&filter, &Config::default()).is_empty());
+    }
+
+    #[test]
+    fn test_filter_show_deprecated() {
+        let mut components = components();
+        components[1].deprecated = true;
+
+        let mut kept = components.clone();
+        ComponentFilter::default().apply(&mut kept, &Config::default());
+        assert_eq!(kept.len(), 4);
+
+        let config: Config = toml::from_str("show_deprecated = false").unwrap();
+        let mut kept = components;
+        ComponentFilter::default().apply(&mut kept, &config);
+        let paths: Vec<_> = kept.iter().map(|c| c.path.display().to_string()).collect();
+        assert_eq!(paths, vec!["a/README.md", "c/README.md", "d/README.md"]);
+    }
+
+    #[test]
+    fn test_filter_show_planned() {
+        let mut components = components();
+        components[2].planned = true;
+
+        let mut kept = components.clone();
+        ComponentFilter::default().apply(&mut kept, &Config::default());
+        let paths: Vec<_> = kept.iter().map(|c| c.path.display().to_string()).collect();
+        assert_eq!(paths, vec!["a/README.md", "b/README.md", "d/README.md"]);
+
+        let config: Config = toml::from_str("show_planned = true").unwrap();
+        let mut kept = components;
+        ComponentFilter::default().apply(&mut kept, &config);
+        assert_eq!(kept.len(), 4);
+    }
+
+    #[test]
+    fn test_filter_category_uses_aliases() {
+        let config: Config = toml::from_str("[aliases]\ncommand-line = \"CLI\"").unwrap();
+        let filter = ComponentFilter {
+            categories: vec!["command-line".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(kept(&filter, &config), vec!["c/README.md", "d/README.md"]);
+    }
+}
@@ -1,23 +1,135 @@
-//! YAML front matter extraction and parsing.
+//! Front matter extraction and parsing.
 //!
-//! This module provides utilities for extracting and parsing YAML front matter
+//! This module provides utilities for extracting and parsing front matter
 //! from markdown files, as well as fallback extraction of the first paragraph.
+//! Front matter is YAML between `---` lines or TOML between `+++` lines,
+//! unless the config forces one syntax.
 
-use serde::Deserialize;
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
 
-/// Parsed YAML front matter from a markdown file.
+use crate::component::Description;
+use crate::config::FrontMatterFormat;
+
+/// Parsed front matter from a markdown file.
+///
+/// Multi-word keys accept `snake_case`, `camelCase` and `kebab-case` spellings.
 #[derive(Debug, Deserialize, PartialEq, Clone)]
 pub(crate) struct FrontMatter {
-    /// Optional description of the component.
-    pub description: Option<String>,
-    /// Required category for grouping in the output.
+    /// Optional description of the component, as a string or a list of lines.
+    pub description: Option<Description>,
+    /// Optional file, relative to the README, whose first paragraph is the description.
+    #[serde(alias = "descriptionFrom", alias = "description-from")]
+    pub description_from: Option<PathBuf>,
+    /// Optional title to show for the component instead of its directory name.
+    #[serde(alias = "displayTitle", alias = "display-title")]
+    pub display_title: Option<String>,
+    /// Required category for grouping in the output. An integer or boolean,
+    /// such as an unquoted `2024`, is read as the text it was written as.
+    #[serde(deserialize_with = "deserialize_scalar")]
     pub category: String,
+    /// Names of the components this component depends on.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Free-form labels for filtering components.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Teams or people owning the component, for `group_by = "owner"`.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Names or paths of sibling components to cross-link as "See also".
+    #[serde(default)]
+    pub related: Vec<String>,
+    /// Optional maturity status, shown as a badge, e.g. "experimental".
+    pub status: Option<String>,
+    /// Whether the component is deprecated, moving it to the deprecated section.
+    pub deprecated: Option<bool>,
+    /// Whether the component doesn't exist yet, moving it to the planned section.
+    pub planned: Option<bool>,
+    /// Optional version the component was added in, e.g. "1.4.0". An integer,
+    /// such as an unquoted `2`, is read as the text it was written as.
+    #[serde(default, deserialize_with = "deserialize_optional_scalar")]
+    pub since: Option<String>,
+    /// Any other keys, retained for use in templates and serialized output.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
 }
 
-/// Extracts YAML front matter from markdown content.
+/// A string field that accepts any scalar in front matter.
 ///
-/// Looks for content between `---` delimiters at the start of the file.
-/// Returns `None` if no valid front matter block is found.
+/// Integers and booleans are converted to their string representation, so an
+/// unquoted `category: 2024` reads as `"2024"` rather than failing to parse.
+/// Floats are rejected, since their text isn't preserved (`1.10` would read
+/// back as `1.1`) and they have to be quoted. Serializes as a plain string.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub(crate) struct Scalar(pub String);
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ScalarVisitor)
+    }
+}
+
+/// The [`Visitor`] behind [`Scalar`], for types that accept a scalar among
+/// other forms.
+pub(crate) struct ScalarVisitor;
+
+impl Visitor<'_> for ScalarVisitor {
+    type Value = Scalar;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string, number or boolean")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Scalar, E> {
+        Ok(Scalar(value.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Scalar, E> {
+        Ok(Scalar(value))
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Scalar, E> {
+        Ok(Scalar(value.to_string()))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Scalar, E> {
+        Ok(Scalar(value.to_string()))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Scalar, E> {
+        Ok(Scalar(value.to_string()))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Scalar, E> {
+        Err(E::custom(format_args!(
+            "unquoted number {value} would lose how it was written; put it in quotes"
+        )))
+    }
+}
+
+/// Deserializes a string from any scalar, as [`Scalar`] does.
+fn deserialize_scalar<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Scalar::deserialize(deserializer).map(|scalar| scalar.0)
+}
+
+/// Deserializes an optional string from any scalar, as [`Scalar`] does.
+fn deserialize_optional_scalar<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Option::<Scalar>::deserialize(deserializer).map(|scalar| scalar.map(|scalar| scalar.0))
+}
+
+/// Splits markdown content into its front matter and the body after it.
+///
+/// Looks for content between delimiter lines at the start of the file, where
+/// a delimiter is a line of three or more dashes or plus signs (see
+/// [`delimiter_char`]), and the closing line uses the same character as the
+/// opening one. Returns `None` if no valid front matter block is found.
 ///
 /// # Example
 ///
@@ -27,25 +139,113 @@ pub(crate) struct FrontMatter {
 /// ---
 /// # Content here
 /// ```
-pub(crate) fn extract_front_matter(content: &str) -> Option<&str> {
-    let content = content.strip_prefix("---")?;
-    let content = content.strip_prefix(['\n', '\r'])?;
-    let end = content.find("\n---").or_else(|| content.find("\r\n---"))?;
-    Some(&content[..end])
+pub(crate) fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let mut lines = content.split_inclusive('\n');
+    let opening = lines.next()?;
+    let delimiter = delimiter_char(opening)?;
+
+    let start = opening.len();
+    let mut end = start;
+    for line in lines {
+        if delimiter_char(line) == Some(delimiter) {
+            let yaml = &content[start..end];
+            let yaml = yaml.strip_suffix('\n').unwrap_or(yaml);
+            let yaml = yaml.strip_suffix('\r').unwrap_or(yaml);
+            return Some((yaml, &content[end + line.len()..]));
+        }
+        end += line.len();
+    }
+    None
 }
 
-/// Parses a YAML string into a [`FrontMatter`] struct.
+/// Returns the delimiter character if the line is a front matter delimiter:
+/// ignoring surrounding whitespace, it consists solely of three or more
+/// dashes (`-`) or plus signs (`+`).
+fn delimiter_char(line: &str) -> Option<u8> {
+    let trimmed = line.trim();
+    let first = *trimmed.as_bytes().first()?;
+    let is_delimiter = (first == b'-' || first == b'+')
+        && trimmed.len() >= 3
+        && trimmed.bytes().all(|b| b == first);
+    is_delimiter.then_some(first)
+}
+
+/// Resolves `format` for `content`: with [`FrontMatterFormat::Auto`], TOML if
+/// the front matter opens with a `+++` line and YAML otherwise.
+pub(crate) fn resolve_format(format: FrontMatterFormat, content: &str) -> FrontMatterFormat {
+    match format {
+        FrontMatterFormat::Auto => {
+            if opening_delimiter(content) == Some(b'+') {
+                FrontMatterFormat::Toml
+            } else {
+                FrontMatterFormat::Yaml
+            }
+        }
+        forced => forced,
+    }
+}
+
+/// The delimiter character of the front matter `content` opens with, if any.
+pub(crate) fn opening_delimiter(content: &str) -> Option<u8> {
+    delimiter_char(content.split_inclusive('\n').next().unwrap_or_default())
+}
+
+/// Front matter that could not be deserialized.
+#[derive(Debug)]
+pub(crate) struct FrontMatterError {
+    /// Line within the front matter block where the error was found, if known.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for FrontMatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Deserializes front matter text as `format`, YAML unless it is
+/// [`FrontMatterFormat::Toml`].
+///
+/// # Errors
+///
+/// Returns an error if the text is invalid or doesn't match `T`.
+pub(crate) fn deserialize_front_matter<T: DeserializeOwned>(
+    text: &str,
+    format: FrontMatterFormat,
+) -> Result<T, FrontMatterError> {
+    match format {
+        FrontMatterFormat::Toml => toml::from_str(text).map_err(|e| FrontMatterError {
+            line: e
+                .span()
+                .map(|span| text[..span.start].matches('\n').count() + 1),
+            message: e.message().to_string(),
+        }),
+        FrontMatterFormat::Auto | FrontMatterFormat::Yaml => {
+            serde_yaml::from_str(text).map_err(|e| FrontMatterError {
+                line: e.location().map(|location| location.line()),
+                message: e.to_string(),
+            })
+        }
+    }
+}
+
+/// Parses front matter text into a [`FrontMatter`] struct.
 ///
 /// # Errors
 ///
-/// Returns an error if the YAML is invalid or missing required fields.
-pub(crate) fn parse_front_matter(yaml: &str) -> anyhow::Result<FrontMatter> {
-    Ok(serde_yaml::from_str(yaml)?)
+/// Returns an error if the text is invalid or missing required fields.
+pub(crate) fn parse_front_matter(
+    text: &str,
+    format: FrontMatterFormat,
+) -> Result<FrontMatter, FrontMatterError> {
+    deserialize_front_matter(text, format)
 }
 
 /// Extracts the first paragraph after the title from markdown content.
 ///
-/// Skips front matter (if present) and any headings, then returns the first
+/// Skips front matter (if present), headings, and lines made up only of
+/// badges/images or link reference definitions, then returns the first
 /// non-empty paragraph. Multi-line paragraphs are joined with spaces.
 ///
 /// Returns `None` if no paragraph content is found.
@@ -53,15 +253,19 @@ pub(crate) fn extract_first_paragraph(content: &str) -> Option<String> {
     let mut lines = content.lines().peekable();
 
     // Skip front matter if present
-    if lines.peek().is_some_and(|l| l.trim() == "---") {
+    if let Some(delimiter) = lines.peek().and_then(|l| delimiter_char(l)) {
         lines.next();
-        lines.find(|line| line.trim() == "---");
+        lines.find(|line| delimiter_char(line) == Some(delimiter));
     }
 
-    // Skip blank lines and headings until we find paragraph content
-    let first_para_line = lines
-        .by_ref()
-        .find(|line| !line.trim().is_empty() && !line.trim().starts_with('#'))?;
+    // Skip blank lines, headings, and badges until we find paragraph content
+    let first_para_line = lines.by_ref().find(|line| {
+        let trimmed = line.trim();
+        !trimmed.is_empty()
+            && !trimmed.starts_with('#')
+            && !is_badge_line(trimmed)
+            && !is_link_reference_definition(trimmed)
+    })?;
 
     // Collect contiguous non-empty lines into a paragraph
     let mut paragraph = String::from(first_para_line.trim());
@@ -77,10 +281,192 @@ pub(crate) fn extract_first_paragraph(content: &str) -> Option<String> {
     Some(paragraph)
 }
 
+/// Extracts the text of the first level-1 ATX heading (`# Title`) from
+/// markdown content, skipping front matter and fenced code blocks.
+///
+/// A closing sequence of `#`s is dropped. Returns `None` if there is no such
+/// heading.
+pub(crate) fn extract_title(content: &str) -> Option<String> {
+    let mut lines = content.lines().peekable();
+    if let Some(delimiter) = lines.peek().and_then(|l| delimiter_char(l)) {
+        lines.next();
+        lines.find(|line| delimiter_char(line) == Some(delimiter));
+    }
+
+    let mut in_fence = false;
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if let Some(title) = trimmed.strip_prefix("# ").filter(|_| !in_fence) {
+            let title = title.trim_end_matches('#').trim();
+            if !title.is_empty() {
+                return Some(title.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the line consists entirely of images (optionally wrapped
+/// in links, as shields.io badges usually are) and whitespace.
+fn is_badge_line(line: &str) -> bool {
+    let mut rest = line.trim_start();
+    if rest.is_empty() {
+        return false;
+    }
+
+    while !rest.is_empty() {
+        match strip_badge(rest) {
+            Some(remaining) => rest = remaining.trim_start(),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Strips a leading image or link-wrapped image, returning the remaining text.
+fn strip_badge(text: &str) -> Option<&str> {
+    if text.starts_with("![") {
+        return strip_image(text);
+    }
+
+    let inner = strip_image(text.strip_prefix('[')?)?;
+    strip_link_target(inner.strip_prefix(']')?)
+}
+
+/// Strips a leading `![alt](url)` or `![alt][ref]` image.
+fn strip_image(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix("![")?;
+    let end = rest.find(']')?;
+    strip_link_target(&rest[end + 1..])
+}
+
+/// Strips a leading `(url)` or `[ref]` link target.
+fn strip_link_target(text: &str) -> Option<&str> {
+    let (close, rest) = if let Some(rest) = text.strip_prefix('(') {
+        (')', rest)
+    } else {
+        (']', text.strip_prefix('[')?)
+    };
+    let end = rest.find(close)?;
+    Some(&rest[end + 1..])
+}
+
+/// Returns true if the line is a link reference definition, e.g. `[build]: https://...`.
+fn is_link_reference_definition(line: &str) -> bool {
+    line.strip_prefix('[')
+        .and_then(|rest| rest.split_once("]:"))
+        .is_some_and(|(label, _)| !label.is_empty() && !label.contains(']'))
+}
+
+/// Words that end with a period without ending a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "e.g", "i.e", "etc", "vs", "cf", "approx", "incl", "Mr", "Mrs", "Ms", "Dr", "St",
+];
+
+/// Truncates text to its first `sentences` sentences, appending `…` if
+/// anything was cut.
+///
+/// A sentence ends at `.`, `!` or `?` followed by whitespace. Periods after
+/// common abbreviations (`e.g.`, `etc.`) and single-letter initials don't end
+/// a sentence.
+pub(crate) fn truncate_sentences(text: &str, sentences: usize) -> String {
+    let mut count = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let at_boundary = chars.peek().is_some_and(|(_, next)| next.is_whitespace());
+        if !matches!(c, '.' | '!' | '?') || !at_boundary {
+            continue;
+        }
+        if c == '.' && is_abbreviation(&text[..i]) {
+            continue;
+        }
+
+        count += 1;
+        if count >= sentences {
+            return format!("{} …", &text[..=i]);
+        }
+    }
+
+    text.to_string()
+}
+
+/// Drops the markdown links and images that end `text`, along with any
+/// separator left dangling before them, keeping final `.`, `!` or `?`.
+///
+/// Conservative: a link is only dropped when it is a whole trailing token,
+/// and `text` is returned unchanged if nothing would be left of it.
+pub(crate) fn trim_trailing_links(text: &str) -> String {
+    let trimmed = text.trim_end();
+    let body = trimmed.trim_end_matches(['.', '!', '?']);
+    let punctuation = &trimmed[body.len()..];
+
+    let mut rest = body;
+    while let Some(start) = trailing_link_start(rest) {
+        let before = rest[..start].trim_end_matches([' ', ',', ':', ';', '-', '\u{2014}']);
+        if before.is_empty() {
+            break;
+        }
+        rest = before;
+    }
+    if rest.len() == body.len() {
+        return text.to_string();
+    }
+    format!("{}{}", rest, punctuation)
+}
+
+/// Byte offset of the `[text](url)` link or `![alt](url)` image ending
+/// `text`, if it is preceded by whitespace or nothing.
+fn trailing_link_start(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    if bytes.last() != Some(&b')') {
+        return None;
+    }
+    let url_start = matching_open(bytes, bytes.len() - 1, b'(', b')')?;
+    if url_start == 0 || bytes[url_start - 1] != b']' {
+        return None;
+    }
+    let mut start = matching_open(bytes, url_start - 1, b'[', b']')?;
+    if start > 0 && bytes[start - 1] == b'!' {
+        start -= 1;
+    }
+    (start == 0 || bytes[start - 1].is_ascii_whitespace()).then_some(start)
+}
+
+/// Index of the `open` byte matching the `close` byte at `end`, scanning back.
+fn matching_open(bytes: &[u8], end: usize, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0;
+    for i in (0..=end).rev() {
+        if bytes[i] == close {
+            depth += 1;
+        } else if bytes[i] == open {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the word ending `text` is an abbreviation or an initial.
+fn is_abbreviation(text: &str) -> bool {
+    let word = text.rsplit(char::is_whitespace).next().unwrap_or_default();
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let is_initial = word.chars().count() == 1 && word.chars().all(char::is_uppercase);
+    is_initial || ABBREVIATIONS.iter().any(|a| a.eq_ignore_ascii_case(word))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn extract_yaml(content: &str) -> Option<&str> {
+        split_front_matter(content).map(|(yaml, _)| yaml)
+    }
+
     #[test]
     fn test_extract_front_matter_with_valid_yaml() {
         let content = r#"---
@@ -91,7 +477,7 @@ category: "Utilities"
 # Header
 Some content"#;
 
-        let front_matter = extract_front_matter(content);
+        let front_matter = extract_yaml(content);
         assert!(front_matter.is_some());
         let fm = front_matter.unwrap();
         assert!(fm.contains("description"));
@@ -103,14 +489,14 @@ Some content"#;
         let content = r#"# Header
 Some content without front matter"#;
 
-        let front_matter = extract_front_matter(content);
+        let front_matter = extract_yaml(content);
         assert!(front_matter.is_none());
     }
 
     #[test]
     fn test_extract_front_matter_empty_file() {
         let content = "";
-        let front_matter = extract_front_matter(content);
+        let front_matter = extract_yaml(content);
         assert!(front_matter.is_none());
     }
 
@@ -120,22 +506,163 @@ Some content without front matter"#;
 description: "Test"
 No closing delimiter"#;
 
-        let front_matter = extract_front_matter(content);
+        let front_matter = extract_yaml(content);
         assert!(front_matter.is_none());
     }
 
+    #[test]
+    fn test_extract_front_matter_four_dashes() {
+        let content = "----\ncategory: \"Utilities\"\n----\n\n# Header";
+        assert_eq!(extract_yaml(content), Some("category: \"Utilities\""));
+    }
+
+    #[test]
+    fn test_extract_front_matter_trailing_whitespace() {
+        let content = "--- \ncategory: \"Utilities\"\n---\t\r\n\n# Header";
+        assert_eq!(extract_yaml(content), Some("category: \"Utilities\""));
+    }
+
+    #[test]
+    fn test_extract_front_matter_mismatched_dash_counts() {
+        let content = "----\ncategory: \"Utilities\"\n---\n\n# Header";
+        assert_eq!(extract_yaml(content), Some("category: \"Utilities\""));
+    }
+
+    #[test]
+    fn test_extract_front_matter_requires_dashes_only() {
+        let content = "---\ncategory: \"Utilities\"\n---more\n";
+        assert_eq!(extract_yaml(content), None);
+        assert_eq!(extract_yaml("--\ncategory: x\n--\n"), None);
+    }
+
+    #[test]
+    fn test_split_front_matter_body() {
+        let content = "---\ncategory: \"Utilities\"\n---\n\n# Header\nBody";
+        assert_eq!(
+            split_front_matter(content),
+            Some(("category: \"Utilities\"", "\n# Header\nBody"))
+        );
+    }
+
+    #[test]
+    fn test_split_front_matter_plus_delimiters() {
+        let content = "+++\ncategory = \"Utilities\"\n+++\nBody";
+        assert_eq!(
+            split_front_matter(content),
+            Some(("category = \"Utilities\"", "Body"))
+        );
+        // The closing delimiter must match the opening one
+        assert_eq!(extract_yaml("+++\ncategory = \"x\"\n---\n"), None);
+        assert_eq!(
+            extract_first_paragraph("+++\ncategory = \"x\"\n+++\n\nFirst.\n"),
+            Some("First.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_format() {
+        assert_eq!(
+            resolve_format(FrontMatterFormat::Auto, "+++\n"),
+            FrontMatterFormat::Toml
+        );
+        assert_eq!(
+            resolve_format(FrontMatterFormat::Auto, "---\n"),
+            FrontMatterFormat::Yaml
+        );
+        assert_eq!(
+            resolve_format(FrontMatterFormat::Toml, "---\n"),
+            FrontMatterFormat::Toml
+        );
+    }
+
+    #[test]
+    fn test_truncate_sentences() {
+        let text = "Parses input. Validates it! Does it work? Yes.";
+        assert_eq!(truncate_sentences(text, 1), "Parses input. …");
+        assert_eq!(truncate_sentences(text, 2), "Parses input. Validates it! …");
+        assert_eq!(truncate_sentences(text, 4), text);
+        assert_eq!(truncate_sentences(text, 10), text);
+    }
+
+    #[test]
+    fn test_trim_trailing_links() {
+        assert_eq!(
+            trim_trailing_links("Parses input, see the guide [here](https://example.com/guide)."),
+            "Parses input, see the guide."
+        );
+        assert_eq!(
+            trim_trailing_links("Parses input: [docs](a(b)) ![ci](badge.svg)"),
+            "Parses input"
+        );
+    }
+
+    #[test]
+    fn test_trim_trailing_links_is_conservative() {
+        let mid_sentence = "Wraps [serde](https://serde.rs) for config files.";
+        assert_eq!(trim_trailing_links(mid_sentence), mid_sentence);
+        // Not a whole token, or nothing left without it
+        assert_eq!(trim_trailing_links("See x[y](z)"), "See x[y](z)");
+        assert_eq!(
+            trim_trailing_links("[Guide](guide.md)."),
+            "[Guide](guide.md)."
+        );
+        assert_eq!(trim_trailing_links("Ends (in parens)"), "Ends (in parens)");
+    }
+
+    #[test]
+    fn test_truncate_sentences_abbreviations() {
+        let text = "Handles formats, e.g. JSON and YAML, etc. for J. Smith. Second sentence.";
+        assert_eq!(
+            truncate_sentences(text, 1),
+            "Handles formats, e.g. JSON and YAML, etc. for J. Smith. …"
+        );
+        assert_eq!(
+            truncate_sentences("Version 1.2 is out. More.", 1),
+            "Version 1.2 is out. …"
+        );
+    }
+
+    #[test]
+    fn test_parse_front_matter_display_title_spellings() {
+        for key in ["display_title", "displayTitle", "display-title"] {
+            let yaml = format!("category: \"Utilities\"\n{}: \"Core Library\"", key);
+            let front_matter = parse_front_matter(&yaml, FrontMatterFormat::Yaml).unwrap();
+            assert_eq!(
+                front_matter.display_title.as_deref(),
+                Some("Core Library"),
+                "{}",
+                key
+            );
+            assert!(front_matter.extra.is_empty(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_parse_front_matter_description_from_spellings() {
+        for key in ["description_from", "descriptionFrom", "description-from"] {
+            let yaml = format!("category: \"Utilities\"\n{}: \"docs/overview.md\"", key);
+            let front_matter = parse_front_matter(&yaml, FrontMatterFormat::Yaml).unwrap();
+            assert_eq!(
+                front_matter.description_from,
+                Some(PathBuf::from("docs/overview.md")),
+                "{}",
+                key
+            );
+        }
+    }
+
     #[test]
     fn test_parse_front_matter_valid() {
         let yaml = r#"description: "Core utilities for the project"
 category: "Utilities""#;
 
-        let result = parse_front_matter(yaml);
+        let result = parse_front_matter(yaml, FrontMatterFormat::Yaml);
         assert!(result.is_ok());
 
         let front_matter = result.unwrap();
         assert_eq!(
             front_matter.description,
-            Some("Core utilities for the project".to_string())
+            Some("Core utilities for the project".into())
         );
         assert_eq!(front_matter.category, "Utilities");
     }
@@ -144,18 +671,94 @@ category: "Utilities""#;
     fn test_parse_front_matter_missing_description() {
         let yaml = r#"category: "Utilities""#;
 
-        let result = parse_front_matter(yaml);
+        let result = parse_front_matter(yaml, FrontMatterFormat::Yaml);
         assert!(result.is_ok());
         let front_matter = result.unwrap();
         assert_eq!(front_matter.description, None);
         assert_eq!(front_matter.category, "Utilities");
     }
 
+    #[test]
+    fn test_parse_front_matter_coerces_scalars() {
+        let front_matter =
+            parse_front_matter("category: 2024\ndescription: true", FrontMatterFormat::Yaml)
+                .unwrap();
+        assert_eq!(front_matter.category, "2024");
+        assert_eq!(front_matter.description, Some("true".into()));
+
+        let front_matter = parse_front_matter(
+            "category: false\ndescription: [15, \"More\"]",
+            FrontMatterFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(front_matter.category, "false");
+        assert_eq!(
+            front_matter.description,
+            Some(vec!["15".to_string(), "More".to_string()].into())
+        );
+
+        let front_matter =
+            parse_front_matter("category = 7\ndescription = 42", FrontMatterFormat::Toml).unwrap();
+        assert_eq!(front_matter.category, "7");
+        assert_eq!(front_matter.description, Some("42".into()));
+    }
+
+    #[test]
+    fn test_parse_front_matter_coerces_since() {
+        let front_matter =
+            parse_front_matter("category: Core\nsince: 2", FrontMatterFormat::Yaml).unwrap();
+        assert_eq!(front_matter.since.as_deref(), Some("2"));
+
+        let front_matter = parse_front_matter("category: Core", FrontMatterFormat::Yaml).unwrap();
+        assert_eq!(front_matter.since, None);
+
+        let err =
+            parse_front_matter("category: Core\nsince: 1.10", FrontMatterFormat::Yaml).unwrap_err();
+        assert!(err.message.contains("put it in quotes"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_front_matter_rejects_unquoted_floats() {
+        let err = parse_front_matter("category: 1.10", FrontMatterFormat::Yaml).unwrap_err();
+        assert!(err.message.contains("put it in quotes"), "{}", err);
+
+        let err = parse_front_matter(
+            "category = \"Core\"\ndescription = 1e3",
+            FrontMatterFormat::Toml,
+        )
+        .unwrap_err();
+        assert!(err.message.contains("put it in quotes"), "{}", err);
+
+        let front_matter =
+            parse_front_matter("category: \"1.10\"", FrontMatterFormat::Yaml).unwrap();
+        assert_eq!(front_matter.category, "1.10");
+    }
+
+    #[test]
+    fn test_parse_front_matter_rejects_non_scalar_category() {
+        let err = parse_front_matter("category: [a, b]", FrontMatterFormat::Yaml).unwrap_err();
+        assert!(
+            err.message.contains("a string, number or boolean"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_front_matter_owners() {
+        let front_matter = parse_front_matter(
+            "category: Core\nowners: [platform, web]",
+            FrontMatterFormat::Yaml,
+        )
+        .unwrap();
+        assert_eq!(front_matter.owners, vec!["platform", "web"]);
+    }
+
     #[test]
     fn test_parse_front_matter_missing_category() {
         let yaml = r#"description: "Core utilities""#;
 
-        let result = parse_front_matter(yaml);
+        let result = parse_front_matter(yaml, FrontMatterFormat::Yaml);
         assert!(result.is_err());
     }
 
@@ -163,7 +766,7 @@ category: "Utilities""#;
     fn test_parse_front_matter_invalid_yaml() {
         let yaml = r#"this is not valid yaml: ["#;
 
-        let result = parse_front_matter(yaml);
+        let result = parse_front_matter(yaml, FrontMatterFormat::Yaml);
         assert!(result.is_err());
     }
 
@@ -172,13 +775,40 @@ category: "Utilities""#;
         let yaml = r#"description: "Parser with **markdown** and `code` formatting"
 category: "Utilities""#;
 
-        let result = parse_front_matter(yaml);
+        let result = parse_front_matter(yaml, FrontMatterFormat::Yaml);
         assert!(result.is_ok());
 
         let front_matter = result.unwrap();
         assert_eq!(
             front_matter.description,
-            Some("Parser with **markdown** and `code` formatting".to_string())
+            Some("Parser with **markdown** and `code` formatting".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_front_matter_retains_extra_keys() {
+        let yaml = r#"category: "Utilities"
+jira: "CORE"
+slack_channel: "core-team""#;
+
+        let front_matter = parse_front_matter(yaml, FrontMatterFormat::Yaml).unwrap();
+        assert_eq!(front_matter.extra.len(), 2);
+        assert_eq!(
+            front_matter.extra.get("jira"),
+            Some(&serde_yaml::Value::from("CORE"))
+        );
+        assert!(!front_matter.extra.contains_key("category"));
+    }
+
+    #[test]
+    fn test_extract_title() {
+        assert_eq!(
+            extract_title("---\ntitle: x\n---\n## Sub\n\n# Project #\n\n# Other\n"),
+            Some("Project".to_string())
+        );
+        assert_eq!(
+            extract_title("```sh\n# comment\n```\n#Not a heading\n"),
+            None
         );
     }
 
@@ -213,6 +843,15 @@ More content here."#;
         );
     }
 
+    #[test]
+    fn test_extract_first_paragraph_with_four_dash_front_matter() {
+        let content = "---- \ncategory: \"Testing\"\n----\n\n# Title\n\nFirst paragraph.";
+        assert_eq!(
+            extract_first_paragraph(content),
+            Some("First paragraph.".to_string())
+        );
+    }
+
     #[test]
     fn test_extract_first_paragraph_multiline() {
         let content = r#"# Title
@@ -252,4 +891,48 @@ This is the second paragraph."#;
         let result = extract_first_paragraph(content);
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_extract_first_paragraph_skips_badge_row() {
+        let content = r#"# Title
+
+[![build](https://img.shields.io/badge/build-passing-green)](https://ci.example.com) ![license](https://img.shields.io/badge/license-MIT-blue)
+![crates.io][crates-badge]
+
+[crates-badge]: https://img.shields.io/crates/v/example
+
+This is the real description."#;
+
+        let result = extract_first_paragraph(content);
+        assert_eq!(result, Some("This is the real description.".to_string()));
+    }
+
+    #[test]
+    fn test_extract_first_paragraph_keeps_mixed_badge_and_text() {
+        let content = r#"# Title
+
+![status](https://img.shields.io/badge/status-beta-yellow) A parser for config files."#;
+
+        let result = extract_first_paragraph(content);
+        assert_eq!(
+            result,
+            Some(
+                "![status](https://img.shields.io/badge/status-beta-yellow) A parser for config files."
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_extract_first_paragraph_keeps_text_link() {
+        let content = r#"# Title
+
+[Docs](https://example.com) explain everything."#;
+
+        let result = extract_first_paragraph(content);
+        assert_eq!(
+            result,
+            Some("[Docs](https://example.com) explain everything.".to_string())
+        );
+    }
 }
@@ -6,23 +6,101 @@
 
 use itertools::Itertools;
 
-use crate::component::Component;
-use crate::config::Config;
-use std::collections::HashMap;
+use crate::atom::escape_xml;
+use crate::component::{Component, Description, DescriptionSource};
+use crate::config::{
+    AnchorStyle, CategoryOrder, Config, GroupBy, IndexStyle, LegendPosition, Pin, SortOrder,
+    DEPRECATED_SECTION, PLANNED_SECTION,
+};
+use crate::dependencies::sort_by_dependencies;
+use crate::front_matter::truncate_sentences;
+use crate::slug::slugify;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Write;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
 
 /// Generates architecture documentation from a list of components.
 ///
 /// Produces a markdown document with:
 /// - A title (from config or default)
 /// - An optional document description
-/// - Sections for each category, containing component entries
+/// - Sections for each category, containing component entries, or for each
+///   release cohort with `group_by = "release"` or each owner with
+///   `group_by = "owner"`
+/// - A legend of the statuses and tags shown, if any, before or after the
+///   categories per `legend`
 ///
 /// Categories are ordered according to the config, with any unlisted
 /// categories appended alphabetically. Components within each category
-/// are sorted by path.
+/// are sorted by path, or by dependencies when configured via `sort`.
+///
+/// The document ends with exactly one newline, or none with
+/// `final_newline = false`.
 pub fn generate_document(components: &[Component], config: &Config) -> String {
-    let mut doc = format!("# {}\n", config.title());
+    with_final_newline(render_document(components, config), config)
+}
+
+/// Generates the document like [`generate_document`], after letting `hook`
+/// change the components.
+///
+/// The hook gets its own copy of the components and may edit, add or remove
+/// them, e.g. to rewrite descriptions or add computed tags; grouping and
+/// rendering then see the result.
+///
+/// # Example
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_architecture::{generate_document_with, Component, Config};
+///
+/// let components = vec![Component {
+///     path: PathBuf::from("core/README.md"),
+///     description: "Core lib".into(),
+///     category: "core".to_string(),
+///     ..Default::default()
+/// }];
+///
+/// let doc = generate_document_with(&components, &Config::default(), |components| {
+///     for component in components.iter_mut() {
+///         component.category = component.category.to_uppercase();
+///     }
+/// });
+/// assert!(doc.contains("\n## CORE\n\n- `core/README.md`: Core lib\n"));
+/// ```
+pub fn generate_document_with<F>(components: &[Component], config: &Config, mut hook: F) -> String
+where
+    F: FnMut(&mut Vec<Component>),
+{
+    let mut components = components.to_vec();
+    hook(&mut components);
+    generate_document(&components, config)
+}
+
+/// Ends `text` with exactly one newline, or none with `final_newline = false`.
+///
+/// Trailing blank lines are dropped either way, so output compares equal
+/// whatever trailing newlines a template or previous run left behind.
+pub fn with_final_newline(mut text: String, config: &Config) -> String {
+    text.truncate(text.trim_end_matches(['\n', '\r']).len());
+    if config.final_newline() {
+        text.push('\n');
+    }
+    text
+}
+
+/// Renders the document for [`generate_document`], before its final newline
+/// is normalized.
+fn render_document(components: &[Component], config: &Config) -> String {
+    if let Some(template) = &config.template {
+        return render_skeleton(template, components, config);
+    }
+
+    let mut doc = format!("{} {}\n", heading(1, config), config.title());
 
     // Add document description if present
     if let Some(desc) = &config.description {
@@ -33,286 +111,2819 @@ pub fn generate_document(components: &[Component], config: &Config) -> String {
         return doc;
     }
 
-    let grouped = group_by_category(components);
+    let grouped = group_by_category(components, config);
     let ordered_categories = order_categories(&grouped, config);
 
-    // Generate output for each category
-    for category_name in ordered_categories {
-        if let Some(comps) = grouped.get(category_name) {
-            // Get display title from config or use raw category name
-            let display_title = config.display_title_for(category_name);
-            writeln!(doc, "\n## {}", display_title).unwrap();
-
-            // Add category description if present in config
-            if let Some(desc) = config
-                .get_category(category_name)
-                .and_then(|c| c.description.as_deref())
-            {
-                writeln!(doc, "\n{}", desc.trim_end()).unwrap();
+    if config.overview {
+        writeln!(
+            doc,
+            "\nThis document describes {} across {}.",
+            pluralize(
+                components.iter().filter(|c| is_shown(c, config)).count(),
+                "component",
+                "components"
+            ),
+            pluralize(grouped.len(), "category", "categories")
+        )
+        .unwrap();
+    }
+
+    let links = RelatedLinks::new(components).anchoring_all(config.index);
+    let cohorts = flat_sections(components, config);
+    let sections = match cohorts {
+        Some(_) => Vec::new(),
+        None => category_sections(&ordered_categories, config),
+    };
+    if config.toc {
+        doc.push('\n');
+        for (title, _) in cohorts.iter().flatten() {
+            writeln!(doc, "- [{}](#{})", title, slugify(title)).unwrap();
+        }
+        for (depth, category_name) in &sections {
+            writeln!(
+                doc,
+                "{}- [{}](#{})",
+                "  ".repeat(depth - 1),
+                config.display_title_for(category_name),
+                category_anchor(category_name, config)
+            )
+            .unwrap();
+        }
+    }
+
+    let legend = render_legend(components, config);
+    if config.legend == LegendPosition::Top {
+        doc.push_str(legend.as_deref().unwrap_or_default());
+    }
+
+    for (title, comps) in cohorts.iter().flatten() {
+        writeln!(
+            doc,
+            "\n{}\n",
+            anchored_heading(2, title, &slugify(title), config)
+        )
+        .unwrap();
+        for comp in comps {
+            doc.push_str(&render_entry_in(comp, &links, config, |target| {
+                format!("#{}", entry_anchor(target))
+            }));
+        }
+    }
+
+    // Generate output for each category; parents of nested categories that
+    // have no components of their own get just their heading and description
+    for (depth, category_name) in &sections {
+        let comps = grouped.get(category_name);
+        let collapsible = comps.is_some() && config.is_collapsible(category_name);
+        if collapsible {
+            // The heading stays inside the block so its anchor keeps working
+            writeln!(
+                doc,
+                "\n<details{}>\n<summary>{}</summary>",
+                if config.collapsed_by_default {
+                    ""
+                } else {
+                    " open"
+                },
+                escape_xml(config.display_title_for(category_name))
+            )
+            .unwrap();
+        }
+        writeln!(
+            doc,
+            "\n{}",
+            category_heading(depth + 1, category_name, config)
+        )
+        .unwrap();
+
+        if let Some(levels) = breadcrumb_levels(category_name, config) {
+            let (current, parents) = levels.split_last().unwrap();
+            let crumbs = parents
+                .iter()
+                .map(|(title, anchor)| format!("[{}](#{})", title, anchor))
+                .chain([current.0.clone()])
+                .join(BREADCRUMB_SEPARATOR);
+            writeln!(doc, "\n{}", crumbs).unwrap();
+        }
+
+        // Add category description if present in config
+        if let Some(desc) = config
+            .get_category(category_name)
+            .and_then(|c| c.description.as_deref())
+        {
+            writeln!(doc, "\n{}", desc.trim_end()).unwrap();
+        }
+
+        if let Some(comps) = comps {
+            doc.push('\n');
+            for comp in comps {
+                doc.push_str(&render_entry_in(comp, &links, config, |target| {
+                    format!("#{}", entry_anchor(target))
+                }));
+            }
+        }
+
+        if collapsible {
+            doc.push_str("\n</details>\n");
+        }
+    }
+
+    doc.push_str(&render_index(components, config).unwrap_or_default());
+
+    if config.legend == LegendPosition::Bottom {
+        doc.push_str(legend.as_deref().unwrap_or_default());
+    }
+
+    doc
+}
+
+/// Renders an `Index` section listing every component by title, linked to its
+/// category's heading, or `None` unless `index` is set.
+///
+/// Each title links to its component's entry anchor (see [`entry_anchor`]).
+/// With `index_style = "alphabetical"`, entries are grouped under a heading per
+/// first letter, skipping letters no title starts with, after a bar linking to
+/// each heading. Titles that don't start with a letter are grouped last, under
+/// `Other`. Letter headings are anchored as `index-a` and so on, so they can't
+/// collide with a category's anchor.
+fn render_index(components: &[Component], config: &Config) -> Option<String> {
+    if !config.index || components.is_empty() {
+        return None;
+    }
+
+    let mut entries: Vec<(Option<char>, String, &Component)> = components
+        .iter()
+        .filter(|comp| is_shown(comp, config))
+        .map(|comp| {
+            let title = comp.title();
+            (index_letter(&title), title, comp)
+        })
+        .collect();
+    // Letters first, then titles that don't start with one
+    entries.sort_by(|(a_letter, a_title, a), (b_letter, b_title, b)| {
+        (
+            a_letter.is_none(),
+            a_letter,
+            a_title.to_lowercase(),
+            &a.path,
+        )
+            .cmp(&(
+                b_letter.is_none(),
+                b_letter,
+                b_title.to_lowercase(),
+                &b.path,
+            ))
+    });
+    let entry = |title: &str, comp: &Component| {
+        let category = config.canonical_category(&comp.category);
+        let mut line = format!("- [{}](#{})", title, entry_anchor(comp));
+        if let Some(levels) = breadcrumb_levels(category, config) {
+            let crumbs = levels
+                .iter()
+                .map(|(title, _)| title)
+                .join(BREADCRUMB_SEPARATOR);
+            write!(line, " _({})_", crumbs).unwrap();
+        }
+        line.push('\n');
+        line
+    };
+
+    let mut index = format!("\n{} Index\n\n", heading(2, config));
+    match config.index_style {
+        IndexStyle::List => {
+            for (_, title, comp) in &entries {
+                index.push_str(&entry(title, comp));
+            }
+        }
+        IndexStyle::Alphabetical => {
+            let groups = entries.iter().chunk_by(|(letter, _, _)| *letter);
+            let groups: Vec<(String, Vec<_>)> = groups
+                .into_iter()
+                .map(|(letter, group)| {
+                    let name = letter.map_or_else(|| "Other".to_string(), String::from);
+                    (name, group.collect())
+                })
+                .collect();
+
+            let nav = groups
+                .iter()
+                .map(|(name, _)| format!("[{}](#{})", name, index_letter_anchor(name)))
+                .join(" · ");
+            writeln!(index, "{}", nav).unwrap();
+            for (name, group) in &groups {
+                writeln!(
+                    index,
+                    "\n{}\n",
+                    anchored_heading(3, name, &index_letter_anchor(name), config)
+                )
+                .unwrap();
+                for (_, title, comp) in group {
+                    index.push_str(&entry(title, comp));
+                }
+            }
+        }
+    }
+    Some(index)
+}
+
+/// The anchor of an alphabetical index's heading for `letter`.
+fn index_letter_anchor(letter: &str) -> String {
+    format!("index-{}", slugify(letter))
+}
+
+/// Text between the levels of a breadcrumb.
+const BREADCRUMB_SEPARATOR: &str = " \u{203a} ";
+
+/// The display title and heading anchor of each level of a nested category,
+/// outermost first, or `None` for a single-level one or unless `breadcrumbs`
+/// is set.
+fn breadcrumb_levels(category: &str, config: &Config) -> Option<Vec<(String, String)>> {
+    let segments = config.category_segments(category);
+    if !config.breadcrumbs || segments.len() < 2 {
+        return None;
+    }
+    let levels = (1..=segments.len())
+        .map(|depth| {
+            let level = segments[..depth].join(config.category_separator());
+            (
+                config.display_title_for(&level).to_string(),
+                category_anchor(&level, config),
+            )
+        })
+        .collect();
+    Some(levels)
+}
+
+/// The uppercased first character of an index title, if it is a letter.
+fn index_letter(title: &str) -> Option<char> {
+    title
+        .chars()
+        .next()
+        .filter(|c| c.is_alphabetic())
+        .map(|c| c.to_uppercase().next().unwrap_or(c))
+}
+
+/// Renders a legend section listing each status badge and tag chip shown in
+/// the document with its configured description, or `None` if none is shown.
+///
+/// An entry shows its status and tags unless its entry template leaves out
+/// `{status}` or `{tags}`; without a template, tags are shown only with
+/// `show_tags`.
+fn render_legend(components: &[Component], config: &Config) -> Option<String> {
+    let shown = || components.iter().filter(|c| is_shown(c, config));
+    let renders = |comp: &Component, placeholder: &str, by_default: bool| match config
+        .entry_template_for(&comp.category)
+    {
+        Some(template) => template.contains(placeholder),
+        None => by_default,
+    };
+    let statuses: BTreeSet<&str> = shown()
+        .filter(|c| renders(c, "{status}", true))
+        .filter_map(|c| c.status.as_deref())
+        .collect();
+    let tags: BTreeSet<&str> = shown()
+        .filter(|c| renders(c, "{tags}", config.show_tags))
+        .flat_map(|c| c.tags.iter().map(String::as_str))
+        .collect();
+    if statuses.is_empty() && tags.is_empty() {
+        return None;
+    }
+
+    let mut legend = format!("\n{} Legend\n\n", heading(DEEPEST_HEADING, config));
+    let items = statuses
+        .into_iter()
+        .map(|status| (status_badge(status), config.statuses.get(status)))
+        .chain(
+            tags.into_iter()
+                .map(|tag| (tag_chip(tag), config.tag_descriptions.get(tag))),
+        );
+    for (marker, description) in items {
+        match description {
+            Some(description) => writeln!(legend, "- {}: {}", marker, description),
+            None => writeln!(legend, "- {}", marker),
+        }
+        .unwrap();
+    }
+    Some(legend)
+}
+
+/// The badge shown after a description for a component's status.
+fn status_badge(status: &str) -> String {
+    format!("**[{}]**", status)
+}
+
+/// The chip shown after a description for each of a component's tags.
+fn tag_chip(tag: &str) -> String {
+    format!("`#{}`", tag)
+}
+
+/// Renders the whole document from a skeleton template.
+///
+/// Outside the categories section, `{{title}}` and `{{description}}` refer to
+/// the document, and `{{legend}}` and `{{index}}` are its legend and index
+/// sections, if any. The text between `{{#categories}}` and `{{/categories}}`
+/// is repeated for each category in order, where `{{title}}`,
+/// `{{description}}`, `{{count}}`, `{{entries}}` and
+/// `{{category.extra.<key>}}` refer to the category. Unknown placeholders are
+/// left untouched.
+fn render_skeleton(template: &str, components: &[Component], config: &Config) -> String {
+    const OPEN: &str = "{{#categories}}";
+    const CLOSE: &str = "{{/categories}}";
+
+    let document_value = |key: &str| match key {
+        "title" => Some(config.title().to_string()),
+        "description" => Some(config.description.clone().unwrap_or_default()),
+        "legend" => Some(render_legend(components, config).unwrap_or_default()),
+        "index" => Some(render_index(components, config).unwrap_or_default()),
+        _ => None,
+    };
+
+    let Some((before, rest)) = template.split_once(OPEN) else {
+        return fill_placeholders(template, document_value);
+    };
+    let (section, after) = rest.split_once(CLOSE).unwrap_or((rest, ""));
+
+    let grouped = group_by_category(components, config);
+    let links = RelatedLinks::new(components).anchoring_all(config.index);
+    let mut doc = fill_placeholders(before, document_value);
+    for category_name in order_categories(&grouped, config) {
+        let comps = &grouped[category_name];
+        doc.push_str(&fill_placeholders(section, |key| match key {
+            "title" => Some(config.display_title_for(category_name).to_string()),
+            "description" => Some(
+                config
+                    .get_category(category_name)
+                    .and_then(|c| c.description.clone())
+                    .unwrap_or_default(),
+            ),
+            "count" => Some(comps.len().to_string()),
+            "entries" => Some(
+                comps
+                    .iter()
+                    .map(|c| {
+                        render_entry_in(c, &links, config, |target| {
+                            format!("#{}", entry_anchor(target))
+                        })
+                    })
+                    .collect(),
+            ),
+            key if key.starts_with("category.extra.") => {
+                Some(category_extra_value(category_name, key, config))
+            }
+            _ => None,
+        }));
+    }
+    doc.push_str(&fill_placeholders(after, document_value));
+    doc
+}
+
+/// Replaces `{{key}}` placeholders using `value`, leaving unknown ones untouched.
+fn fill_placeholders(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let key = &after[..end];
+        match value(key.trim()) {
+            Some(value) => out.push_str(&value),
+            None => write!(out, "{{{{{}}}}}", key).unwrap(),
+        }
+        rest = &after[end + 2..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Renders a single component's entry as [`generate_document`] does, apart
+/// from the cross-links of its `related` field, which need the other components.
+///
+/// The result is the entry line, using the category's or else the document's
+/// `entry_template` if configured and the component's `link`, status badge
+/// and (with `show_tags`) tag chips otherwise, with the path struck through
+/// for deprecated components and an italic `(planned)` marker after the
+/// description of planned ones, followed by its embedded body
+/// when `embed_bodies` is set. It always ends with a newline.
+pub fn render_entry(comp: &Component, config: &Config) -> String {
+    render_entry_with(comp, config, None, None)
+}
+
+/// Renders a component's entry among the components of `links`, adding a
+/// "See also" line for its `related` components.
+///
+/// Related components link to `target_link(target)` when `explicit_anchors` is
+/// set, in which case every entry some other entry links to carries an inline
+/// `<a name>` anchor; otherwise, and for references that match no component,
+/// they are listed as plain text. See [`check_related`](crate::check_related)
+/// for reporting unmatched references.
+pub(crate) fn render_entry_in(
+    comp: &Component,
+    links: &RelatedLinks,
+    config: &Config,
+    target_link: impl Fn(&Component) -> String,
+) -> String {
+    let anchor = links.needs_anchor(comp, config).then(|| entry_anchor(comp));
+
+    let see_also = (!comp.related.is_empty()).then(|| {
+        comp.related
+            .iter()
+            .map(|id| match links.resolve(id) {
+                Some(target) if config.explicit_anchors => {
+                    format!("[{}]({})", target.title(), target_link(target))
+                }
+                Some(target) => target.title(),
+                None => id.clone(),
+            })
+            .join(", ")
+    });
+
+    render_entry_with(comp, config, anchor.as_deref(), see_also.as_deref())
+}
+
+/// The `related` references among a set of components, resolved once so
+/// rendering each entry doesn't search the whole set again.
+pub(crate) struct RelatedLinks<'a> {
+    components: &'a [Component],
+    by_name: HashMap<String, usize>,
+    by_path: HashMap<&'a Path, usize>,
+    linked: HashSet<&'a Path>,
+    anchor_all: bool,
+}
+
+impl<'a> RelatedLinks<'a> {
+    /// Indexes `components` by name, README path and directory, and records
+    /// which of them some component's `related` field names.
+    pub(crate) fn new(components: &'a [Component]) -> Self {
+        let mut by_name = HashMap::new();
+        let mut by_path = HashMap::new();
+        // The first component with a key wins, as a search in order would find
+        for (i, comp) in components.iter().enumerate() {
+            by_name.entry(comp.name()).or_insert(i);
+            by_path.entry(comp.path.as_path()).or_insert(i);
+            if let Some(parent) = comp.path.parent() {
+                by_path.entry(parent).or_insert(i);
+            }
+        }
+
+        let mut links = RelatedLinks {
+            components,
+            by_name,
+            by_path,
+            linked: HashSet::new(),
+            anchor_all: false,
+        };
+        links.linked = components
+            .iter()
+            .flat_map(|comp| &comp.related)
+            .filter_map(|id| links.resolve(id))
+            .map(|target| target.path.as_path())
+            .collect();
+        links
+    }
+
+    /// Finds the component a `related` reference names: by its
+    /// [`name`](Component::name), its README path or its directory. A
+    /// reference several components match names the first of them.
+    pub(crate) fn resolve(&self, id: &str) -> Option<&'a Component> {
+        let id = id.trim().trim_end_matches('/');
+        let by_name = self.by_name.get(id);
+        let by_path = self.by_path.get(Path::new(id));
+        let index = by_name.into_iter().chain(by_path).min()?;
+        Some(&self.components[*index])
+    }
+
+    /// Gives every entry an anchor if `anchor_all` is set, for a document
+    /// whose index links to each of them.
+    pub(crate) fn anchoring_all(mut self, anchor_all: bool) -> Self {
+        self.anchor_all = anchor_all;
+        self
+    }
+
+    /// Whether some component's `related` field names `comp`.
+    fn is_linked(&self, comp: &Component) -> bool {
+        self.linked.contains(comp.path.as_path())
+    }
+
+    /// Whether `comp`'s entry carries its anchor: when every entry does, or
+    /// when `explicit_anchors` is set and another entry links to it.
+    fn needs_anchor(&self, comp: &Component, config: &Config) -> bool {
+        self.anchor_all || (config.explicit_anchors && self.is_linked(comp))
+    }
+}
+
+/// The anchor of a component's entry: the slug of its directory, prefixed so
+/// it can't collide with a category's anchor.
+pub(crate) fn entry_anchor(comp: &Component) -> String {
+    format!(
+        "component-{}",
+        slugify(&comp.path.parent().unwrap_or(&comp.path).to_string_lossy())
+    )
+}
+
+/// Renders an entry, optionally with an inline `anchor` at the start of its
+/// line and a `see_also` line under it.
+fn render_entry_with(
+    comp: &Component,
+    config: &Config,
+    anchor: Option<&str>,
+    see_also: Option<&str>,
+) -> String {
+    let description = entry_description(comp, config);
+    let template = config.entry_template_for(&comp.category);
+    let mut entry = match template {
+        Some(template) => render_entry_template(template, comp, config),
+        None => {
+            let display_path = display_path(comp, config);
+            let path = if comp.deprecated {
+                format!("~~`{}`~~", display_path)
+            } else {
+                format!("`{}`", display_path)
+            };
+            let mut line = match entry_link(comp, config) {
+                Some(link) => format!(
+                    "- [{}]({}){}{}",
+                    path,
+                    link,
+                    config.separator(),
+                    description.primary()
+                ),
+                None => format!("- {}{}{}", path, config.separator(), description.primary()),
+            };
+            if comp.planned {
+                line.push_str(" _(planned)_");
+            }
+            if let Some(status) = &comp.status {
+                write!(line, " {}", status_badge(status)).unwrap();
+            }
+            if config.group_by == GroupBy::Owner {
+                write!(line, " _({})_", category_title(comp, config)).unwrap();
+            }
+            if config.show_tags {
+                for tag in sorted_tags(comp) {
+                    write!(line, " {}", tag_chip(tag)).unwrap();
+                }
+            }
+            line
+        }
+    };
+    if let Some(anchor) = anchor {
+        let marker = if entry.starts_with("- ") { 2 } else { 0 };
+        entry.insert_str(marker, &format!("<a name=\"{}\"></a>", anchor));
+    }
+    entry.push('\n');
+    // A template places continuation lines itself with `{continuation}`
+    if template.is_none() {
+        for line in description.continuation() {
+            writeln!(entry, "  {}", line).unwrap();
+        }
+    }
+    if let Some(see_also) = see_also {
+        writeln!(entry, "  See also: {}", see_also).unwrap();
+    }
+
+    if config.embed_bodies {
+        if let Some(body) = comp.body.as_deref().filter(|b| !b.is_empty()) {
+            let body = if config.demote_headings {
+                demote_headings(body, DEEPEST_HEADING + config.heading_offset)
+            } else {
+                body.to_string()
+            };
+            write!(
+                entry,
+                "\n<details>\n<summary>{}</summary>\n\n{}\n\n</details>\n\n",
+                comp.path.display(),
+                body
+            )
+            .unwrap();
+        }
+    }
+
+    entry
+}
+
+/// The description a component is documented with: a first-paragraph fallback
+/// cut to the config's `description_sentences` sentences, if set, or else the
+/// component's own.
+pub(crate) fn entry_description<'a>(comp: &'a Component, config: &Config) -> Cow<'a, Description> {
+    match config.description_sentences {
+        Some(sentences) if comp.description_source == DescriptionSource::FirstParagraph => {
+            Cow::Owned(truncate_sentences(comp.description.primary(), sentences).into())
+        }
+        _ => Cow::Borrowed(&comp.description),
+    }
+}
+
+/// The link an entry's path is rendered with: the component's `link`, or its
+/// own path if `strip_filename` displays it as a directory, so it still links
+/// to the file.
+pub(crate) fn entry_link(comp: &Component, config: &Config) -> Option<String> {
+    comp.link.clone().or_else(|| {
+        let path = comp.path.display().to_string();
+        (display_path(comp, config) != path).then_some(path)
+    })
+}
+
+/// The path an entry displays: the component's path without the config's
+/// `strip_filename`, if it ends with that filename and has a directory left.
+fn display_path(comp: &Component, config: &Config) -> String {
+    let stripped = config.strip_filename.as_deref().and_then(|filename| {
+        let parent = comp.path.parent()?;
+        (comp.path.file_name()? == filename && !parent.as_os_str().is_empty()).then_some(parent)
+    });
+    stripped.unwrap_or(&comp.path).display().to_string()
+}
+
+/// Formats a count with the singular or plural form of a noun, e.g. `1 category`.
+fn pluralize(count: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+/// The deepest heading level the generator emits before `heading_offset`.
+pub(crate) const DEEPEST_HEADING: usize = 2;
+
+/// The ATX marker for a heading at `level`, shifted by `heading_offset` and
+/// capped at `######`.
+pub(crate) fn heading(level: usize, config: &Config) -> String {
+    "#".repeat((level + config.heading_offset).min(6))
+}
+
+/// The anchor that links to a category's heading: the slug of the display
+/// titles of each of its levels, so `Backend/Storage` and `Frontend/Storage`
+/// get `backend-storage` and `frontend-storage`. A single-level category's is
+/// the slug of its display title.
+pub(crate) fn category_anchor(category: &str, config: &Config) -> String {
+    let segments = config.category_segments(category);
+    let titles: Vec<String> = (1..=segments.len())
+        .map(|depth| {
+            let level = segments[..depth].join(config.category_separator());
+            config.display_title_for(&level).to_string()
+        })
+        .collect();
+    slugify(&titles.join(" "))
+}
+
+/// Renders a category's heading line at `level`, using its display title.
+///
+/// The heading carries its [`category_anchor`] as described for
+/// [`anchored_heading`].
+pub(crate) fn category_heading(level: usize, category: &str, config: &Config) -> String {
+    anchored_heading(
+        level,
+        config.display_title_for(category),
+        &category_anchor(category, config),
+        config,
+    )
+}
+
+/// Renders a heading line at `level`, carrying `anchor` either as an
+/// `<a name>` line before it or as a `{#slug}` attribute, per `anchor_style`.
+///
+/// The anchor is left out unless `explicit_anchors` is set or it differs from
+/// the slug of `title`, which renderers derive on their own.
+fn anchored_heading(level: usize, title: &str, anchor: &str, config: &Config) -> String {
+    let marker = heading(level, config);
+    if !config.explicit_anchors && anchor == slugify(title) {
+        return format!("{} {}", marker, title);
+    }
+
+    match config.anchor_style {
+        AnchorStyle::Html => format!("<a name=\"{}\"></a>\n{} {}", anchor, marker, title),
+        AnchorStyle::Attribute => format!("{} {} {{#{}}}", marker, title, anchor),
+    }
+}
+
+/// Demotes ATX headings by `levels`, capping at `######`.
+///
+/// Lines inside fenced code blocks are left untouched.
+fn demote_headings(body: &str, levels: usize) -> String {
+    let mut in_fence = false;
+    body.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+                return line.to_string();
+            }
+
+            let level = line.bytes().take_while(|b| *b == b'#').count();
+            let is_heading =
+                (1..=6).contains(&level) && line[level..].chars().next().is_none_or(|c| c == ' ');
+            if in_fence || !is_heading {
+                return line.to_string();
+            }
+            format!("{}{}", "#".repeat((level + levels).min(6)), &line[level..])
+        })
+        .join("\n")
+}
+
+/// Renders a component using an entry template.
+///
+/// Supported placeholders are `{path}`, `{display_path}` (the path without
+/// `strip_filename`), `{name}`, `{title}`, `{description}`, `{continuation}`
+/// (the description's further lines, one per line), `{category}`,
+/// `{category_title}` (the category's display title), `{link}`, `{status}`,
+/// `{tags}` (comma-separated), `{extra.<key>}` for front matter keys not
+/// otherwise modeled and `{category.extra.<key>}` for extra keys of the
+/// category's config. Missing extra keys render as empty text; unknown
+/// placeholders are left untouched.
+///
+/// Tags and the keys of mapping values render sorted, so the output doesn't
+/// depend on the order they were written in.
+fn render_entry_template(template: &str, comp: &Component, config: &Config) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let key = &after[..end];
+        match placeholder_value(key, comp, config) {
+            Some(value) => out.push_str(&value),
+            None => write!(out, "{{{}}}", key).unwrap(),
+        }
+        rest = &after[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// The display title of a component's category, which entries note under
+/// `group_by = "owner"`.
+fn category_title<'a>(comp: &'a Component, config: &'a Config) -> &'a str {
+    config.display_title_for(config.canonical_category(&comp.category))
+}
+
+/// Resolves a single entry template placeholder for a component.
+fn placeholder_value(key: &str, comp: &Component, config: &Config) -> Option<String> {
+    match key {
+        "path" => Some(comp.path.display().to_string()),
+        "display_path" => Some(display_path(comp, config)),
+        "name" => Some(comp.name()),
+        "title" => Some(comp.title()),
+        "description" => Some(entry_description(comp, config).primary().to_string()),
+        "continuation" => Some(entry_description(comp, config).continuation().join("\n")),
+        "category" => Some(comp.category.clone()),
+        "category_title" => Some(category_title(comp, config).to_string()),
+        "link" => Some(comp.link.clone().unwrap_or_default()),
+        "status" => Some(comp.status.clone().unwrap_or_default()),
+        "tags" => Some(sorted_tags(comp).join(", ")),
+        _ if key.starts_with("category.extra.") => {
+            Some(category_extra_value(&comp.category, key, config))
+        }
+        _ => {
+            let extra_key = key.strip_prefix("extra.")?;
+            Some(
+                comp.extra
+                    .get(extra_key)
+                    .map(yaml_value_to_string)
+                    .unwrap_or_default(),
+            )
+        }
+    }
+}
+
+/// Resolves a `category.extra.<key>` placeholder for a front matter category,
+/// rendering a missing key as empty text.
+fn category_extra_value(category: &str, placeholder: &str, config: &Config) -> String {
+    let key = placeholder.trim_start_matches("category.extra.");
+    match config.category_extra(category, key) {
+        Some(toml::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Formats a YAML value as plain text for use in rendered output.
+fn yaml_value_to_string(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::Null => String::new(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(&sort_mapping_keys(other.clone()))
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns a YAML value with the keys of every mapping in it sorted.
+///
+/// Mappings keep the order their keys were written in, which would otherwise
+/// leak into rendered output.
+fn sort_mapping_keys(value: serde_yaml::Value) -> serde_yaml::Value {
+    match value {
+        serde_yaml::Value::Mapping(mapping) => serde_yaml::Value::Mapping(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (key, sort_mapping_keys(value)))
+                .sorted_by_cached_key(|(key, _)| serde_yaml::to_string(key).unwrap_or_default())
+                .collect(),
+        ),
+        serde_yaml::Value::Sequence(items) => items.into_iter().map(sort_mapping_keys).collect(),
+        serde_yaml::Value::Tagged(mut tagged) => {
+            tagged.value = sort_mapping_keys(tagged.value);
+            serde_yaml::Value::Tagged(tagged)
+        }
+        other => other,
+    }
+}
+
+/// A component's tags, sorted and without duplicates.
+fn sorted_tags(comp: &Component) -> Vec<&str> {
+    comp.tags
+        .iter()
+        .map(String::as_str)
+        .sorted()
+        .dedup()
+        .collect()
+}
+
+/// Group key for a category matched ignoring case, built level by level so
+/// parents merge too: each level takes its configured spelling, or else the
+/// first spelling recorded in `spellings`, keyed by the lowercased prefix.
+fn case_insensitive_key(
+    category: &str,
+    config: &Config,
+    spellings: &mut BTreeMap<String, String>,
+) -> String {
+    let separator = config.category_separator();
+    let mut key = String::new();
+    for segment in config.category_segments(config.canonical_category(category)) {
+        let prefix = if key.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}{}{}", key, separator, segment)
+        };
+        key = match config.get_category(&prefix) {
+            Some(configured) => config
+                .category_segments(&configured.category)
+                .join(separator),
+            None => spellings
+                .entry(prefix.to_lowercase())
+                .or_insert(prefix)
+                .clone(),
+        };
+    }
+    key
+}
+
+/// Groups components by canonical category, sorting within each group per the configured order.
+///
+/// Deprecated components are grouped under a section titled by the config's
+/// `deprecated_title`, and planned ones under its `planned_title`, instead of
+/// their own category; components the config doesn't show are left out.
+/// With `case_insensitive_categories`, categories differing only in case merge
+/// into one group, spelled as configured or else as the first component by
+/// path spells it, level by level. The groups are keyed in sorted order, so
+/// iterating them never depends on hashing and output is reproducible from run
+/// to run.
+pub(crate) fn group_by_category<'a>(
+    components: &'a [Component],
+    config: &Config,
+) -> BTreeMap<String, Vec<&'a Component>> {
+    // Insert in a fully deterministic order so that components which compare
+    // equal by path still come out the same way regardless of input order
+    let mut ordered: Vec<&Component> = components
+        .iter()
+        .filter(|comp| is_shown(comp, config))
+        .collect();
+    ordered.sort_by(|a, b| {
+        (&a.path, &a.category, &a.description).cmp(&(&b.path, &b.category, &b.description))
+    });
+
+    let mut grouped: BTreeMap<String, Vec<&Component>> = BTreeMap::new();
+    let mut spellings = BTreeMap::new();
+    for comp in ordered {
+        // Nested categories group by their levels, whatever the spacing
+        // around the separator
+        let key = if comp.deprecated {
+            DEPRECATED_SECTION.to_string()
+        } else if comp.planned {
+            PLANNED_SECTION.to_string()
+        } else if config.case_insensitive_categories {
+            case_insensitive_key(&comp.category, config, &mut spellings)
+        } else {
+            config
+                .category_segments(config.canonical_category(&comp.category))
+                .join(config.category_separator())
+        };
+        grouped.entry(key).or_default().push(comp);
+    }
+
+    for (category, comps) in grouped.iter_mut() {
+        comps.sort_by(|a, b| {
+            let exact = path_cmp(&a.path, &b.path, config.natural_sort);
+            if config.case_insensitive_paths {
+                let (a, b) = (lowercase_path(&a.path), lowercase_path(&b.path));
+                path_cmp(&a, &b, config.natural_sort).then(exact)
+            } else {
+                exact
+            }
+        });
+
+        // A cycle keeps path order; check_dependency_order reports it
+        if config.sort_for(category) == SortOrder::Dependencies {
+            if let Some(sorted) = sort_by_dependencies(comps) {
+                *comps = sorted;
+            }
+        }
+    }
+
+    grouped
+}
+
+/// Whether a component is documented at all: deprecated ones are left out
+/// with `show_deprecated = false`, and planned ones unless `show_planned` is set.
+pub(crate) fn is_shown(comp: &Component, config: &Config) -> bool {
+    (!comp.deprecated || config.show_deprecated()) && (!comp.planned || config.show_planned())
+}
+
+/// Title of the release cohort of components without a `since` version.
+const UNVERSIONED_TITLE: &str = "Unversioned";
+
+/// The document's sections when they aren't categories: release cohorts or
+/// owners, per `group_by`, each with its title and components. `None` with
+/// `group_by = "category"`.
+pub(crate) fn flat_sections<'a>(
+    components: &'a [Component],
+    config: &Config,
+) -> Option<Vec<(String, Vec<&'a Component>)>> {
+    match config.group_by {
+        GroupBy::Category => None,
+        GroupBy::Release => Some(group_by_release(components, config)),
+        GroupBy::Owner => Some(group_by_owner(components, config)),
+    }
+}
+
+/// Title of the section of components without an owner.
+const UNOWNED_TITLE: &str = "Unowned";
+
+/// Groups components by owner, listing a component with several owners under
+/// each of them.
+///
+/// Owners in `owner_order` come first, in that order, followed by the other
+/// owners alphabetically and an `Unowned` section of the components without
+/// an owner. Components within a section are sorted by path, whatever their
+/// category.
+pub(crate) fn group_by_owner<'a>(
+    components: &'a [Component],
+    config: &Config,
+) -> Vec<(String, Vec<&'a Component>)> {
+    let mut by_owner: BTreeMap<&str, Vec<&Component>> = BTreeMap::new();
+    let mut unowned = Vec::new();
+    for comp in components.iter().filter(|comp| is_shown(comp, config)) {
+        let owners: BTreeSet<&str> = comp
+            .owners
+            .iter()
+            .map(|owner| owner.trim())
+            .filter(|owner| !owner.is_empty())
+            .collect();
+        if owners.is_empty() {
+            unowned.push(comp);
+        }
+        for owner in owners {
+            by_owner.entry(owner).or_default().push(comp);
+        }
+    }
+
+    let mut sections = Vec::new();
+    for owner in &config.owner_order {
+        if let Some(comps) = by_owner.remove(owner.trim()) {
+            sections.push((owner.trim().to_string(), comps));
+        }
+    }
+    sections.extend(
+        by_owner
+            .into_iter()
+            .map(|(owner, comps)| (owner.to_string(), comps)),
+    );
+    if !unowned.is_empty() {
+        sections.push((UNOWNED_TITLE.to_string(), unowned));
+    }
+    for (_, comps) in &mut sections {
+        comps.sort_by(|a, b| path_cmp(&a.path, &b.path, config.natural_sort));
+    }
+    sections
+}
+
+/// Groups components into release cohorts by their `since` version, each
+/// titled `Added in <version>`.
+///
+/// Cohorts are ordered by version, newest first, followed by an `Unversioned`
+/// cohort of the components without a version. Components within a cohort
+/// are sorted by path, whatever their category.
+pub(crate) fn group_by_release<'a>(
+    components: &'a [Component],
+    config: &Config,
+) -> Vec<(String, Vec<&'a Component>)> {
+    let mut by_version: BTreeMap<Option<&str>, Vec<&Component>> = BTreeMap::new();
+    for comp in components.iter().filter(|comp| is_shown(comp, config)) {
+        let version = comp
+            .since
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty());
+        by_version.entry(version).or_default().push(comp);
+    }
+
+    let mut cohorts: Vec<(Option<&str>, Vec<&Component>)> = by_version.into_iter().collect();
+    cohorts.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => version_cmp(b, a).then(a.cmp(b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    cohorts
+        .into_iter()
+        .map(|(version, mut comps)| {
+            comps.sort_by(|a, b| path_cmp(&a.path, &b.path, config.natural_sort));
+            let title = match version {
+                Some(version) => format!("Added in {}", version),
+                None => UNVERSIONED_TITLE.to_string(),
+            };
+            (title, comps)
+        })
+        .collect()
+}
+
+/// Compares versions like semver: numeric parts by value, an optional leading
+/// `v` and any `+build` metadata ignored, and a pre-release such as
+/// `2.0.0-beta` before its release.
+fn version_cmp(a: &str, b: &str) -> Ordering {
+    let ((a_core, a_pre), (b_core, b_pre)) = (split_version(a), split_version(b));
+
+    natural_cmp(a_core, b_core).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => natural_cmp(a, b),
+    })
+}
+
+/// Splits a version into its core and pre-release parts, e.g. `v2.0.0-beta+7`
+/// into `2.0.0` and `beta`.
+fn split_version(version: &str) -> (&str, Option<&str>) {
+    let version = version.trim_start_matches(['v', 'V']);
+    let version = version.split_once('+').map_or(version, |(v, _)| v);
+    match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    }
+}
+
+/// Compares paths component by component, or in natural order when `natural`.
+fn path_cmp(a: &Path, b: &Path, natural: bool) -> Ordering {
+    if natural {
+        natural_cmp(&a.to_string_lossy(), &b.to_string_lossy())
+    } else {
+        a.cmp(b)
+    }
+}
+
+/// The path with every character lowercased.
+fn lowercase_path(path: &Path) -> PathBuf {
+    PathBuf::from(path.to_string_lossy().to_lowercase())
+}
+
+/// Compares strings in natural order, treating runs of ASCII digits as numbers.
+///
+/// Numeric runs compare by value, then by length so that `01` sorts after `1`;
+/// everything else compares character by character.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, y) = (digit_run(&mut a), digit_run(&mut b));
+                let (x_value, y_value) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                let ordering = x_value
+                    .len()
+                    .cmp(&y_value.len())
+                    .then_with(|| x_value.cmp(y_value))
+                    .then_with(|| x.len().cmp(&y.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+/// Consumes a run of ASCII digits from the iterator.
+fn digit_run(chars: &mut Peekable<Chars>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        run.push(c);
+    }
+    run
+}
+
+/// Orders categories, config-specified order first, then remaining alphabetically.
+///
+/// With `order = "count"`, categories are instead ordered by descending
+/// number of components, with ties broken alphabetically. Either way,
+/// categories with a `pin` are then moved to the top or bottom, keeping
+/// their relative order, and the sections of planned and then deprecated
+/// components, if any, come last.
+pub(crate) fn order_categories<'a>(
+    grouped: &'a BTreeMap<String, Vec<&Component>>,
+    config: &'a Config,
+) -> Vec<&'a str> {
+    let mut result: Vec<&str> = if config.order == CategoryOrder::Count {
+        let mut result: Vec<&str> = grouped.keys().map(String::as_str).collect();
+        result.sort_by(|a, b| grouped[*b].len().cmp(&grouped[*a].len()).then(a.cmp(b)));
+        result
+    } else {
+        let config_order = config.category_order();
+
+        let mut result: Vec<&str> = config_order
+            .iter()
+            .copied()
+            .filter(|name| grouped.contains_key(*name))
+            .collect();
+
+        let mut remaining: Vec<_> = grouped
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !config_order.contains(name))
+            .collect();
+        remaining.sort();
+
+        result.extend(remaining);
+        result
+    };
+
+    result.sort_by_key(|name| match config.get_category(name).and_then(|c| c.pin) {
+        _ if *name == DEPRECATED_SECTION => 4,
+        _ if *name == PLANNED_SECTION => 3,
+        Some(Pin::Top) => 0,
+        None => 1,
+        Some(Pin::Bottom) => 2,
+    });
+
+    // Keep nested categories together: each level of a category is ranked by
+    // the earliest category under it, so a parent's subtree stays contiguous
+    // and comes before anything ordered after its first member
+    let separator = config.category_separator();
+    let mut rank: HashMap<String, usize> = HashMap::new();
+    for (i, name) in result.iter().enumerate() {
+        let segments = config.category_segments(name);
+        for depth in 1..=segments.len() {
+            rank.entry(segments[..depth].join(separator)).or_insert(i);
+        }
+    }
+    result.sort_by_cached_key(|name| {
+        let segments = config.category_segments(name);
+        (1..=segments.len())
+            .map(|depth| rank[&segments[..depth].join(separator)])
+            .collect::<Vec<_>>()
+    });
+    result
+}
+
+/// Expands ordered categories into the headings to emit, each with its
+/// nesting depth (1 for a top-level category).
+///
+/// A nested category is preceded by a heading for each of its parents not
+/// already emitted, so `Backend/Storage` alone yields `Backend` at depth 1
+/// and then itself at depth 2. Parents are named by the category's leading
+/// levels joined with the separator.
+pub(crate) fn category_sections(ordered: &[&str], config: &Config) -> Vec<(usize, String)> {
+    let separator = config.category_separator();
+    let mut sections = Vec::new();
+    let mut open: Vec<String> = Vec::new();
+    for name in ordered {
+        let segments = config.category_segments(name);
+        let depth = segments.len();
+        for parent_depth in 1..depth {
+            let parent = segments[..parent_depth].join(separator);
+            if open.get(parent_depth - 1) != Some(&parent) {
+                open.truncate(parent_depth - 1);
+                open.push(parent.clone());
+                sections.push((parent_depth, parent));
+            }
+        }
+        open.truncate(depth - 1);
+        open.push(segments.join(separator));
+        sections.push((depth, name.to_string()));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_TITLE;
+    use std::path::PathBuf;
+
+    fn config_from_str(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_generate_document_empty() {
+        let components = vec![];
+        let doc = generate_document(&components, &Config::default());
+        assert_eq!(doc.trim(), format!("# {}", DEFAULT_TITLE));
+    }
+
+    #[test]
+    fn test_generate_document_single_category() {
+        let components = vec![Component {
+            path: PathBuf::from("crates/core/README.md"),
+            description: "Core utilities".into(),
+            category: "Utilities".to_string(),
+            ..Default::default()
+        }];
+
+        let doc = generate_document(&components, &Config::default());
+        assert!(doc.contains(&format!("# {}", DEFAULT_TITLE)));
+        assert!(doc.contains("## Utilities"));
+        assert!(doc.contains("crates/core"));
+        assert!(doc.contains("Core utilities"));
+    }
+
+    #[test]
+    fn test_generate_document_multiple_categories() {
+        let components = vec![
+            Component {
+                path: PathBuf::from("crates/core/README.md"),
+                description: "Core utilities".into(),
+                category: "Utilities".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("crates/cli/README.md"),
+                description: "CLI interface".into(),
+                category: "Interfaces".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("crates/helpers/README.md"),
+                description: "Helper functions".into(),
+                category: "Utilities".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &Config::default());
+        assert!(doc.contains("## Utilities"));
+        assert!(doc.contains("## Interfaces"));
+        assert!(doc.contains("crates/core"));
+        assert!(doc.contains("crates/cli"));
+        assert!(doc.contains("crates/helpers"));
+    }
+
+    #[test]
+    fn test_generate_document_sorted_categories() {
+        let components = vec![
+            Component {
+                path: PathBuf::from("crates/cli/README.md"),
+                description: "CLI interface".into(),
+                category: "Interfaces".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("crates/core/README.md"),
+                description: "Core utilities".into(),
+                category: "Utilities".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &Config::default());
+        let interfaces_pos = doc.find("## Interfaces").unwrap();
+        let utilities_pos = doc.find("## Utilities").unwrap();
+        // Categories should be sorted alphabetically
+        assert!(interfaces_pos < utilities_pos);
+    }
+
+    #[test]
+    fn test_generate_document_multiple_components_same_category() {
+        let components = vec![
+            Component {
+                path: PathBuf::from("a/README.md"),
+                description: "First".into(),
+                category: "Test".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("b/README.md"),
+                description: "Second".into(),
+                category: "Test".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &Config::default());
+        assert!(doc.contains("First"));
+        assert!(doc.contains("Second"));
+        let category_count = doc.matches("## Test").count();
+        assert_eq!(
+            category_count, 1,
+            "Should only have one Test category header"
+        );
+    }
+
+    #[test]
+    fn test_generate_document_with_custom_title() {
+        let config = config_from_str(r#"title = "Custom Title""#);
+        let components = vec![];
+        let doc = generate_document(&components, &config);
+        assert!(doc.starts_with("# Custom Title"));
+    }
+
+    #[test]
+    fn test_generate_document_with_description() {
+        let config = config_from_str(
+            r#"
+title = "Arch Doc"
+description = "This is the description."
+"#,
+        );
+        let components = vec![];
+        let doc = generate_document(&components, &config);
+        assert!(doc.contains("This is the description."));
+    }
+
+    #[test]
+    fn test_generate_document_category_ordering() {
+        let config = config_from_str(
+            r#"
+[[categories]]
+category = "Utilities"
+
+[[categories]]
+category = "Interfaces"
+"#,
+        );
+
+        let components = vec![
+            Component {
+                path: PathBuf::from("cli/README.md"),
+                description: "CLI".into(),
+                category: "Interfaces".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("core/README.md"),
+                description: "Core".into(),
+                category: "Utilities".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &config);
+        let utilities_pos = doc.find("## Utilities").unwrap();
+        let interfaces_pos = doc.find("## Interfaces").unwrap();
+        // Config order: Utilities before Interfaces
+        assert!(utilities_pos < interfaces_pos);
+    }
+
+    #[test]
+    fn test_generate_document_unlisted_categories_appended() {
+        let config = config_from_str(
+            r#"
+[[categories]]
+category = "First"
+"#,
+        );
+
+        let components = vec![
+            Component {
+                path: PathBuf::from("a/README.md"),
+                description: "A".into(),
+                category: "First".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("b/README.md"),
+                description: "B".into(),
+                category: "ZUnlisted".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("c/README.md"),
+                description: "C".into(),
+                category: "AUnlisted".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &config);
+        let first_pos = doc.find("## First").unwrap();
+        let a_unlisted_pos = doc.find("## AUnlisted").unwrap();
+        let z_unlisted_pos = doc.find("## ZUnlisted").unwrap();
+
+        // First from config, then unlisted alphabetically
+        assert!(first_pos < a_unlisted_pos);
+        assert!(a_unlisted_pos < z_unlisted_pos);
+    }
+
+    fn components_in(categories: &[&str]) -> Vec<Component> {
+        categories
+            .iter()
+            .enumerate()
+            .map(|(i, category)| Component {
+                path: PathBuf::from(format!("{}/README.md", i)),
+                description: i.to_string().into(),
+                category: category.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn heading_order(doc: &str) -> Vec<&str> {
+        doc.lines()
+            .filter_map(|line| line.strip_prefix("## "))
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_document_padded_and_cased_categories() {
+        let toml = r#"
+[[categories]]
+category = "Utilities"
+
+[[categories]]
+category = "core"
+title = "Core Systems"
+"#;
+        let components = components_in(&[" core ", "Core", "Utilities", "Zed "]);
+
+        let doc = generate_document(&components, &config_from_str(toml));
+        assert_eq!(
+            heading_order(&doc),
+            vec!["Utilities", "Core Systems", "Core", "Zed"]
+        );
+
+        let config = config_from_str(&format!("case_insensitive_categories = true\n{}", toml));
+        let doc = generate_document(&components, &config);
+        assert_eq!(
+            heading_order(&doc),
+            vec!["Utilities", "Core Systems", "Zed"]
+        );
+        assert!(doc.contains("## Core Systems\n\n- `0/README.md`: 0\n- `1/README.md`: 1\n"));
+    }
+
+    #[test]
+    fn test_generate_document_case_insensitive_heading_casing() {
+        let components = components_in(&["cli", "Cli", "CLI ", "Backend/api", "backend/Api"]);
+
+        // Without a configured spelling, the first component by path sets it
+        let config =
+            config_from_str("case_insensitive_categories = true\ncategory_separator = \"/\"");
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Backend", "cli"]);
+        assert!(doc.contains("### api\n\n- `3/README.md`: 3\n- `4/README.md`: 4\n"));
+        assert!(
+            doc.contains("## cli\n\n- `0/README.md`: 0\n- `1/README.md`: 1\n- `2/README.md`: 2\n")
+        );
+
+        // A configured spelling wins, at every level
+        let config = config_from_str(
+            "case_insensitive_categories = true\ncategory_separator = \"/\"\n\n\
+             [[categories]]\ncategory = \"CLI\"\n\n\
+             [[categories]]\ncategory = \"BACKEND\"",
+        );
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["CLI", "BACKEND"]);
+        assert!(doc.contains("## BACKEND\n\n<a name=\"backend-api\"></a>\n### api\n\n"));
+    }
+
+    #[test]
+    fn test_generate_document_heading_offset() {
+        let components = components_in(&["Core"]);
+
+        let doc = generate_document(&components, &config_from_str("heading_offset = 1"));
+        assert!(doc.starts_with(&format!("## {}\n", DEFAULT_TITLE)));
+        assert!(doc.contains("\n### Core\n"));
+
+        let doc = generate_document(&components, &config_from_str("heading_offset = 2"));
+        assert!(doc.starts_with(&format!("### {}\n", DEFAULT_TITLE)));
+        assert!(doc.contains("\n#### Core\n"));
+    }
+
+    #[test]
+    fn test_generate_document_heading_offset_clamped() {
+        let components = components_in(&["Core"]);
+
+        let doc = generate_document(&components, &config_from_str("heading_offset = 5"));
+        assert!(doc.starts_with(&format!("###### {}\n", DEFAULT_TITLE)));
+        assert!(doc.contains("\n###### Core\n"));
+        assert!(!doc.contains("#######"));
+    }
+
+    #[test]
+    fn test_generate_document_overview_plural() {
+        let config = config_from_str("overview = true\ndescription = \"Intro.\"");
+        let doc = generate_document(&components_in(&["Core", "Core", "CLI"]), &config);
+        assert!(doc.contains(
+            "\nIntro.\n\nThis document describes 3 components across 2 categories.\n\n## "
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_toc() {
+        let config = config_from_str(
+            r#"
+toc = true
+
+[[categories]]
+category = "Core"
+title = "Core Utilities"
+"#,
+        );
+        let doc = generate_document(&components_in(&["CLI", "Core"]), &config);
+        assert!(doc.contains(
+            "\n- [Core Utilities](#core-utilities)\n- [CLI](#cli)\n\n## Core Utilities\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_deprecated_section() {
+        let config = config_from_str(
+            r#"
+deprecated_title = "Retired"
+
+[[categories]]
+category = "Tools"
+pin = "bottom"
+"#,
+        );
+        let mut components = components_in(&["Core", "Core", "Tools", "Core"]);
+        components[1].deprecated = true;
+        components[2].deprecated = true;
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Core", "Retired"]);
+        assert!(doc.ends_with("## Retired\n\n- ~~`1/README.md`~~: 1\n- ~~`2/README.md`~~: 2\n"));
+        assert!(doc.contains("## Core\n\n- `0/README.md`: 0\n- `3/README.md`: 3\n"));
+    }
+
+    #[test]
+    fn test_generate_document_deprecated_section_apart_from_same_named_category() {
+        let mut components = components_in(&["Deprecated", "Core"]);
+        components[1].deprecated = true;
+
+        let doc = generate_document(&components, &Config::default());
+        assert_eq!(heading_order(&doc), vec!["Deprecated", "Deprecated"]);
+        assert!(doc.contains(
+            "## Deprecated\n\n- `0/README.md`: 0\n\n## Deprecated\n\n- ~~`1/README.md`~~: 1\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_hides_deprecated() {
+        let config = config_from_str("show_deprecated = false\nindex = true\noverview = true");
+        let mut components = components_in(&["Core", "Core"]);
+        components[1].deprecated = true;
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Core", "Index"]);
+        assert!(doc.contains("describes 1 component across 1 category"));
+        assert!(!doc.contains("1/README.md"));
+    }
+
+    #[test]
+    fn test_generate_document_planned_section() {
+        let config = config_from_str("show_planned = true");
+        let mut components = components_in(&["Core", "Tools", "Core", "Core"]);
+        components[0].deprecated = true;
+        components[1].planned = true;
+        components[3].planned = true;
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Core", "Planned", "Deprecated"]);
+        assert!(doc.contains(
+            "## Planned\n\n- `1/README.md`: 1 _(planned)_\n- `3/README.md`: 3 _(planned)_\n\n\
+             ## Deprecated\n"
+        ));
+        assert!(doc.contains("## Core\n\n- `2/README.md`: 2\n\n"));
+    }
+
+    #[test]
+    fn test_render_entry_truncates_fallback_description() {
+        let config = config_from_str("description_sentences = 1");
+        let mut comp = Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Parses input. Validates it.".into(),
+            description_source: DescriptionSource::FirstParagraph,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- `core/README.md`: Parses input. …\n"
+        );
+
+        comp.description_source = DescriptionSource::FrontMatter;
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- `core/README.md`: Parses input. Validates it.\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_document_hides_planned_by_default() {
+        let mut components = components_in(&["Core", "Planned"]);
+        components[0].planned = true;
+
+        let doc = generate_document(&components, &Config::default());
+        assert_eq!(heading_order(&doc), vec!["Planned"]);
+        assert!(!doc.contains("0/README.md"));
+    }
+
+    #[test]
+    fn test_generate_document_release_cohorts() {
+        let config = config_from_str("group_by = \"release\"\ntoc = true");
+        let mut components = components_in(&["Core", "CLI", "Core", "CLI", "Core", "Core"]);
+        for (comp, since) in components.iter_mut().zip([
+            Some("1.3.0"),
+            Some("1.10.0"),
+            None,
+            Some("1.3.0"),
+            Some(" "),
+            Some("1.10.0-rc.1"),
+        ]) {
+            comp.since = since.map(str::to_string);
+        }
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(
+            heading_order(&doc),
+            vec![
+                "Added in 1.10.0",
+                "Added in 1.10.0-rc.1",
+                "Added in 1.3.0",
+                "Unversioned"
+            ]
+        );
+        assert!(doc.contains("\n- [Added in 1.10.0](#added-in-1-10-0)\n"));
+        assert!(doc.contains(
+            "## Added in 1.3.0\n\n- `0/README.md`: 0\n- `3/README.md`: 3\n\n## Unversioned\n\n\
+             - `2/README.md`: 2\n- `4/README.md`: 4\n"
+        ));
+    }
+
+    fn owned(owners: &[&[&str]]) -> Vec<Component> {
+        let mut components = components_in(&["Core", "CLI", "Core", "Core"]);
+        for (comp, owners) in components.iter_mut().zip(owners) {
+            comp.owners = owners.iter().map(|o| o.to_string()).collect();
+        }
+        components
+    }
+
+    #[test]
+    fn test_generate_document_owner_sections() {
+        let config = config_from_str(
+            "group_by = \"owner\"\n\n[[categories]]\ncategory = \"Core\"\ntitle = \"Core Systems\"",
+        );
+        let components = owned(&[&["platform"], &["web", "platform"], &[], &["web"]]);
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["platform", "web", "Unowned"]);
+        assert!(doc.contains(
+            "## platform\n\n- `0/README.md`: 0 _(Core Systems)_\n- `1/README.md`: 1 _(CLI)_\n\n\
+             ## web\n\n- `1/README.md`: 1 _(CLI)_\n- `3/README.md`: 3 _(Core Systems)_\n\n\
+             ## Unowned\n\n- `2/README.md`: 2 _(Core Systems)_\n"
+        ));
+
+        // Templates place the category title themselves
+        let config = config_from_str(
+            "group_by = \"owner\"\nentry_template = \"- {path} [{category_title}]\"\n\n\
+             [[categories]]\ncategory = \"Core\"\ntitle = \"Core Systems\"",
+        );
+        let doc = generate_document(&components, &config);
+        assert!(
+            doc.contains("## platform\n\n- 0/README.md [Core Systems]\n- 1/README.md [CLI]\n\n")
+        );
+    }
+
+    #[test]
+    fn test_generate_document_owner_order() {
+        let config = config_from_str("group_by = \"owner\"\nowner_order = [\"web\", \"infra\"]");
+        let components = owned(&[&["platform"], &[" web "], &["data"], &[]]);
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(
+            heading_order(&doc),
+            vec!["web", "data", "platform", "Unowned"]
+        );
+    }
+
+    #[test]
+    fn test_version_cmp() {
+        assert_eq!(version_cmp("1.10.0", "1.9.2"), Ordering::Greater);
+        assert_eq!(version_cmp("v2.0.0", "2.0.0+build.5"), Ordering::Equal);
+        assert_eq!(version_cmp("2.0.0-beta", "2.0.0"), Ordering::Less);
+        assert_eq!(version_cmp("2.0.0-beta.2", "2.0.0-beta.10"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_generate_document_final_newline() {
+        let components = components_in(&["Core"]);
+        let doc = generate_document(&components, &Config::default());
+        assert!(doc.ends_with("- `0/README.md`: 0\n"), "{:?}", doc);
+
+        // Trailing blank lines from a skeleton template are dropped
+        let mut config = Config {
+            template: Some(
+                "# {{title}}\n\n{{#categories}}{{entries}}{{/categories}}\n\n\n".to_string(),
+            ),
+            ..Default::default()
+        };
+        let doc = generate_document(&components, &config);
+        assert!(doc.ends_with("- `0/README.md`: 0\n"), "{:?}", doc);
+
+        config.final_newline = Some(false);
+        let doc = generate_document(&components, &config);
+        assert!(doc.ends_with("- `0/README.md`: 0"), "{:?}", doc);
+        let doc = generate_document(&[], &config);
+        assert_eq!(doc, format!("# {}", DEFAULT_TITLE));
+    }
+
+    fn with_status_and_tags(mut components: Vec<Component>) -> Vec<Component> {
+        components[0].status = Some("beta".to_string());
+        components[0].tags = vec!["async".to_string(), "internal".to_string()];
+        components[1].tags = vec!["async".to_string()];
+        components
+    }
+
+    #[test]
+    fn test_generate_document_legend() {
+        let config = config_from_str(
+            r#"
+show_tags = true
+
+[statuses]
+beta = "Not yet stable"
+stable = "Unused, so not listed"
+
+[tag_descriptions]
+async = "Runs on the async runtime"
+"#,
+        );
+        let components = with_status_and_tags(components_in(&["Core", "Core", "Core"]));
+        let doc = generate_document(&components, &config);
+
+        assert!(doc.contains("- `0/README.md`: 0 **[beta]** `#async` `#internal`\n"));
+        assert!(doc.contains("- `1/README.md`: 1 `#async`\n"));
+        assert!(doc.ends_with(
+            "- `2/README.md`: 2\n\n## Legend\n\n\
+             - **[beta]**: Not yet stable\n\
+             - `#async`: Runs on the async runtime\n\
+             - `#internal`\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_legend_top() {
+        let config = config_from_str("legend = \"top\"");
+        let components = with_status_and_tags(components_in(&["Core", "Core"]));
+        let doc = generate_document(&components, &config);
+
+        // Tags aren't shown without show_tags, so only the status is listed
+        assert!(
+            doc.contains("# Architecture Documentation\n\n## Legend\n\n- **[beta]**\n\n## Core\n")
+        );
+        assert!(!doc.contains("#async"));
+    }
+
+    #[test]
+    fn test_generate_document_legend_omitted_when_unused() {
+        let config = config_from_str("show_tags = true\n[statuses]\nbeta = \"Not yet stable\"");
+        let doc = generate_document(&components_in(&["Core", "CLI"]), &config);
+        assert!(!doc.contains("Legend"));
+
+        // Tags alone don't need a legend unless they're shown
+        let mut components = components_in(&["Core"]);
+        components[0].tags = vec!["async".to_string()];
+        assert!(!generate_document(&components, &Config::default()).contains("Legend"));
+
+        // Nor do statuses an entry template leaves out
+        let components = with_status_and_tags(components_in(&["Core", "Core"]));
+        let config = config_from_str("entry_template = \"- {path}: {description}\"");
+        assert!(!generate_document(&components, &config).contains("Legend"));
+        let config = config_from_str(
+            "entry_template = \"- {path}: {description}\"\n\n\
+             [[categories]]\ncategory = \"Core\"\nentry_template = \"- {path} ({status})\"",
+        );
+        assert!(generate_document(&components, &config).contains("## Legend\n\n- **[beta]**\n"));
+    }
+
+    fn titled(titles: &[&str]) -> Vec<Component> {
+        titles
+            .iter()
+            .map(|title| Component {
+                path: PathBuf::from(format!("{}/README.md", slugify(title))),
+                description: "Desc".into(),
+                category: "Core Utilities".to_string(),
+                display_title: Some(title.to_string()),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_document_index_list() {
+        let doc = generate_document(
+            &titled(&["zlib", "Cache", "apache"]),
+            &config_from_str("index = true"),
+        );
+        assert!(doc.ends_with(
+            "\n## Index\n\n\
+             - [apache](#component-apache)\n\
+             - [Cache](#component-cache)\n\
+             - [zlib](#component-zlib)\n"
+        ));
+        // Each entry carries the anchor its index entry links to
+        assert!(doc.contains("- <a name=\"component-cache\"></a>`cache/README.md`: Desc\n"));
+        assert!(!generate_document(&titled(&["zlib"]), &Config::default()).contains("Index"));
+    }
+
+    #[test]
+    fn test_generate_document_index_alphabetical() {
+        let config = config_from_str("index = true\nindex_style = \"alphabetical\"");
+        let doc = generate_document(&titled(&["beta", "Alpha", "3d", "apex", "Delta"]), &config);
+        let index = &doc[doc.find("## Index").unwrap()..];
+
+        // Letters no title starts with are skipped, and non-letters come last
+        assert!(index.starts_with(
+            "## Index\n\n[A](#index-a) · [B](#index-b) · [D](#index-d) · [Other](#index-other)\n"
+        ));
+        assert!(index.contains(
+            "\n<a name=\"index-a\"></a>\n### A\n\n\
+             - [Alpha](#component-alpha)\n- [apex](#component-apex)\n\
+             \n<a name=\"index-b\"></a>\n### B\n\n- [beta](#component-beta)\n\
+             \n<a name=\"index-d\"></a>\n### D\n\n- [Delta](#component-delta)\n\
+             \n<a name=\"index-other\"></a>\n### Other\n\n- [3d](#component-3d)\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_index_letter_anchors() {
+        let config = config_from_str(
+            "index = true\nindex_style = \"alphabetical\"\nexplicit_anchors = true",
+        );
+        let doc = generate_document(&titled(&["Alpha"]), &config);
+        assert!(doc.contains("\n[A](#index-a)\n\n<a name=\"index-a\"></a>\n### A\n"));
+
+        // A category named like the `Other` group keeps its own anchor
+        let mut components = titled(&["3d"]);
+        components[0].category = "Other".to_string();
+        let doc = generate_document(&components, &config);
+        assert!(doc.contains("<a name=\"other\"></a>\n## Other\n"));
+        assert!(doc.contains("<a name=\"index-other\"></a>\n### Other\n"));
+        assert_eq!(doc.matches("<a name=\"other\"></a>").count(), 1);
+    }
+
+    fn nested_document(separator: &str) -> String {
+        let components = components_in(&[
+            &format!("Backend{}Storage", separator),
+            "Frontend",
+            &format!("Backend {} Api", separator),
+            "Backend",
+        ]);
+        let config = config_from_str(&format!(
+            "toc = true\ncategory_separator = \"{}\"\n\n\
+             [[categories]]\ncategory = \"Frontend\"\n\n\
+             [[categories]]\ncategory = \"Backend{}Storage\"\ntitle = \"Storage Layer\"",
+            separator, separator
+        ));
+        generate_document(&components, &config)
+    }
+
+    #[test]
+    fn test_generate_document_nested_categories() {
+        let doc = nested_document("/");
+        // Subcategories nest under their parent, which keeps its subtree
+        // together at the position of its first configured member
+        assert!(doc.contains(
+            "\n- [Frontend](#frontend)\n- [Backend](#backend)\n  \
+             - [Storage Layer](#backend-storage-layer)\n  - [Api](#backend-api)\n"
+        ));
+        // Subcategory headings carry their anchors, which aren't their titles' slugs
+        assert!(doc.contains("\n<a name=\"backend-api\"></a>\n### Api\n"));
+        let headings: Vec<&str> = doc.lines().filter(|l| l.starts_with("##")).collect();
+        assert_eq!(
+            headings,
+            vec!["## Frontend", "## Backend", "### Storage Layer", "### Api"]
+        );
+    }
+
+    #[test]
+    fn test_generate_document_nested_parent_without_components() {
+        let config = config_from_str("category_separator = \"/\"");
+        let doc = generate_document(&components_in(&["Backend/Api"]), &config);
+        assert!(doc.contains(
+            "\n## Backend\n\n<a name=\"backend-api\"></a>\n### Api\n\n- `0/README.md`: 0\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_same_leaf_under_different_parents() {
+        let config = config_from_str("toc = true\ncategory_separator = \"/\"");
+        let doc = generate_document(
+            &components_in(&["Backend/Storage", "Frontend/Storage"]),
+            &config,
+        );
+        assert!(doc.contains("  - [Storage](#backend-storage)\n"));
+        assert!(doc.contains("  - [Storage](#frontend-storage)\n"));
+    }
+
+    #[test]
+    fn test_generate_document_categories_nest_only_with_separator() {
+        let doc = generate_document(&components_in(&["CI/CD"]), &Config::default());
+        assert_eq!(heading_order(&doc), vec!["CI/CD"]);
+    }
+
+    #[test]
+    fn test_generate_document_breadcrumbs() {
+        let config = config_from_str(
+            "breadcrumbs = true\nindex = true\ncategory_separator = \"/\"\n\n\
+             [[categories]]\ncategory = \"Backend/Storage\"\ntitle = \"Storage Layer\"",
+        );
+        let doc = generate_document(&components_in(&["Backend/Storage", "Core"]), &config);
+
+        assert!(doc.contains(
+            "\n## Backend\n\n<a name=\"backend-storage-layer\"></a>\n### Storage Layer\n\n\
+             [Backend](#backend) \u{203a} Storage Layer\n\n\
+             - <a name=\"component-0\"></a>`0/README.md`: 0\n"
+        ));
+        assert!(doc.contains("\n- [0](#component-0) _(Backend \u{203a} Storage Layer)_\n"));
+
+        // Flat categories, and the parents of nested ones, get none
+        assert!(doc.contains("\n## Core\n\n- <a name=\"component-1\"></a>`1/README.md`: 1\n"));
+        assert!(doc.contains("\n- [1](#component-1)\n"));
+        assert_eq!(doc.matches('\u{203a}').count(), 2);
+
+        let config = config_from_str("index = true\ncategory_separator = \"/\"");
+        let doc = generate_document(&components_in(&["Backend/Storage"]), &config);
+        assert!(!doc.contains('\u{203a}'));
+    }
+
+    #[test]
+    fn test_generate_document_category_separators_nest_alike() {
+        let slashed = nested_document("/");
+        assert_eq!(nested_document("."), slashed);
+        assert_eq!(nested_document("::"), slashed);
+    }
+
+    #[test]
+    fn test_generate_document_explicit_html_anchors() {
+        let config = config_from_str("toc = true\nexplicit_anchors = true");
+        let doc = generate_document(&components_in(&["Core Utilities"]), &config);
+        assert!(doc.contains(
+            "\n- [Core Utilities](#core-utilities)\n\n\
+             <a name=\"core-utilities\"></a>\n## Core Utilities\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_explicit_attribute_anchors() {
+        let config = config_from_str(
+            "toc = true\nexplicit_anchors = true\nanchor_style = \"attribute\"\nheading_offset = 1",
+        );
+        let doc = generate_document(&components_in(&["Core Utilities"]), &config);
+        assert!(doc.contains(
+            "\n- [Core Utilities](#core-utilities)\n\n### Core Utilities {#core-utilities}\n"
+        ));
+        assert!(!doc.contains("<a name"));
+    }
+
+    #[test]
+    fn test_generate_document_collapsible_open() {
+        let config = config_from_str(
+            r#"
+collapsible = true
+toc = true
+
+[[categories]]
+category = "Core"
+title = "Core & Utilities"
+"#,
+        );
+        let doc = generate_document(&components_in(&["Core"]), &config);
+        assert!(doc.contains(
+            "\n- [Core & Utilities](#core-utilities)\n\n\
+             <details open>\n<summary>Core &amp; Utilities</summary>\n\n\
+             ## Core & Utilities\n\n- `0/README.md`: 0\n\n</details>\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_document_collapsed_by_default() {
+        let config = config_from_str("collapsible = true\ncollapsed_by_default = true");
+        let doc = generate_document(&components_in(&["Core"]), &config);
+        assert!(doc.contains("\n<details>\n<summary>Core</summary>\n\n## Core\n"));
+        assert!(!doc.contains("<details open>"));
+    }
+
+    #[test]
+    fn test_generate_document_collapsible_per_category() {
+        let config = config_from_str(
+            r#"
+[[categories]]
+category = "Core"
+collapsible = true
+
+[[categories]]
+category = "CLI"
+"#,
+        );
+        let doc = generate_document(&components_in(&["Core", "CLI"]), &config);
+        assert!(doc.contains("<summary>Core</summary>"));
+        assert!(!doc.contains("<summary>CLI</summary>"));
+        assert_eq!(doc.matches("</details>").count(), 1);
+    }
+
+    #[test]
+    fn test_generate_document_overview_singular() {
+        let config = config_from_str("overview = true");
+        let doc = generate_document(&components_in(&["Core"]), &config);
+        assert!(doc.contains("\nThis document describes 1 component across 1 category.\n"));
+    }
+
+    #[test]
+    fn test_generate_document_overview_suppressed() {
+        let doc = generate_document(&[], &config_from_str("overview = true"));
+        assert!(!doc.contains("This document describes"));
+
+        let doc = generate_document(&components_in(&["Core"]), &Config::default());
+        assert!(!doc.contains("This document describes"));
+    }
+
+    #[test]
+    fn test_generate_document_skeleton_template() {
+        let mut config = config_from_str(
+            r#"
+title = "Platform"
+description = "Everything we run."
+
+[[categories]]
+category = "Core"
+description = "The foundation."
+"#,
+        );
+        config.template = Some(
+            "<!-- generated -->\n# {{title}}\n\n> {{description}}\n\
+             {{#categories}}\n### {{title}} ({{count}})\n\n{{description}}\n\n{{entries}}{{/categories}}\
+             \n{{unknown}}\n"
+                .to_string(),
+        );
+
+        let doc = generate_document(&components_in(&["CLI", "Core", "Core"]), &config);
+        assert_eq!(
+            doc,
+            "<!-- generated -->\n# Platform\n\n> Everything we run.\n\
+             \n### Core (2)\n\nThe foundation.\n\n- `1/README.md`: 1\n- `2/README.md`: 2\n\
+             \n### CLI (1)\n\n\n\n- `0/README.md`: 0\n\
+             \n{{unknown}}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_document_category_extra_in_templates() {
+        let mut config = config_from_str(
+            r##"
+entry_template = "- {name} ({category.extra.team})"
+
+[[categories]]
+category = "Core"
+team = "platform"
+channel = "#core"
+tier = 1
+"##,
+        );
+        assert_eq!(
+            config.categories[0].extra.get("team"),
+            Some(&toml::Value::from("platform"))
+        );
+
+        config.template = Some(
+            "{{#categories}}## {{title}} ({{category.extra.channel}}, tier {{category.extra.tier}})\n\
+             {{entries}}{{/categories}}"
+                .to_string(),
+        );
+        let doc = generate_document(&components_in(&["Core", "CLI"]), &config);
+        assert_eq!(
+            doc,
+            "## Core (#core, tier 1)\n- 0 (platform)\n## CLI (, tier )\n- 1 ()\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_document_skeleton_template_without_categories() {
+        let config = Config {
+            template: Some("Title: {{ title }}\n".to_string()),
+            ..Default::default()
+        };
+
+        let doc = generate_document(&components_in(&["Core"]), &config);
+        assert_eq!(doc, format!("Title: {}\n", DEFAULT_TITLE));
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("module2", "module10"), Ordering::Less);
+        assert_eq!("module2".cmp("module10"), Ordering::Greater);
+        assert_eq!(natural_cmp("v1.10.2", "v1.9.10"), Ordering::Greater);
+        assert_eq!(natural_cmp("a2b10", "a2b9"), Ordering::Greater);
+        assert_eq!(natural_cmp("file01", "file1"), Ordering::Greater);
+        assert_eq!(natural_cmp("abc", "abc"), Ordering::Equal);
+        assert_eq!(natural_cmp("abc", "abcd"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_generate_document_natural_sort() {
+        let components: Vec<Component> = ["module10", "module2", "module1"]
+            .iter()
+            .map(|name| Component {
+                path: PathBuf::from(format!("crates/{}/README.md", name)),
+                description: name.to_string().into(),
+                category: "Modules".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let entries = |doc: &str| -> Vec<String> {
+            doc.lines()
+                .filter_map(|line| line.strip_prefix("- "))
+                .map(|entry| entry.split(':').nth(1).unwrap().trim().to_string())
+                .collect()
+        };
+
+        let doc = generate_document(&components, &Config::default());
+        assert_eq!(entries(&doc), vec!["module1", "module10", "module2"]);
+
+        let doc = generate_document(&components, &config_from_str("natural_sort = true"));
+        assert_eq!(entries(&doc), vec!["module1", "module2", "module10"]);
+    }
+
+    #[test]
+    fn test_generate_document_case_insensitive_paths() {
+        let components: Vec<Component> = ["Zlib", "apache", "Apache", "bzip2"]
+            .iter()
+            .map(|name| Component {
+                path: PathBuf::from(format!("{}/README.md", name)),
+                description: name.to_string().into(),
+                category: "Vendored".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let entries = |doc: &str| -> Vec<String> {
+            doc.lines()
+                .filter_map(|line| line.strip_prefix("- "))
+                .map(|entry| entry.split(':').nth(1).unwrap().trim().to_string())
+                .collect()
+        };
+
+        let doc = generate_document(&components, &Config::default());
+        assert_eq!(entries(&doc), vec!["Apache", "Zlib", "apache", "bzip2"]);
+
+        // Ties between paths differing only in case fall back to the exact path
+        let doc = generate_document(
+            &components,
+            &config_from_str("case_insensitive_paths = true"),
+        );
+        assert_eq!(entries(&doc), vec!["Apache", "apache", "bzip2", "Zlib"]);
+    }
+
+    #[test]
+    fn test_generate_document_order_by_count() {
+        let config = config_from_str(
+            r#"
+order = "count"
+
+[[categories]]
+category = "Small"
+
+[[categories]]
+category = "Empty"
+"#,
+        );
+        let components = components_in(&["Small", "Big", "Big", "Big", "Medium", "Medium"]);
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Big", "Medium", "Small"]);
+    }
+
+    #[test]
+    fn test_generate_document_order_by_count_tie_alphabetical() {
+        let config = config_from_str(r#"order = "count""#);
+        let components = components_in(&["Beta", "Alpha", "Gamma", "Gamma"]);
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Gamma", "Alpha", "Beta"]);
+    }
+
+    #[test]
+    fn test_generate_document_pin_bottom_after_alphabetical_tail() {
+        let config = config_from_str(
+            r#"
+[[categories]]
+category = "Deprecated"
+pin = "bottom"
+
+[[categories]]
+category = "Core"
+
+[[categories]]
+category = "Legacy"
+pin = "bottom"
+"#,
+        );
+        let components = components_in(&["Deprecated", "Zeta", "Core", "Alpha", "Legacy"]);
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(
+            heading_order(&doc),
+            vec!["Core", "Alpha", "Zeta", "Deprecated", "Legacy"]
+        );
+    }
+
+    #[test]
+    fn test_generate_document_pin_with_count_order() {
+        let config = config_from_str(
+            r#"
+order = "count"
+
+[[categories]]
+category = "Big"
+pin = "bottom"
+
+[[categories]]
+category = "Small"
+pin = "top"
+"#,
+        );
+        let components = components_in(&["Big", "Big", "Big", "Medium", "Medium", "Small"]);
+
+        let doc = generate_document(&components, &config);
+        assert_eq!(heading_order(&doc), vec!["Small", "Medium", "Big"]);
+    }
+
+    #[test]
+    fn test_generate_document_category_display_title() {
+        let config = config_from_str(
+            r#"
+[[categories]]
+category = "utils"
+title = "Utility Functions"
+"#,
+        );
+
+        let components = vec![Component {
+            path: PathBuf::from("utils/README.md"),
+            description: "Utils".into(),
+            category: "utils".to_string(),
+            ..Default::default()
+        }];
+
+        let doc = generate_document(&components, &config);
+        assert!(doc.contains("## Utility Functions"));
+        assert!(!doc.contains("## utils"));
+    }
+
+    #[test]
+    fn test_generate_document_sort_by_dependencies() {
+        let config = config_from_str(r#"sort = "dependencies""#);
+
+        let components = vec![
+            Component {
+                path: PathBuf::from("a/README.md"),
+                description: "A".into(),
+                category: "Test".to_string(),
+                dependencies: vec!["b".to_string()],
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("b/README.md"),
+                description: "B".into(),
+                category: "Test".to_string(),
+                dependencies: vec!["c".to_string()],
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("c/README.md"),
+                description: "C".into(),
+                category: "Test".to_string(),
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &config);
+        let a_pos = doc.find("`a/README.md`").unwrap();
+        let b_pos = doc.find("`b/README.md`").unwrap();
+        let c_pos = doc.find("`c/README.md`").unwrap();
+        assert!(c_pos < b_pos);
+        assert!(b_pos < a_pos);
+    }
+
+    #[test]
+    fn test_generate_document_sort_by_dependencies_cycle_falls_back_to_path() {
+        let config = config_from_str(r#"sort = "dependencies""#);
+
+        let components = vec![
+            Component {
+                path: PathBuf::from("b/README.md"),
+                description: "B".into(),
+                category: "Test".to_string(),
+                dependencies: vec!["a".to_string()],
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("a/README.md"),
+                description: "A".into(),
+                category: "Test".to_string(),
+                dependencies: vec!["b".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        let doc = generate_document(&components, &config);
+        let a_pos = doc.find("`a/README.md`").unwrap();
+        let b_pos = doc.find("`b/README.md`").unwrap();
+        assert!(a_pos < b_pos);
+    }
+
+    #[test]
+    fn test_generate_document_entry_template_with_extra() {
+        let config = config_from_str(
+            r#"entry_template = "- [{name}]({path}) ({extra.jira}): {description}""#,
+        );
+
+        let components = vec![Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            category: "core".to_string(),
+            extra: [("jira".to_string(), serde_yaml::Value::from("CORE"))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }];
+
+        let doc = generate_document(&components, &config);
+        assert!(doc.contains("- [core](core/README.md) (CORE): Core lib\n"));
+    }
+
+    #[test]
+    fn test_render_entry_template_sorts_tags_and_mappings() {
+        let config =
+            config_from_str(r#"entry_template = "- {path} [{tags}] {extra.owners} {extra.links}""#);
+        let component = |tags: &[&str], front_matter: &str| {
+            let extra: BTreeMap<String, serde_yaml::Value> =
+                serde_yaml::from_str(front_matter).unwrap();
+            Component {
+                path: PathBuf::from("core/README.md"),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                extra,
+                ..Default::default()
             }
+        };
 
-            doc.push('\n');
-            for comp in comps {
-                writeln!(doc, "- `{}`: {}", comp.path.display(), comp.description).unwrap();
-            }
-        }
+        let first = component(
+            &["zeta", "alpha", "zeta"],
+            "owners: {web: bob, api: alice}\nlinks: [{z: 1, a: 2}]",
+        );
+        let second = component(
+            &["alpha", "zeta"],
+            "links: [{a: 2, z: 1}]\nowners: {api: alice, web: bob}",
+        );
+
+        let rendered = render_entry(&first, &config);
+        assert_eq!(
+            rendered,
+            "- core/README.md [alpha, zeta] api: alice\nweb: bob - a: 2\n  z: 1\n"
+        );
+        assert_eq!(render_entry(&second, &config), rendered);
     }
 
-    doc
-}
+    #[test]
+    fn test_generate_document_category_entry_templates() {
+        let config = config_from_str(
+            r#"
+entry_template = "* {path} - {description}"
 
-/// Groups components by category, sorting by path within each group.
-fn group_by_category(components: &[Component]) -> HashMap<String, Vec<&Component>> {
-    let mut grouped: HashMap<String, Vec<&Component>> =
-        components.iter().into_group_map_by(|c| c.category.clone());
+[aliases]
+svc = "Services"
 
-    for comps in grouped.values_mut() {
-        comps.sort_by_key(|c| &c.path);
-    }
+[[categories]]
+category = "Services"
+entry_template = "- {name} on port {extra.port}"
 
-    grouped
-}
+[[categories]]
+category = "Libraries"
+entry_template = "- {name}: {description}"
 
-/// Orders categories, config-specified order first, then remaining alphabetically.
-fn order_categories<'a>(
-    grouped: &'a HashMap<String, Vec<&Component>>,
-    config: &'a Config,
-) -> Vec<&'a str> {
-    let config_order = config.category_order();
+[[categories]]
+category = "Tools"
+"#,
+        );
+        let component = |path: &str, category: &str| Component {
+            path: PathBuf::from(path),
+            description: "Does things".into(),
+            category: category.to_string(),
+            extra: [("port".to_string(), serde_yaml::Value::from(8080))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
 
-    let mut result: Vec<&str> = config_order
-        .iter()
-        .copied()
-        .filter(|name| grouped.contains_key(*name))
-        .collect();
+        let doc = generate_document(
+            &[
+                component("api/README.md", "svc"),
+                component("core/README.md", "Libraries"),
+                component("lint/README.md", "Tools"),
+            ],
+            &config,
+        );
+        assert!(doc.contains("## Services\n\n- api on port 8080\n"));
+        assert!(doc.contains("## Libraries\n\n- core: Does things\n"));
+        assert!(doc.contains("## Tools\n\n* lint/README.md - Does things\n"));
 
-    let mut remaining: Vec<_> = grouped
-        .keys()
-        .map(String::as_str)
-        .filter(|name| !config_order.contains(name))
-        .collect();
-    remaining.sort_unstable();
+        // Without a document-level template, other categories use the built-in entry
+        let mut config = config;
+        config.entry_template = None;
+        assert_eq!(
+            render_entry(&component("lint/README.md", "Tools"), &config),
+            "- `lint/README.md`: Does things\n"
+        );
+    }
 
-    result.extend(remaining);
-    result
-}
+    fn with_related() -> Vec<Component> {
+        vec![
+            Component {
+                path: PathBuf::from("crates/cache/README.md"),
+                description: "Caching".into(),
+                category: "Core".to_string(),
+                related: vec!["store".to_string(), "crates/missing".to_string()],
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("crates/store/README.md"),
+                description: "Storage".into(),
+                category: "Services".to_string(),
+                display_title: Some("Store".to_string()),
+                ..Default::default()
+            },
+        ]
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::DEFAULT_TITLE;
-    use std::path::PathBuf;
+    #[test]
+    fn test_generate_document_related_plain() {
+        let doc = generate_document(&with_related(), &Config::default());
+        assert!(doc
+            .contains("- `crates/cache/README.md`: Caching\n  See also: Store, crates/missing\n"));
+        assert!(doc.contains("- `crates/store/README.md`: Storage\n"));
+    }
 
-    fn config_from_str(toml: &str) -> Config {
-        toml::from_str(toml).unwrap()
+    #[test]
+    fn test_generate_document_related_anchors() {
+        let doc = generate_document(&with_related(), &config_from_str("explicit_anchors = true"));
+        // Resolved references link to the target's entry anchor, unresolved ones stay plain
+        assert!(doc.contains(
+            "- `crates/cache/README.md`: Caching\n  \
+             See also: [Store](#component-crates-store), crates/missing\n"
+        ));
+        assert!(doc.contains(
+            "- <a name=\"component-crates-store\"></a>`crates/store/README.md`: Storage\n"
+        ));
     }
 
     #[test]
-    fn test_generate_document_empty() {
-        let components = vec![];
-        let doc = generate_document(&components, &Config::default());
-        assert_eq!(doc.trim(), format!("# {}", DEFAULT_TITLE));
+    fn test_related_links_resolve() {
+        let components = with_related();
+        let links = RelatedLinks::new(&components);
+        let store = Some(&components[1]);
+        assert_eq!(links.resolve("store"), store);
+        assert_eq!(links.resolve("crates/store/"), store);
+        assert_eq!(links.resolve("crates/store/README.md"), store);
+        assert_eq!(links.resolve("Store"), None);
+        assert!(links.is_linked(&components[1]));
+        assert!(!links.is_linked(&components[0]));
     }
 
     #[test]
-    fn test_generate_document_single_category() {
+    fn test_related_links_resolve_first_match() {
+        // `cache` names the first component by name and the second by directory
+        let components: Vec<Component> = ["lib/cache/README.md", "cache/README.md"]
+            .into_iter()
+            .map(|path| Component {
+                path: PathBuf::from(path),
+                ..Default::default()
+            })
+            .collect();
+        let links = RelatedLinks::new(&components);
+        assert_eq!(links.resolve("cache"), Some(&components[0]));
+    }
+
+    #[test]
+    fn test_generate_document_embed_bodies() {
         let components = vec![Component {
-            path: PathBuf::from("crates/core/README.md"),
-            description: "Core utilities".to_string(),
-            category: "Utilities".to_string(),
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            category: "core".to_string(),
+            body: Some("# Core\n\nCore lib.\n\n## Usage\n\nRun it.".to_string()),
+            ..Default::default()
         }];
 
         let doc = generate_document(&components, &Config::default());
-        assert!(doc.contains(&format!("# {}", DEFAULT_TITLE)));
-        assert!(doc.contains("## Utilities"));
-        assert!(doc.contains("crates/core"));
-        assert!(doc.contains("Core utilities"));
+        assert!(!doc.contains("<details>"));
+
+        let doc = generate_document(&components, &config_from_str("embed_bodies = true"));
+        assert!(doc.contains(
+            "- `core/README.md`: Core lib\n\n<details>\n<summary>core/README.md</summary>\n\n\
+             # Core\n\nCore lib.\n\n## Usage\n\nRun it.\n\n</details>\n"
+        ));
     }
 
     #[test]
-    fn test_generate_document_multiple_categories() {
-        let components = vec![
-            Component {
-                path: PathBuf::from("crates/core/README.md"),
-                description: "Core utilities".to_string(),
-                category: "Utilities".to_string(),
-            },
-            Component {
-                path: PathBuf::from("crates/cli/README.md"),
-                description: "CLI interface".to_string(),
-                category: "Interfaces".to_string(),
-            },
-            Component {
-                path: PathBuf::from("crates/helpers/README.md"),
-                description: "Helper functions".to_string(),
-                category: "Utilities".to_string(),
-            },
-        ];
+    fn test_generate_document_embed_bodies_demote_headings() {
+        let components = vec![Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            category: "core".to_string(),
+            body: Some("# Core\n\n## Usage\n\n#hashtag".to_string()),
+            ..Default::default()
+        }];
 
-        let doc = generate_document(&components, &Config::default());
-        assert!(doc.contains("## Utilities"));
-        assert!(doc.contains("## Interfaces"));
-        assert!(doc.contains("crates/core"));
-        assert!(doc.contains("crates/cli"));
-        assert!(doc.contains("crates/helpers"));
+        let config = config_from_str("embed_bodies = true\ndemote_headings = true");
+        let doc = generate_document(&components, &config);
+        assert!(doc.contains("\n### Core\n\n#### Usage\n\n#hashtag\n"));
     }
 
     #[test]
-    fn test_generate_document_sorted_categories() {
+    fn test_render_entry_matches_document() {
         let components = vec![
             Component {
-                path: PathBuf::from("crates/cli/README.md"),
-                description: "CLI interface".to_string(),
-                category: "Interfaces".to_string(),
+                path: PathBuf::from("core/README.md"),
+                description: "Core lib".into(),
+                category: "core".to_string(),
+                link: Some("core/core.md".to_string()),
+                ..Default::default()
             },
             Component {
-                path: PathBuf::from("crates/core/README.md"),
-                description: "Core utilities".to_string(),
-                category: "Utilities".to_string(),
+                path: PathBuf::from("cli/README.md"),
+                description: "CLI".into(),
+                category: "core".to_string(),
+                body: Some("# CLI\n\nRuns things.".to_string()),
+                ..Default::default()
             },
         ];
 
-        let doc = generate_document(&components, &Config::default());
-        let interfaces_pos = doc.find("## Interfaces").unwrap();
-        let utilities_pos = doc.find("## Utilities").unwrap();
-        // Categories should be sorted alphabetically
-        assert!(interfaces_pos < utilities_pos);
+        for config in [
+            Config::default(),
+            config_from_str(r#"entry_template = "* {name} ({path})""#),
+            config_from_str("embed_bodies = true\ndemote_headings = true"),
+        ] {
+            let doc = generate_document(&components, &config);
+            for comp in &components {
+                let entry = render_entry(comp, &config);
+                assert!(entry.ends_with('\n'));
+                assert!(doc.contains(&entry), "{:?} not in {:?}", entry, doc);
+            }
+        }
     }
 
     #[test]
-    fn test_generate_document_multiple_components_same_category() {
-        let components = vec![
-            Component {
-                path: PathBuf::from("a/README.md"),
-                description: "First".to_string(),
-                category: "Test".to_string(),
-            },
-            Component {
-                path: PathBuf::from("b/README.md"),
-                description: "Second".to_string(),
-                category: "Test".to_string(),
-            },
-        ];
-
-        let doc = generate_document(&components, &Config::default());
-        assert!(doc.contains("First"));
-        assert!(doc.contains("Second"));
-        let category_count = doc.matches("## Test").count();
+    fn test_render_entry() {
+        let comp = Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            ..Default::default()
+        };
         assert_eq!(
-            category_count, 1,
-            "Should only have one Test category header"
+            render_entry(&comp, &Config::default()),
+            "- `core/README.md`: Core lib\n"
         );
     }
 
     #[test]
-    fn test_generate_document_with_custom_title() {
-        let config = config_from_str(r#"title = "Custom Title""#);
-        let components = vec![];
-        let doc = generate_document(&components, &config);
-        assert!(doc.starts_with("# Custom Title"));
+    fn test_render_entry_separator() {
+        let mut comp = Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            ..Default::default()
+        };
+
+        let config = config_from_str(r#"separator = " – ""#);
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- `core/README.md` – Core lib\n"
+        );
+
+        let config = config_from_str(r#"separator = """#);
+        assert_eq!(render_entry(&comp, &config), "- `core/README.md`Core lib\n");
+
+        // Inserted as-is after links too, and ignored by entry templates
+        let config = config_from_str(r#"separator = " — ""#);
+        comp.link = Some("core.md".to_string());
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- [`core/README.md`](core.md) — Core lib\n"
+        );
+        let config =
+            config_from_str("separator = \" — \"\nentry_template = \"{path}: {description}\"");
+        assert_eq!(render_entry(&comp, &config), "core/README.md: Core lib\n");
     }
 
     #[test]
-    fn test_generate_document_with_description() {
+    fn test_render_entry_strip_filename() {
+        let config = config_from_str(r#"strip_filename = "README.md""#);
+        let mut comp = Component {
+            path: PathBuf::from("crates/core/README.md"),
+            description: "Core lib".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- [`crates/core`](crates/core/README.md): Core lib\n"
+        );
+
+        // An explicit link is kept as the target
+        comp.link = Some("docs/core.md".to_string());
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- [`crates/core`](docs/core.md): Core lib\n"
+        );
+
+        // Paths not ending with the filename, or with no directory, are unchanged
+        for path in ["crates/core/GUIDE.md", "README.md"] {
+            let comp = Component {
+                path: PathBuf::from(path),
+                description: "Core lib".into(),
+                ..Default::default()
+            };
+            assert_eq!(
+                render_entry(&comp, &config),
+                format!("- `{}`: Core lib\n", path)
+            );
+        }
+
         let config = config_from_str(
-            r#"
-title = "Arch Doc"
-description = "This is the description."
-"#,
+            "strip_filename = \"README.md\"\nentry_template = \"- [{display_path}]({path})\"",
+        );
+        assert_eq!(
+            render_entry(&comp, &config),
+            "- [crates/core](crates/core/README.md)\n"
         );
-        let components = vec![];
-        let doc = generate_document(&components, &config);
-        assert!(doc.contains("This is the description."));
     }
 
     #[test]
-    fn test_generate_document_category_ordering() {
-        let config = config_from_str(
-            r#"
-[[categories]]
-category = "Utilities"
+    fn test_render_entry_continuation_lines() {
+        let comp = Component {
+            path: PathBuf::from("core/README.md"),
+            description: vec!["Core lib".to_string(), "Shared by every crate.".to_string()].into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_entry(&comp, &Config::default()),
+            "- `core/README.md`: Core lib\n  Shared by every crate.\n"
+        );
 
-[[categories]]
-category = "Interfaces"
-"#,
+        let config = config_from_str(r#"entry_template = "| {name} | {description} |""#);
+        assert_eq!(render_entry(&comp, &config), "| core | Core lib |\n");
+
+        let config = config_from_str(r#"entry_template = "* {description}\n\n  > {continuation}""#);
+        assert_eq!(
+            render_entry(&comp, &config),
+            "* Core lib\n\n  > Shared by every crate.\n"
         );
+    }
 
-        let components = vec![
-            Component {
-                path: PathBuf::from("cli/README.md"),
-                description: "CLI".to_string(),
-                category: "Interfaces".to_string(),
-            },
-            Component {
-                path: PathBuf::from("core/README.md"),
-                description: "Core".to_string(),
-                category: "Utilities".to_string(),
-            },
-        ];
+    #[test]
+    fn test_demote_headings() {
+        assert_eq!(
+            demote_headings("# A\n##### B\n###### C", 2),
+            "### A\n###### B\n###### C"
+        );
+        assert_eq!(
+            demote_headings("```sh\n# comment\n```\n# A", 2),
+            "```sh\n# comment\n```\n### A"
+        );
+    }
 
-        let doc = generate_document(&components, &config);
-        let utilities_pos = doc.find("## Utilities").unwrap();
-        let interfaces_pos = doc.find("## Interfaces").unwrap();
-        // Config order: Utilities before Interfaces
-        assert!(utilities_pos < interfaces_pos);
+    #[test]
+    fn test_generate_document_entry_with_link() {
+        let components = vec![Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            category: "core".to_string(),
+            link: Some("core/core.md".to_string()),
+            ..Default::default()
+        }];
+
+        let doc = generate_document(&components, &Config::default());
+        assert!(doc.contains("- [`core/README.md`](core/core.md): Core lib\n"));
     }
 
     #[test]
-    fn test_generate_document_unlisted_categories_appended() {
+    fn test_render_entry_template_missing_and_unknown_placeholders() {
+        let comp = Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            category: "core".to_string(),
+            ..Default::default()
+        };
+
+        let rendered =
+            render_entry_template("{extra.missing}|{unknown}|{path", &comp, &Config::default());
+        assert_eq!(rendered, "|{unknown}|{path");
+    }
+
+    #[test]
+    fn test_generate_document_aliases_merge_categories() {
         let config = config_from_str(
             r#"
-[[categories]]
-category = "First"
+[aliases]
+cli = "CLI"
+Cli = "CLI"
 "#,
         );
 
         let components = vec![
             Component {
-                path: PathBuf::from("a/README.md"),
-                description: "A".to_string(),
-                category: "First".to_string(),
+                path: PathBuf::from("c/README.md"),
+                description: "C".into(),
+                category: "cli".to_string(),
+                ..Default::default()
             },
             Component {
-                path: PathBuf::from("b/README.md"),
-                description: "B".to_string(),
-                category: "ZUnlisted".to_string(),
+                path: PathBuf::from("a/README.md"),
+                description: "A".into(),
+                category: "CLI".to_string(),
+                ..Default::default()
             },
             Component {
-                path: PathBuf::from("c/README.md"),
-                description: "C".to_string(),
-                category: "AUnlisted".to_string(),
+                path: PathBuf::from("b/README.md"),
+                description: "B".into(),
+                category: "Cli".to_string(),
+                ..Default::default()
             },
         ];
 
         let doc = generate_document(&components, &config);
-        let first_pos = doc.find("## First").unwrap();
-        let a_unlisted_pos = doc.find("## AUnlisted").unwrap();
-        let z_unlisted_pos = doc.find("## ZUnlisted").unwrap();
-
-        // First from config, then unlisted alphabetically
-        assert!(first_pos < a_unlisted_pos);
-        assert!(a_unlisted_pos < z_unlisted_pos);
+        assert_eq!(doc.matches("\n## ").count(), 1);
+        assert!(doc.contains(
+            "## CLI\n\n- `a/README.md`: A\n- `b/README.md`: B\n- `c/README.md`: C\n"
+        ));
     }
 
     #[test]
-    fn test_generate_document_category_display_title() {
-        let config = config_from_str(
-            r#"
-[[categories]]
-category = "utils"
-title = "Utility Functions"
-"#,
-        );
-
-        let components = vec![Component {
-            path: PathBuf::from("utils/README.md"),
-            description: "Utils".to_string(),
-            category: "utils".to_string(),
-        }];
+    fn test_generate_document_deterministic_for_shuffled_input() {
+        let components = vec![
+            Component {
+                path: PathBuf::from("same/README.md"),
+                description: "First".into(),
+                category: "Test".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("same/README.md"),
+                description: "Second".into(),
+                category: "Test".to_string(),
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("other/README.md"),
+                description: "Other".into(),
+                category: "Other".to_string(),
+                ..Default::default()
+            },
+        ];
+        let mut shuffled = components.clone();
+        shuffled.reverse();
+        shuffled.rotate_left(1);
 
-        let doc = generate_document(&components, &config);
-        assert!(doc.contains("## Utility Functions"));
-        assert!(!doc.contains("## utils"));
+        let config = Config::default();
+        assert_eq!(
+            generate_document(&components, &config),
+            generate_document(&shuffled, &config)
+        );
     }
 
     #[test]
@@ -327,8 +2938,9 @@ description = "These are the core components."
 
         let components = vec![Component {
             path: PathBuf::from("core/README.md"),
-            description: "Core lib".to_string(),
+            description: "Core lib".into(),
             category: "core".to_string(),
+            ..Default::default()
         }];
 
         let doc = generate_document(&components, &config);
@@ -0,0 +1,54 @@
+//! HTML output: the markdown document rendered as a standalone page.
+
+use pulldown_cmark::{html, Options, Parser};
+
+use crate::atom::escape_xml;
+use crate::component::Component;
+use crate::config::Config;
+use crate::generator::generate_document;
+
+/// Generates the architecture document as a standalone HTML page.
+///
+/// The page body is [`generate_document`]'s markdown rendered to HTML, with
+/// tables, strikethrough and heading attributes (for `{#slug}` anchors) enabled.
+pub fn generate_html(components: &[Component], config: &Config) -> String {
+    let markdown = generate_document(components, config);
+    let parser = Parser::new_ext(
+        &markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_HEADING_ATTRIBUTES,
+    );
+
+    let mut body = String::new();
+    html::push_html(&mut body, parser);
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(config.title()),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_generate_html() {
+        let config: Config = toml::from_str(r#"title = "Q&A Architecture""#).unwrap();
+        let components = vec![Component {
+            path: PathBuf::from("core/README.md"),
+            description: "Core lib".into(),
+            category: "Utilities".to_string(),
+            ..Default::default()
+        }];
+
+        let html = generate_html(&components, &config);
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<title>Q&amp;A Architecture</title>"));
+        assert!(html.contains("<h1>Q&amp;A Architecture</h1>"));
+        assert!(html.contains("<h2>Utilities</h2>"));
+        assert!(html.contains("<li><code>core/README.md</code>: Core lib</li>"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+    }
+}
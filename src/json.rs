@@ -0,0 +1,222 @@
+//! Normalized JSON document of components, for tools that consume the
+//! architecture programmatically.
+//!
+//! Each component is listed once, under a stable `id`, and the document's
+//! sections refer to components by id. A component that appears in several
+//! sections, such as one with several owners under `group_by = "owner"`, is
+//! therefore never duplicated.
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::component::Component;
+use crate::config::{Config, DEPRECATED_SECTION, PLANNED_SECTION};
+use crate::generator::{flat_sections, group_by_category, is_shown, order_categories};
+
+/// The JSON document.
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    /// Every component once, sorted by id.
+    components: Vec<JsonComponent<'a>>,
+    sections: Vec<JsonSection>,
+}
+
+/// A component with its id.
+#[derive(Serialize)]
+struct JsonComponent<'a> {
+    id: String,
+    #[serde(flatten)]
+    component: &'a Component,
+}
+
+/// A section of the document, listing its components by id in entry order.
+#[derive(Serialize)]
+struct JsonSection {
+    title: String,
+    /// The category the section lists, when grouped by category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    components: Vec<String>,
+}
+
+/// Generates a JSON document with a top-level `components` array and the
+/// document's `sections` in order.
+///
+/// Components carry an `id` derived from their relative path (see
+/// [`Component::id`]) and are sorted by it, listing components that share an
+/// id once; [`check_unique_ids`] rules those out beforehand. Each section has
+/// its display `title`, its `category` when sections are categories, and the
+/// ids of its `components` in entry order.
+pub fn generate_json(components: &[Component], config: &Config) -> String {
+    let sections: Vec<JsonSection> = match flat_sections(components, config) {
+        Some(sections) => sections
+            .into_iter()
+            .map(|(title, comps)| JsonSection {
+                title,
+                category: None,
+                components: comps.iter().map(|c| c.id()).collect(),
+            })
+            .collect(),
+        None => {
+            let grouped = group_by_category(components, config);
+            order_categories(&grouped, config)
+                .into_iter()
+                .map(|category| JsonSection {
+                    title: config.display_title_for(category).to_string(),
+                    category: (![DEPRECATED_SECTION, PLANNED_SECTION].contains(&category))
+                        .then(|| category.to_string()),
+                    components: grouped[category].iter().map(|c| c.id()).collect(),
+                })
+                .collect()
+        }
+    };
+
+    let unique: BTreeMap<String, &Component> = components
+        .iter()
+        .filter(|c| is_shown(c, config))
+        .map(|c| (c.id(), c))
+        .collect();
+    let document = JsonDocument {
+        title: config.title(),
+        description: config.description.as_deref(),
+        components: unique
+            .into_iter()
+            .map(|(id, component)| JsonComponent { id, component })
+            .collect(),
+        sections,
+    };
+    serde_json::to_string_pretty(&document).expect("components serialize to JSON")
+}
+
+/// Fails if two shown components share an id, as files with the same relative
+/// path under the bases of different patterns do.
+///
+/// [`generate_json`] lists each id once, so it would otherwise merge distinct
+/// components into one.
+pub fn check_unique_ids(components: &[Component], config: &Config) -> Result<()> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for comp in components.iter().filter(|c| is_shown(c, config)) {
+        *counts.entry(comp.id()).or_default() += 1;
+    }
+    let duplicates = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    if !duplicates.is_empty() {
+        bail!(
+            "Several components have the id {}; their patterns need different base directories",
+            duplicates.iter().map(|id| format!("'{}'", id)).join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::path::PathBuf;
+
+    fn component(path: &str, category: &str, owners: &[&str]) -> Component {
+        Component {
+            path: PathBuf::from(path),
+            description: "Description".into(),
+            category: category.to_string(),
+            owners: owners.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn json(components: &[Component], config: &Config) -> Value {
+        serde_json::from_str(&generate_json(components, config)).unwrap()
+    }
+
+    #[test]
+    fn test_generate_json_sections_reference_ids() {
+        let config: Config =
+            toml::from_str("[[categories]]\ncategory = \"Services\"\ntitle = \"Backend Services\"")
+                .unwrap();
+        let components = vec![
+            component("core/README.md", "Core", &[]),
+            component("api/README.md", "Services", &[]),
+        ];
+
+        let doc = json(&components, &config);
+        assert_eq!(doc["components"][0]["id"], "api/README.md");
+        assert_eq!(doc["components"][0]["category"], "Services");
+        assert_eq!(doc["components"][1]["id"], "core/README.md");
+        assert_eq!(
+            doc["sections"],
+            serde_json::json!([
+                {"title": "Backend Services", "category": "Services", "components": ["api/README.md"]},
+                {"title": "Core", "category": "Core", "components": ["core/README.md"]},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_generate_json_deduplicates_components() {
+        let config: Config = toml::from_str("group_by = \"owner\"").unwrap();
+        let components = vec![
+            component("web/README.md", "Core", &["platform", "web"]),
+            component("db/README.md", "Core", &["platform"]),
+        ];
+
+        let doc = json(&components, &config);
+        let ids: Vec<&str> = doc["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["db/README.md", "web/README.md"]);
+        assert_eq!(
+            doc["sections"],
+            serde_json::json!([
+                {"title": "platform", "components": ["db/README.md", "web/README.md"]},
+                {"title": "web", "components": ["web/README.md"]},
+            ])
+        );
+
+        // Ids don't depend on input order
+        let reversed: Vec<Component> = components.into_iter().rev().collect();
+        assert_eq!(json(&reversed, &config)["components"], doc["components"]);
+    }
+
+    #[test]
+    fn test_check_unique_ids() {
+        let config = Config::default();
+        let components = vec![
+            component("db/README.md", "Services", &[]),
+            component("api/README.md", "Services", &[]),
+        ];
+        assert!(check_unique_ids(&components, &config).is_ok());
+
+        let mut colliding = components.clone();
+        colliding.push(component("db/README.md", "Legacy", &[]));
+        let err = check_unique_ids(&colliding, &config).unwrap_err();
+        assert!(err.to_string().contains("'db/README.md'"), "{}", err);
+    }
+
+    #[test]
+    fn test_generate_json_planned_section_has_no_category() {
+        let config: Config = toml::from_str("show_planned = true").unwrap();
+        let mut components = vec![component("db/README.md", "Core", &[])];
+        components[0].planned = true;
+
+        assert_eq!(
+            json(&components, &config)["sections"],
+            serde_json::json!([{"title": "Planned", "components": ["db/README.md"]}])
+        );
+        assert_eq!(
+            json(&components, &Config::default())["components"],
+            serde_json::json!([])
+        );
+    }
+}
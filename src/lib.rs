@@ -1,8 +1,68 @@
+mod atom;
+mod cache;
+mod collect;
 mod component;
 mod config;
+mod dependencies;
+mod discovery;
+mod doctor;
+mod error;
+mod explain;
+mod filter;
 mod front_matter;
 mod generator;
+mod html;
+mod json;
+mod llms;
+mod normalize;
+mod outline;
+mod region;
+mod serve;
+mod slug;
+mod split;
+mod stub;
+mod table;
+#[cfg(feature = "testing")]
+mod testing;
+mod validate;
+mod verify;
 
-pub use component::{parse_component, Component};
-pub use config::{CategoryConfig, Config};
-pub use generator::generate_document;
+pub use atom::generate_atom_feed;
+pub use collect::{Collected, Collector};
+pub use component::{
+    parse_component, parse_component_content, parse_component_content_with, parse_component_with,
+    parse_components, parse_components_with, Component, Description, DescriptionSource,
+};
+pub use config::{
+    AnchorStyle, CategoryConfig, CategoryOrder, Config, ConfigSource, DescriptionFallback,
+    FrontMatterFormat, GroupBy, IndexStyle, LegendPosition, Pin, SortOrder, SourceConfig,
+};
+pub use dependencies::find_dependency_cycle;
+pub use discovery::{
+    base_dir_from_pattern, common_base_dir, components, components_with, find_orphans,
+    glob_patterns, matching_files, matching_files_in, matching_files_with, merge_matches,
+    with_sources, FileFilter, Globbed,
+};
+pub use doctor::{DocStats, HealthCheck, HealthReport};
+pub use error::{ComponentError, SchemaViolation};
+pub use explain::explain_component;
+pub use filter::ComponentFilter;
+pub use generator::{generate_document, generate_document_with, render_entry, with_final_newline};
+pub use html::generate_html;
+pub use json::{check_unique_ids, generate_json};
+pub use llms::generate_llms_txt;
+pub use normalize::normalize_content;
+pub use outline::generate_outline;
+pub use region::{replace_managed_region, strip_title, REGION_BEGIN, REGION_END};
+pub use serve::PreviewServer;
+pub use split::{category_page_path, generate_split, SplitDocument};
+pub use stub::{generate_stub, stub_path};
+pub use table::generate_csv;
+#[cfg(feature = "testing")]
+pub use testing::render_to_string;
+pub use validate::{
+    check_category_keys, check_component, check_dependency_order, check_heading_offset,
+    check_links, check_near_miss_category, check_related, near_miss_category, Finding, SkipReport,
+    ValidationReport,
+};
+pub use verify::{verify_document, VerifyReport};
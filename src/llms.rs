@@ -0,0 +1,153 @@
+//! Plain-text summary of components in the style of `llms.txt`.
+//!
+//! The summary carries the same content as the markdown document in the same
+//! order, without markdown decoration, so it costs few tokens when given to an
+//! assistant as context.
+
+use std::fmt::Write;
+
+use crate::component::Component;
+use crate::config::Config;
+use crate::generator::{entry_description, flat_sections, group_by_category, order_categories};
+
+/// Generates a plain-text summary of the components.
+///
+/// The summary starts with the title and the description, if any. Each
+/// category follows as a header line with its display title, or the titles of
+/// each of its levels joined with ` / ` for a nested one, and one
+/// `path — description` line per component, with multi-line descriptions
+/// joined into one line. With `group_by = "release"` or `"owner"`, the
+/// release cohorts or owners take the place of the categories.
+pub fn generate_llms_txt(components: &[Component], config: &Config) -> String {
+    let mut text = format!("{}\n", config.title());
+    if let Some(desc) = &config.description {
+        writeln!(text, "\n{}", desc.trim_end()).unwrap();
+    }
+
+    if let Some(sections) = flat_sections(components, config) {
+        for (title, comps) in sections {
+            writeln!(text, "\n{}", title).unwrap();
+            write_lines(&mut text, &comps, config);
+        }
+        return text;
+    }
+
+    let grouped = group_by_category(components, config);
+    let separator = config.category_separator();
+    for category in order_categories(&grouped, config) {
+        let segments = config.category_segments(category);
+        let header: Vec<String> = (1..=segments.len())
+            .map(|depth| {
+                let level = segments[..depth].join(separator);
+                config.display_title_for(&level).to_string()
+            })
+            .collect();
+        writeln!(text, "\n{}", header.join(" / ")).unwrap();
+        write_lines(&mut text, &grouped[category], config);
+    }
+
+    text
+}
+
+/// Writes the `path — description` line of each component.
+fn write_lines(text: &mut String, components: &[&Component], config: &Config) {
+    for comp in components {
+        writeln!(
+            text,
+            "{} \u{2014} {}",
+            comp.path.display(),
+            entry_description(comp, config).joined()
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn component(path: &str, description: &str, category: &str) -> Component {
+        Component {
+            path: PathBuf::from(path),
+            description: description.into(),
+            category: category.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_llms_txt() {
+        let config: Config = toml::from_str(
+            r#"
+title = "Platform"
+description = "Everything we run."
+
+[[categories]]
+category = "Services"
+title = "Backend Services"
+"#,
+        )
+        .unwrap();
+        let components = vec![
+            component("core/README.md", "Parses `input` files", "Core"),
+            component("api/README.md", "The API", "Services"),
+            Component {
+                status: Some("beta".to_string()),
+                tags: vec!["async".to_string()],
+                ..component("cli/README.md", "The entry point", "Core")
+            },
+        ];
+
+        assert_eq!(
+            generate_llms_txt(&components, &config),
+            "Platform\n\nEverything we run.\n\n\
+             Backend Services\n\
+             api/README.md \u{2014} The API\n\n\
+             Core\n\
+             cli/README.md \u{2014} The entry point\n\
+             core/README.md \u{2014} Parses `input` files\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_llms_txt_has_no_markdown_decoration() {
+        let components = vec![
+            component("a/README.md", "A", "Backend/Storage"),
+            Component {
+                link: Some("docs/b.md".to_string()),
+                ..component("b/README.md", "B", "Core")
+            },
+        ];
+
+        let config: Config = toml::from_str("category_separator = \"/\"").unwrap();
+        let text = generate_llms_txt(&components, &config);
+        for line in text.lines() {
+            assert!(!line.starts_with(['-', '#', '*']), "{}", line);
+            assert!(!line.contains('`') && !line.contains('['), "{}", line);
+        }
+        assert!(text.contains("\nBackend / Storage\na/README.md \u{2014} A\n"));
+        assert!(text.contains("\nCore\nb/README.md \u{2014} B\n"));
+    }
+
+    #[test]
+    fn test_generate_llms_txt_by_release() {
+        let components = vec![
+            Component {
+                since: Some("1.2.0".to_string()),
+                ..component("a/README.md", "A", "Core")
+            },
+            component("b/README.md", "B", "Core"),
+        ];
+
+        let config: Config = toml::from_str("group_by = \"release\"").unwrap();
+        assert_eq!(
+            generate_llms_txt(&components, &config),
+            "Architecture Documentation\n\n\
+             Added in 1.2.0\n\
+             a/README.md \u{2014} A\n\n\
+             Unversioned\n\
+             b/README.md \u{2014} B\n"
+        );
+    }
+}
@@ -1,25 +1,181 @@
-use anyhow::{Context, Result};
+use anstream::ColorChoice;
+use anstyle::{AnsiColor, Style};
+use anyhow::{bail, Context, Result};
 use argh::FromArgs;
-use glob::glob;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
-use rust_architecture::{generate_document, parse_component, Config};
+use rust_architecture::{
+    base_dir_from_pattern, check_category_keys, check_component, check_dependency_order,
+    check_heading_offset, check_links, check_near_miss_category, check_related, check_unique_ids,
+    common_base_dir, components, components_with, explain_component, find_dependency_cycle,
+    find_orphans, generate_atom_feed, generate_csv, generate_document, generate_html,
+    generate_json, generate_llms_txt, generate_outline, generate_split, generate_stub,
+    glob_patterns, matching_files_with, normalize_content, parse_component_content_with,
+    parse_component_with, render_entry, replace_managed_region, strip_title, stub_path,
+    verify_document, with_final_newline, with_sources, Collector, Component, ComponentError,
+    ComponentFilter, Config, DocStats, Finding, FrontMatterFormat, GroupBy, HealthCheck,
+    HealthReport, PreviewServer, SkipReport, ValidationReport,
+};
 
 #[derive(FromArgs)]
 /// Generate architecture documentation from markdown files
 struct Cli {
+    #[argh(switch)]
+    /// print a failure as a JSON object `{code, message}` on stderr
+    json_errors: bool,
+
+    #[argh(option, default = "ColorWhen::Auto")]
+    /// when to color diagnostics: auto (default; off when not a terminal or
+    /// `NO_COLOR` is set), always or never
+    color: ColorWhen,
+
+    #[argh(switch)]
+    /// never color diagnostics, same as `--color never`
+    no_color: bool,
+
+    #[argh(switch, short = 'v')]
+    /// log details of the run on stderr, such as which config was loaded
+    verbose: bool,
+
+    #[argh(option, short = 'j')]
+    /// number of threads to parse files on, 0 for one per CPU (default: 0);
+    /// `--jobs 1` parses one file at a time
+    jobs: Option<usize>,
+
     #[argh(subcommand)]
     command: Commands,
 }
 
+/// When diagnostics are colored.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColorWhen {
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorWhen {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorWhen::Auto),
+            "always" => Ok(ColorWhen::Always),
+            "never" => Ok(ColorWhen::Never),
+            _ => Err(format!(
+                "unknown color choice '{}', expected one of: auto, always, never",
+                s
+            )),
+        }
+    }
+}
+
+impl From<ColorWhen> for ColorChoice {
+    fn from(when: ColorWhen) -> Self {
+        match when {
+            ColorWhen::Auto => ColorChoice::Auto,
+            ColorWhen::Always => ColorChoice::Always,
+            ColorWhen::Never => ColorChoice::Never,
+        }
+    }
+}
+
+/// Style for errors and checks that fail the run.
+const ERROR: Style = AnsiColor::Red.on_default().bold();
+/// Style for warnings and checks with findings that don't fail the run.
+const WARNING: Style = AnsiColor::Yellow.on_default().bold();
+/// Style for lines removed in a diff.
+const REMOVED: Style = AnsiColor::Red.on_default();
+/// Style for lines added in a diff.
+const ADDED: Style = AnsiColor::Green.on_default();
+/// Style for category names.
+const CATEGORY: Style = AnsiColor::Cyan.on_default();
+
+/// Exit code for a run that failed for any reason without a dedicated code.
+const EXIT_ERROR: u8 = 1;
+/// Exit code when files fail to parse or validate (e.g. unknown categories in strict mode).
+const EXIT_PARSE_FAILURE: u8 = 2;
+/// Exit code when `--check` finds the output out of date, or `verify` finds a mismatch.
+const EXIT_CHECK_MISMATCH: u8 = 3;
+/// Exit code when `--fail-on-empty` finds no components.
+const EXIT_EMPTY_RESULT: u8 = 4;
+
+/// Whether `--verbose` was given, for logging from anywhere in the run.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// A failure with a dedicated exit code.
+#[derive(Debug)]
+enum Failure {
+    /// Files failed to parse or validate.
+    Parse(String),
+    /// Generated output differs from what is on disk, or a document disagrees
+    /// with the components.
+    CheckMismatch(String),
+    /// No components were found.
+    EmptyResult(String),
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Parse(_) => EXIT_PARSE_FAILURE,
+            Failure::CheckMismatch(_) => EXIT_CHECK_MISMATCH,
+            Failure::EmptyResult(_) => EXIT_EMPTY_RESULT,
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Parse(message)
+            | Failure::CheckMismatch(message)
+            | Failure::EmptyResult(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// Maps an error to the process exit code documented for it.
+fn exit_code(err: &anyhow::Error) -> u8 {
+    if let Some(failure) = err.downcast_ref::<Failure>() {
+        failure.exit_code()
+    } else if err.downcast_ref::<ComponentError>().is_some() {
+        EXIT_PARSE_FAILURE
+    } else {
+        EXIT_ERROR
+    }
+}
+
 #[derive(FromArgs)]
 #[argh(subcommand)]
+// Parsed once per run, so boxing the large generate args buys nothing
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     Generate(GenerateArgs),
+    Validate(ValidateArgs),
+    Doctor(DoctorArgs),
+    Verify(VerifyArgs),
+    Preview(PreviewArgs),
+    Explain(ExplainArgs),
+    List(ListArgs),
+    Watch(WatchArgs),
+    Render(RenderArgs),
+    Normalize(NormalizeArgs),
+    PrintConfig(PrintConfigArgs),
 }
 
-#[derive(FromArgs)]
+#[derive(FromArgs, Default)]
 #[argh(subcommand, name = "generate")]
 /// Generate architecture documentation
 struct GenerateArgs {
@@ -31,83 +187,1611 @@ struct GenerateArgs {
     /// output file path for the generated documentation
     output: PathBuf,
 
+    #[argh(option, long = "pattern")]
+    /// another glob pattern to match markdown files (repeatable); a file
+    /// matched by several patterns is documented once
+    patterns: Vec<String>,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// glob pattern for matched files to skip, added to the config's `exclude` (repeatable)
+    exclude: Vec<String>,
+
+    #[argh(option)]
+    /// parse front matter as yaml or toml regardless of its delimiter, or auto
+    /// to go by the delimiter (default: the config's `front_matter_format`)
+    front_matter_format: Option<FrontMatterFormat>,
+
+    #[argh(switch)]
+    /// fail on components whose description falls back to their first
+    /// paragraph, as with `require_front_matter_description = true`
+    require_descriptions: bool,
+
+    #[argh(option)]
+    /// override a config value, as `key=value` (repeatable)
+    set: Vec<String>,
+
+    #[argh(option)]
+    /// directory to write a stub page per component into, linked from the output
+    output_dir: Option<PathBuf>,
+
+    #[argh(switch)]
+    /// print a breakdown of time spent in each phase to stderr
+    timings: bool,
+
+    #[argh(switch)]
+    /// fail instead of creating the output file's parent directory if missing
+    no_create_dirs: bool,
+
+    #[argh(option)]
+    /// skip files nested more than this many directories below the base dir
+    max_depth: Option<usize>,
+
+    #[argh(option)]
+    /// fail before reading any file if the pattern matches more than this many
+    /// files (default: unlimited)
+    max_files: Option<usize>,
+
+    #[argh(option, default = "OutputFormat::Markdown")]
+    /// output format: markdown (default), html, atom, csv, llms, toc or json
+    format: OutputFormat,
+
+    #[argh(option)]
+    /// only document components in this category (repeatable)
+    only: Vec<String>,
+
+    #[argh(option)]
+    /// only document components with this tag (repeatable; all are required)
+    tag: Vec<String>,
+
+    #[argh(option)]
+    /// skip components with this tag (repeatable)
+    not_tag: Vec<String>,
+
+    #[argh(option)]
+    /// only document components whose file changed between this git ref and HEAD
+    since: Option<String>,
+
+    #[argh(option)]
+    /// skeleton template for the whole document, overriding `template_file`
+    output_template: Option<PathBuf>,
+
+    #[argh(switch)]
+    /// show symlinked READMEs at their target's path instead of the matched path
+    follow_symlinks: bool,
+
+    #[argh(switch)]
+    /// use the deepest directory containing every matched file as the base dir,
+    /// instead of the pattern's leading directories
+    common_base: bool,
+
+    #[argh(option)]
+    /// directory to show component paths relative to (default: the pattern's base dir)
+    relative_to: Option<PathBuf>,
+
+    #[argh(switch)]
+    /// write the output as an index plus one page per category next to it
+    split: bool,
+
+    #[argh(switch)]
+    /// write nothing, and fail if the output is missing or out of date
+    check: bool,
+
+    #[argh(switch)]
+    /// fail if no components were found
+    fail_on_empty: bool,
+
+    #[argh(option)]
+    /// also write the parsed components as JSON to this file, for `render`
+    emit_components: Option<PathBuf>,
+
+    #[argh(option)]
+    /// also write documentation coverage stats as JSON to this file, for
+    /// tracking trends in CI
+    emit_stats: Option<PathBuf>,
+
+    #[argh(option)]
+    /// report the files left out of the document and why, with a count per
+    /// reason: human (on stderr) or json (on stdout)
+    report: Option<ReportFormat>,
+
+    #[argh(switch)]
+    /// warn about entry links that don't resolve to a file next to the output
+    verify_links: bool,
+
+    #[argh(switch)]
+    /// like --verify-links, but fail before writing anything if an entry link
+    /// doesn't resolve
+    strict_links: bool,
+
+    #[argh(switch)]
+    /// replace only the lines between the output file's `<!-- ARCH:BEGIN -->`
+    /// and `<!-- ARCH:END -->` markers, keeping the rest of the file
+    managed_region: bool,
+
+    #[argh(switch)]
+    /// with --managed-region, keep the document title in the region
+    keep_title: bool,
+
+    #[argh(switch)]
+    /// skip files ignored by `.gitignore` or `.ignore` rules
+    respect_gitignore: bool,
+}
+
+/// Output format of the generated document
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum OutputFormat {
+    #[default]
+    Markdown,
+    Html,
+    Atom,
+    Csv,
+    Llms,
+    Toc,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            "atom" => Ok(OutputFormat::Atom),
+            "csv" => Ok(OutputFormat::Csv),
+            "llms" => Ok(OutputFormat::Llms),
+            "toc" => Ok(OutputFormat::Toc),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "unknown format '{}', expected one of: markdown, html, atom, csv, llms, toc, json",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "validate")]
+/// Validate that all matched markdown files parse as components
+struct ValidateArgs {
+    #[argh(positional)]
+    /// glob pattern to match markdown files (e.g., **/README.md)
+    pattern: String,
+
     #[argh(option, short = 'c')]
     /// path to config file (default: architecture.toml in current directory)
     config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// glob pattern for matched files to skip, added to the config's `exclude` (repeatable)
+    exclude: Vec<String>,
+
+    #[argh(option)]
+    /// parse front matter as yaml or toml regardless of its delimiter, or auto
+    /// to go by the delimiter (default: the config's `front_matter_format`)
+    front_matter_format: Option<FrontMatterFormat>,
+
+    #[argh(switch)]
+    /// fail on components whose description falls back to their first
+    /// paragraph, as with `require_front_matter_description = true`
+    require_descriptions: bool,
+
+    #[argh(switch)]
+    /// fail if the `dependencies` between components form a cycle
+    check_cycles: bool,
+
+    #[argh(option)]
+    /// report markdown files under this directory that have a `category` in
+    /// their front matter but aren't matched by the pattern
+    find_orphans: Option<PathBuf>,
+
+    #[argh(option)]
+    /// fail if an entry link doesn't resolve to a file, taking links relative
+    /// to this generated document
+    verify_links: Option<PathBuf>,
+
+    #[argh(option, default = "ReportFormat::Human")]
+    /// report format: human (default) or json
+    report: ReportFormat,
+
+    #[argh(option)]
+    /// also write documentation coverage stats as JSON to this file, for
+    /// tracking trends in CI
+    emit_stats: Option<PathBuf>,
+
+    #[argh(switch)]
+    /// skip files ignored by `.gitignore` or `.ignore` rules
+    respect_gitignore: bool,
+}
+
+/// Output format of the validation and skip reports
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    Human,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(ReportFormat::Human),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!(
+                "unknown report format '{}', expected one of: human, json",
+                s
+            )),
+        }
+    }
 }
 
-fn main() -> Result<()> {
+#[derive(FromArgs)]
+#[argh(subcommand, name = "doctor")]
+/// Report documentation health metrics for all matched markdown files
+struct DoctorArgs {
+    #[argh(positional)]
+    /// glob pattern to match markdown files (e.g., **/README.md)
+    pattern: String,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// glob pattern for matched files to skip, added to the config's `exclude` (repeatable)
+    exclude: Vec<String>,
+
+    #[argh(option)]
+    /// parse front matter as yaml or toml regardless of its delimiter, or auto
+    /// to go by the delimiter (default: the config's `front_matter_format`)
+    front_matter_format: Option<FrontMatterFormat>,
+
+    #[argh(option, default = "3")]
+    /// descriptions with fewer words than this are reported as short (default: 3)
+    min_words: usize,
+
+    #[argh(option)]
+    /// report markdown files under this directory that have a `category` in
+    /// their front matter but aren't matched by the pattern
+    find_orphans: Option<PathBuf>,
+
+    #[argh(option)]
+    /// check that fails the run when it has findings (repeatable; default:
+    /// parse-errors and duplicate-paths)
+    fail_on: Vec<HealthCheck>,
+
+    #[argh(switch)]
+    /// skip files ignored by `.gitignore` or `.ignore` rules
+    respect_gitignore: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "verify")]
+/// Check a hand-written architecture document against the matched components
+struct VerifyArgs {
+    #[argh(positional)]
+    /// path to the hand-written architecture document
+    doc: PathBuf,
+
+    #[argh(positional)]
+    /// glob pattern to match markdown files (e.g., **/README.md)
+    pattern: String,
+
+    #[argh(switch)]
+    /// fail when the document and the components disagree, not just on parse errors
+    deny_warnings: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "preview")]
+/// Render a single component from markdown read on stdin
+struct PreviewArgs {
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option, default = "PathBuf::from(\"README.md\")")]
+    /// path to show the component at (default: README.md)
+    path: PathBuf,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "explain")]
+/// Trace how a single file is parsed, from its front matter to its entry
+struct ExplainArgs {
+    #[argh(positional)]
+    /// markdown file to explain
+    path: PathBuf,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "list")]
+/// List matched components with the category each one is grouped under
+struct ListArgs {
+    #[argh(positional)]
+    /// glob pattern to match markdown files (e.g., **/README.md)
+    pattern: String,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// glob pattern for matched files to skip, added to the config's `exclude` (repeatable)
+    exclude: Vec<String>,
+
+    #[argh(switch)]
+    /// also print the category as written in front matter, before aliases are
+    /// resolved (`-` when it was defaulted)
+    show_raw: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "watch")]
+/// Regenerate the documentation whenever a matched file or the config changes
+struct WatchArgs {
+    #[argh(positional)]
+    /// glob pattern to match markdown files (e.g., **/README.md)
+    pattern: String,
+
+    #[argh(positional)]
+    /// output file path for the generated documentation
+    output: PathBuf,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// glob pattern for matched files to skip, added to the config's `exclude` (repeatable)
+    exclude: Vec<String>,
+
+    #[argh(option, default = "500")]
+    /// how often to check for changes, in milliseconds (default: 500)
+    interval: u64,
+
+    #[argh(option)]
+    /// serve the generated HTML, reloading on changes, on this local port
+    serve: Option<u16>,
+
+    #[argh(switch)]
+    /// skip files ignored by `.gitignore` or `.ignore` rules
+    respect_gitignore: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "render")]
+/// Render documentation from components saved by `generate --emit-components`
+struct RenderArgs {
+    #[argh(positional)]
+    /// JSON file of components written by `generate --emit-components`
+    components: PathBuf,
+
+    #[argh(positional)]
+    /// output file path for the generated documentation
+    output: PathBuf,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option, default = "OutputFormat::Markdown")]
+    /// output format: markdown (default), html, atom, csv, llms, toc or json
+    format: OutputFormat,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "normalize")]
+/// Rewrite the front matter of matched markdown files into canonical form
+struct NormalizeArgs {
+    #[argh(positional)]
+    /// glob pattern to match markdown files (e.g., **/README.md)
+    pattern: String,
+
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// glob pattern for matched files to skip, added to the config's `exclude` (repeatable)
+    exclude: Vec<String>,
+
+    #[argh(switch)]
+    /// print the changes as a diff instead of writing them
+    dry_run: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand, name = "print-config")]
+/// Print the config that takes effect after extends, profiles, environment
+/// variables and overrides are applied
+struct PrintConfigArgs {
+    #[argh(option, short = 'c')]
+    /// path to config file (default: architecture.toml in current directory)
+    config: Option<PathBuf>,
+
+    #[argh(option)]
+    /// named `[profiles.<name>]` section of the config to apply
+    profile: Option<String>,
+
+    #[argh(option)]
+    /// override a config value, as `key=value` (repeatable)
+    set: Vec<String>,
+
+    #[argh(option, default = "ConfigFormat::Toml")]
+    /// output format: toml (default) or json
+    format: ConfigFormat,
+}
+
+/// Output format of the printed config
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl FromStr for ConfigFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "toml" => Ok(ConfigFormat::Toml),
+            "json" => Ok(ConfigFormat::Json),
+            _ => Err(format!(
+                "unknown config format '{}', expected one of: toml, json",
+                s
+            )),
+        }
+    }
+}
+
+fn main() -> ExitCode {
     let cli: Cli = argh::from_env();
+    let color = if cli.no_color {
+        ColorWhen::Never
+    } else {
+        cli.color
+    };
+    ColorChoice::from(color).write_global();
+    VERBOSE.store(cli.verbose, Ordering::Relaxed);
+
+    let Err(err) = set_jobs(cli.jobs).and_then(|()| run(cli.command)) else {
+        return ExitCode::SUCCESS;
+    };
+    let code = exit_code(&err);
+    if cli.json_errors {
+        let error = serde_json::json!({ "code": code, "message": format!("{:#}", err) });
+        eprintln!("{}", error);
+    } else {
+        anstream::eprintln!("{ERROR}Error:{ERROR:#} {:?}", err);
+    }
+    ExitCode::from(code)
+}
+
+/// Sizes the thread pool files are parsed on, if `--jobs` was given.
+fn set_jobs(jobs: Option<usize>) -> Result<()> {
+    if let Some(jobs) = jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .context("Failed to start the parsing thread pool")?;
+    }
+    Ok(())
+}
 
-    match cli.command {
+fn run(command: Commands) -> Result<()> {
+    match command {
         Commands::Generate(args) => {
-            generate_architecture(&args.pattern, &args.output, args.config.as_deref())?;
+            generate_architecture(&args)?;
+            if !args.check && args.report != Some(ReportFormat::Json) {
+                println!(
+                    "Architecture documentation generated at: {}",
+                    args.output.display()
+                );
+            }
+        }
+        Commands::Validate(args) => {
+            validate_architecture(&args)?;
+        }
+        Commands::Doctor(args) => {
+            doctor_architecture(&args)?;
+        }
+        Commands::Verify(args) => {
+            verify_architecture(&args)?;
+        }
+        Commands::Preview(args) => {
+            preview_component(&args)?;
+        }
+        Commands::Explain(args) => {
+            explain_file(&args)?;
+        }
+        Commands::List(args) => {
+            list_components(&args)?;
+        }
+        Commands::Watch(args) => {
+            watch_architecture(&args)?;
+        }
+        Commands::Normalize(args) => {
+            normalize_architecture(&args)?;
+        }
+        Commands::Render(args) => {
+            render_architecture(&args)?;
             println!(
                 "Architecture documentation generated at: {}",
                 args.output.display()
             );
         }
+        Commands::PrintConfig(args) => {
+            print_config(&args)?;
+        }
     }
 
     Ok(())
 }
 
-fn generate_architecture(pattern: &str, output: &Path, config_path: Option<&Path>) -> Result<()> {
-    // Load config (use default if not specified or doesn't exist)
-    let config_file = config_path
+/// Loads the config file, defaulting to `architecture.toml` in the current directory.
+fn load_config(config_path: Option<&Path>, profile: Option<&str>) -> Result<Config> {
+    // Use default if not specified or doesn't exist
+    let (config, source) = Config::load_profile_or(&config_file(config_path), profile)?;
+    if VERBOSE.load(Ordering::Relaxed) {
+        eprintln!("Using config: {}", source);
+    }
+    Ok(config)
+}
+
+/// The config file to load: the given path, or `architecture.toml` in the current directory.
+fn config_file(config_path: Option<&Path>) -> PathBuf {
+    config_path
         .map(PathBuf::from)
-        .unwrap_or_else(|| PathBuf::from("architecture.toml"));
+        .unwrap_or_else(|| PathBuf::from("architecture.toml"))
+}
+
+/// Applies `--set key=value` overrides to the config, in order.
+fn apply_overrides(config: &mut Config, assignments: &[String]) -> Result<()> {
+    for assignment in assignments {
+        let (key, value) = assignment
+            .split_once('=')
+            .with_context(|| format!("Invalid --set value, expected key=value: {}", assignment))?;
+        config.set(key.trim(), value)?;
+    }
+    Ok(())
+}
+
+fn print_config(args: &PrintConfigArgs) -> Result<()> {
+    let mut config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    apply_overrides(&mut config, &args.set)?;
+
+    match args.format {
+        ConfigFormat::Toml => print!("{}", toml::to_string_pretty(&config)?),
+        ConfigFormat::Json => println!("{}", serde_json::to_string_pretty(&config)?),
+    }
+    Ok(())
+}
+
+/// The components a `generate` run documented, and the config it rendered
+/// them with.
+struct Documented {
+    components: Vec<Component>,
+    config: Config,
+}
+
+fn generate_architecture(args: &GenerateArgs) -> Result<Documented> {
+    let mut config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    apply_overrides(&mut config, &args.set)?;
+    if let Some(template_file) = &args.output_template {
+        config.template = Some(fs::read_to_string(template_file).with_context(|| {
+            format!("Failed to read template file: {}", template_file.display())
+        })?);
+    }
+
+    let mut timings = Timings::default();
+
+    config.exclude.extend(args.exclude.iter().cloned());
+    if let Some(format) = args.front_matter_format {
+        config.front_matter_format = format;
+    }
+    if args.require_descriptions {
+        config.require_front_matter_description = true;
+    }
+
+    let patterns: Vec<&str> = std::iter::once(args.pattern.as_str())
+        .chain(args.patterns.iter().map(String::as_str))
+        .collect();
+    let patterns = with_sources(&patterns, &config);
+    // Each matched file with the base dir of the pattern it is documented under
+    let mut files: Vec<(PathBuf, PathBuf)> = timings.time("glob", || {
+        let globbed = glob_patterns(
+            &patterns,
+            &config.exclude,
+            args.respect_gitignore,
+            args.max_files,
+        )?;
+        for e in globbed.errors {
+            eprintln!("Error reading path: {:#}", e);
+        }
+        anyhow::Ok(globbed.files)
+    })?;
+    let base_dir = base_dir_from_pattern(&args.pattern);
+
+    if let Some(max_depth) = args.max_depth {
+        files.retain(|(file, base)| directory_depth(file, base) <= max_depth);
+    }
+
+    if let Some(since) = &args.since {
+        let changed = changed_files(since, &base_dir)?;
+        files.retain(|(file, _)| fs::canonicalize(file).is_ok_and(|file| changed.contains(&file)));
+    }
+
+    if args.follow_symlinks {
+        for (file, base) in &mut files {
+            if let Ok(canonical) = fs::canonicalize(&file) {
+                *file = canonical;
+            }
+            if let Ok(canonical) = fs::canonicalize(base.join(".")) {
+                *base = canonical;
+            }
+        }
+    }
+    if args.common_base {
+        let matched: Vec<PathBuf> = files.iter().map(|(file, _)| file.clone()).collect();
+        if let Some(common) = common_base_dir(&matched) {
+            for (_, base) in &mut files {
+                *base = common.clone();
+            }
+        }
+    }
+    let collector = timings.time("read", || Collector::read(files));
+    timings.files = collector.files();
+    timings.bytes = collector.bytes();
+
+    let mut stats = None;
+    let collected = timings.time("parse", || {
+        collector.parse(&config, |results| {
+            if args.emit_stats.is_some() {
+                stats =
+                    Some(HealthReport::new(results.iter().map(Result::as_ref), &config, 0).stats());
+            }
+        })
+    });
+    if let Some(format) = args.report {
+        print_skip_report(&collected.report, format)?;
+    }
+    if let Some(err) = collected.error {
+        return Err(err);
+    }
+    let mut parsed = collected.components;
+
+    let filter = ComponentFilter {
+        categories: args.only.clone(),
+        tags: args.tag.clone(),
+        not_tags: args.not_tag.clone(),
+    };
+    parsed.retain(|(component, _)| filter.matches(component, &config));
+    let (mut components, sources): (Vec<Component>, Vec<PathBuf>) = parsed.into_iter().unzip();
+
+    // Paths are parsed relative to their pattern's base; re-root them for display
+    if let Some(root) = &args.relative_to {
+        let display_base = if args.follow_symlinks {
+            fs::canonicalize(root).unwrap_or(root.clone())
+        } else {
+            root.clone()
+        };
+        for (component, source) in components.iter_mut().zip(&sources) {
+            component.path = relative_path(&display_base, source)?;
+        }
+    }
+
+    if args.fail_on_empty && components.is_empty() {
+        return Err(Failure::EmptyResult(format!(
+            "No components found matching: {}",
+            args.pattern
+        ))
+        .into());
+    }
+
+    let stubs_start = Instant::now();
+    let output = &args.output;
+    let output_parent = output.parent().unwrap_or(Path::new(""));
+
+    if let Some(output_dir) = &args.output_dir {
+        let mut stub_owners: HashMap<PathBuf, &Path> = HashMap::new();
+        for component in &components {
+            if let Some(other) = stub_owners.insert(stub_path(component), &component.path) {
+                bail!(
+                    "{} and {} would both be written to stub page {}; \
+                     rename one of their directories or categories",
+                    other.display(),
+                    component.path.display(),
+                    output_dir.join(stub_path(component)).display()
+                );
+            }
+        }
+
+        for (component, source) in components.iter_mut().zip(&sources) {
+            let stub_file = output_dir.join(stub_path(component));
+            let stub_dir = stub_file.parent().unwrap_or(output_dir);
+            let source = relative_path(stub_dir, source)?;
 
-    let config = Config::load(&config_file)?;
+            if !args.check {
+                fs::create_dir_all(stub_dir)?;
+                fs::write(&stub_file, generate_stub(component, &config, &source)).with_context(
+                    || format!("Failed to write stub file: {}", stub_file.display()),
+                )?;
+            }
 
-    let files = find_markdown_files(pattern)?;
-    let base_dir = get_base_dir_from_pattern(pattern);
+            component.link = Some(relative_path(output_parent, &stub_file)?.display().to_string());
+        }
+    }
+    if args.output_dir.is_some() {
+        timings.record("stubs", stubs_start.elapsed());
+    }
 
-    let mut components = Vec::new();
-    for file in files {
-        if let Ok(component) = parse_component(file, &base_dir) {
-            components.push(component);
+    for finding in check_category_keys(&config)
+        .into_iter()
+        .chain(check_heading_offset(&config))
+        .chain(check_dependency_order(&components, &config))
+        .chain(check_related(&components))
+    {
+        anstream::eprintln!("{WARNING}Warning:{WARNING:#} {}", finding.message);
+    }
+    if args.verify_links || args.strict_links {
+        let broken = check_links(&components, &config, output_parent);
+        for finding in &broken {
+            if args.strict_links {
+                anstream::eprintln!("{ERROR}Error:{ERROR:#} {}", finding.message);
+            } else {
+                anstream::eprintln!("{WARNING}Warning:{WARNING:#} {}", finding.message);
+            }
         }
+        if args.strict_links && !broken.is_empty() {
+            return Err(Failure::Parse(format!(
+                "{} entry link(s) don't resolve from {}",
+                broken.len(),
+                output.display()
+            ))
+            .into());
+        }
+    }
+
+    if let Some(file) = args.emit_components.as_ref().filter(|_| !args.check) {
+        fs::write(file, serde_json::to_string_pretty(&components)?)
+            .with_context(|| format!("Failed to write components file: {}", file.display()))?;
     }
+    if let (Some(file), Some(stats)) = (&args.emit_stats, &stats) {
+        write_stats(file, stats)?;
+    }
+
+    if args.split && args.format != OutputFormat::Markdown {
+        bail!("--split is only supported with --format markdown");
+    }
+    if args.split {
+        check_group_by(&config, "--split")?;
+    } else if args.format == OutputFormat::Csv {
+        check_group_by(&config, "--format csv")?;
+    } else if args.format == OutputFormat::Json {
+        check_unique_ids(&components, &config)?;
+    }
+    if args.managed_region && (args.split || args.format != OutputFormat::Markdown) {
+        bail!("--managed-region is only supported with --format markdown, without --split");
+    }
+    let index_name = output
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut pages = Vec::new();
+    let doc = timings.time("render", || match args.format {
+        OutputFormat::Markdown if args.split => {
+            let split = generate_split(&components, &config, &index_name);
+            pages = split.pages;
+            split.index
+        }
+        OutputFormat::Markdown => generate_document(&components, &config),
+        OutputFormat::Html => generate_html(&components, &config),
+        OutputFormat::Atom => generate_atom_feed(&components, &config),
+        OutputFormat::Csv => generate_csv(&components, &config),
+        OutputFormat::Llms => generate_llms_txt(&components, &config),
+        OutputFormat::Toc => generate_outline(&components, &config),
+        OutputFormat::Json => generate_json(&components, &config),
+    });
+    let doc = if args.managed_region {
+        let existing = fs::read_to_string(output).with_context(|| {
+            format!(
+                "Failed to read {} to update its managed region",
+                output.display()
+            )
+        })?;
+        let region = if args.keep_title {
+            &doc
+        } else {
+            strip_title(&doc)
+        };
+        replace_managed_region(&existing, region)
+            .with_context(|| format!("Failed to update {}", output.display()))?
+    } else {
+        doc
+    };
+    let doc = with_final_newline(doc, &config);
+    let pages: Vec<_> = pages
+        .into_iter()
+        .map(|(page_path, page)| (page_path, with_final_newline(page, &config)))
+        .collect();
 
-    let doc = generate_document(&components, &config);
+    if args.check {
+        let pages = pages
+            .into_iter()
+            .map(|(page_path, page)| (output_parent.join(page_path), page));
+        let stale: Vec<_> = [(output.clone(), doc)]
+            .into_iter()
+            .chain(pages)
+            .filter(|(file, content)| fs::read_to_string(file).ok().as_ref() != Some(content))
+            .map(|(file, _)| file.display().to_string())
+            .collect();
+        if !stale.is_empty() {
+            return Err(Failure::CheckMismatch(format!(
+                "Output is out of date: {}",
+                stale.join(", ")
+            ))
+            .into());
+        }
+        println!("Output is up to date");
+        return Ok(Documented { components, config });
+    }
 
+    let write_start = Instant::now();
     if let Some(parent) = output.parent() {
-        fs::create_dir_all(parent)?;
+        if args.no_create_dirs {
+            if !parent.as_os_str().is_empty() && !parent.is_dir() {
+                bail!(
+                    "Output directory does not exist: {} (remove --no-create-dirs to create it)",
+                    parent.display()
+                );
+            }
+        } else {
+            fs::create_dir_all(parent)?;
+        }
     }
 
     fs::write(output, doc).context("Failed to write output file")?;
+    for (page_path, page) in pages {
+        let page_file = output_parent.join(page_path);
+        fs::write(&page_file, page)
+            .with_context(|| format!("Failed to write category page: {}", page_file.display()))?;
+    }
+    timings.record("write", write_start.elapsed());
+
+    if args.timings {
+        timings.report();
+    }
+
+    Ok(Documented { components, config })
+}
+
+fn normalize_architecture(args: &NormalizeArgs) -> Result<()> {
+    let mut config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    config.exclude.extend(args.exclude.iter().cloned());
+
+    let files = find_markdown_files(&args.pattern, &config.exclude, false)?;
+    let mut changed = 0;
+    let mut errors = 0;
+    for file in &files {
+        let content = fs::read_to_string(file).map_err(|source| ComponentError::Io {
+            path: file.clone(),
+            source,
+        })?;
+        let normalized = match normalize_content(&content, file, &config) {
+            Ok(normalized) => normalized,
+            Err(e) => {
+                anstream::eprintln!("{ERROR}Error:{ERROR:#} {:#}", e);
+                errors += 1;
+                continue;
+            }
+        };
+        if normalized == content {
+            continue;
+        }
+
+        changed += 1;
+        if args.dry_run {
+            print_diff(file, &content, &normalized);
+        } else {
+            fs::write(file, normalized)
+                .with_context(|| format!("Failed to write file: {}", file.display()))?;
+        }
+    }
 
+    if errors > 0 {
+        return Err(Failure::Parse(format!("{} file(s) could not be normalized", errors)).into());
+    }
+    if args.dry_run {
+        println!("{} of {} files would change", changed, files.len());
+    } else {
+        println!("Normalized {} of {} files", changed, files.len());
+    }
     Ok(())
 }
 
-fn find_markdown_files(pattern: &str) -> Result<Vec<PathBuf>> {
+/// Prints the change from `old` to `new` as a diff of the lines between
+/// their common leading and trailing lines.
+fn print_diff(file: &Path, old: &str, new: &str) {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    println!("--- {}", file.display());
+    println!("+++ {}", file.display());
+    for line in &old[prefix..old.len() - suffix] {
+        anstream::println!("{REMOVED}-{}{REMOVED:#}", line);
+    }
+    for line in &new[prefix..new.len() - suffix] {
+        anstream::println!("{ADDED}+{}{ADDED:#}", line);
+    }
+}
+
+/// Fails if the config groups the document by something other than category,
+/// which `output` (`--split` or `--format csv`) has no sections for.
+fn check_group_by(config: &Config, output: &str) -> Result<()> {
+    let group_by = match config.group_by {
+        GroupBy::Category => return Ok(()),
+        GroupBy::Release => "release",
+        GroupBy::Owner => "owner",
+    };
+    bail!(
+        "group_by = \"{}\" is not supported with {}; use group_by = \"category\"",
+        group_by,
+        output
+    )
+}
+
+fn render_architecture(args: &RenderArgs) -> Result<()> {
+    let config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+
+    let json = fs::read_to_string(&args.components).with_context(|| {
+        format!(
+            "Failed to read components file: {}",
+            args.components.display()
+        )
+    })?;
+    let components: Vec<Component> = serde_json::from_str(&json).with_context(|| {
+        format!(
+            "Failed to parse components file: {}",
+            args.components.display()
+        )
+    })?;
+
+    if args.format == OutputFormat::Csv {
+        check_group_by(&config, "--format csv")?;
+    } else if args.format == OutputFormat::Json {
+        check_unique_ids(&components, &config)?;
+    }
+
+    for finding in check_heading_offset(&config)
+        .into_iter()
+        .chain(check_dependency_order(&components, &config))
+    {
+        anstream::eprintln!("{WARNING}Warning:{WARNING:#} {}", finding.message);
+    }
+
+    let doc = match args.format {
+        OutputFormat::Markdown => generate_document(&components, &config),
+        OutputFormat::Html => generate_html(&components, &config),
+        OutputFormat::Atom => generate_atom_feed(&components, &config),
+        OutputFormat::Csv => generate_csv(&components, &config),
+        OutputFormat::Llms => generate_llms_txt(&components, &config),
+        OutputFormat::Toc => generate_outline(&components, &config),
+        OutputFormat::Json => generate_json(&components, &config),
+    };
+
+    if let Some(parent) = args.output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&args.output, doc).context("Failed to write output file")
+}
+
+/// Wall time spent in each phase of a generate run, reported by `--timings`.
+#[derive(Default)]
+struct Timings {
+    phases: Vec<(&'static str, Duration)>,
+    files: usize,
+    bytes: usize,
+}
+
+impl Timings {
+    /// Runs `f`, recording its wall time under `phase`.
+    fn time<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    fn record(&mut self, phase: &'static str, elapsed: Duration) {
+        self.phases.push((phase, elapsed));
+    }
+
+    /// Prints the breakdown to stderr.
+    fn report(&self) {
+        eprintln!("Timings:");
+        for (phase, elapsed) in &self.phases {
+            eprintln!("  {:<8} {:>10.3?}", phase, elapsed);
+        }
+        let total: Duration = self.phases.iter().map(|(_, elapsed)| *elapsed).sum();
+        eprintln!("  {:<8} {:>10.3?}", "total", total);
+        eprintln!("Read {} files ({} bytes)", self.files, self.bytes);
+    }
+}
+
+/// Computes the path to `to` relative to the directory `from`.
+fn relative_path(from: &Path, to: &Path) -> Result<PathBuf> {
+    let from = if from.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        from
+    };
+    let from = std::path::absolute(from)?;
+    let to = std::path::absolute(to)?;
+
+    let common = from
+        .components()
+        .zip(to.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative: PathBuf = from.components().skip(common).map(|_| "..").collect();
+    relative.extend(to.components().skip(common));
+    Ok(relative)
+}
+
+fn validate_architecture(args: &ValidateArgs) -> Result<()> {
+    let mut config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    config.exclude.extend(args.exclude.iter().cloned());
+    if let Some(format) = args.front_matter_format {
+        config.front_matter_format = format;
+    }
+    if args.require_descriptions {
+        config.require_front_matter_description = true;
+    }
+
+    let results = parse_sources(&args.pattern, &config, args.respect_gitignore)?;
+    if let Some(file) = &args.emit_stats {
+        let stats = HealthReport::new(results.iter().map(Result::as_ref), &config, 0).stats();
+        write_stats(file, &stats)?;
+    }
+
+    let mut report = ValidationReport::default();
+    let mut parsed = Vec::new();
+    for result in results {
+        report.total_files += 1;
+        match result.and_then(|component| check_component(&component, &config).map(|_| component)) {
+            Ok(component) => {
+                report
+                    .warnings
+                    .extend(check_near_miss_category(&component, &config));
+                parsed.push(component);
+            }
+            Err(e) => report.failures.push(Finding::from_error(&e)),
+        }
+    }
+    report.parsed = parsed.len();
+    report.warnings.extend(check_category_keys(&config));
+    report.warnings.extend(check_heading_offset(&config));
+    report
+        .warnings
+        .extend(check_dependency_order(&parsed, &config));
+    report.warnings.extend(check_related(&parsed));
+
+    if let Some(root) = &args.find_orphans {
+        report.warnings.extend(
+            orphans(&args.pattern, root)?
+                .into_iter()
+                .map(|path| Finding {
+                    message: format!(
+                        "{}: has a category but isn't matched by the pattern",
+                        path.display()
+                    ),
+                    path: Some(path),
+                    kind: "orphan".to_string(),
+                    line: None,
+                }),
+        );
+    }
+
+    if let Some(doc) = &args.verify_links {
+        let doc_dir = doc.parent().unwrap_or(Path::new(""));
+        report
+            .failures
+            .extend(check_links(&parsed, &config, doc_dir));
+    }
+
+    if args.check_cycles {
+        if let Some(cycle) = find_dependency_cycle(&parsed) {
+            report.failures.push(Finding {
+                path: None,
+                kind: "dependency_cycle".to_string(),
+                message: format!("dependency cycle detected: {}", cycle.join(" -> ")),
+                line: None,
+            });
+        }
+    }
+
+    match args.report {
+        ReportFormat::Human => {
+            for warning in &report.warnings {
+                anstream::eprintln!("{WARNING}Warning:{WARNING:#} {}", warning.message);
+            }
+            for failure in &report.failures {
+                anstream::eprintln!("{ERROR}Error:{ERROR:#} {}", failure.message);
+            }
+        }
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if !report.passed() {
+        return Err(Failure::Parse(format!(
+            "Validation failed with {} error(s)",
+            report.failures.len()
+        ))
+        .into());
+    }
+
+    if args.report == ReportFormat::Human {
+        println!("Validated {} components", report.parsed);
+    }
+    Ok(())
+}
+
+/// Prints the files `generate` left out: a count per reason and then each
+/// file's error on stderr, or the report as JSON on stdout.
+fn print_skip_report(report: &SkipReport, format: ReportFormat) -> Result<()> {
+    match format {
+        ReportFormat::Human => {
+            eprintln!(
+                "Skipped {} of {} files",
+                report.skipped.len(),
+                report.total_files
+            );
+            for (reason, count) in &report.reasons {
+                eprintln!("  {:<28} {}", reason, count);
+            }
+            for skipped in &report.skipped {
+                anstream::eprintln!("{WARNING}Skipped:{WARNING:#} {}", skipped.message);
+            }
+        }
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+    }
+    Ok(())
+}
+
+/// Writes documentation coverage stats to `file` as pretty-printed JSON.
+fn write_stats(file: &Path, stats: &DocStats) -> Result<()> {
+    fs::write(file, serde_json::to_string_pretty(stats)?)
+        .with_context(|| format!("Failed to write stats file: {}", file.display()))
+}
+
+fn doctor_architecture(args: &DoctorArgs) -> Result<()> {
+    let mut config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    config.exclude.extend(args.exclude.iter().cloned());
+    if let Some(format) = args.front_matter_format {
+        config.front_matter_format = format;
+    }
+    let mut report = HealthReport::new(
+        parse_sources(&args.pattern, &config, args.respect_gitignore)?,
+        &config,
+        args.min_words,
+    );
+    if let Some(root) = &args.find_orphans {
+        report.orphans = orphans(&args.pattern, root)?;
+    }
+    let error_checks = if args.fail_on.is_empty() {
+        HealthCheck::DEFAULT_ERRORS.to_vec()
+    } else {
+        args.fail_on.clone()
+    };
+    print_health_report(&report, &error_checks);
+
+    let failures = report.failures(&error_checks);
+    if !failures.is_empty() {
+        let names: Vec<_> = failures.iter().map(|c| c.name()).collect();
+        bail!("Doctor found problems: {}", names.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Finds component-like files under `root` that `pattern` doesn't match.
+///
+/// Files skipped by `exclude` patterns count as matched, since excluding
+/// them is deliberate.
+fn orphans(pattern: &str, root: &Path) -> Result<Vec<PathBuf>> {
+    let matched = find_markdown_files(pattern, &[], false)?;
+    Ok(find_orphans(root, &matched))
+}
+
+/// Prints the report like its `Display` form, highlighting checks with
+/// findings: red if they fail the run, yellow otherwise.
+fn print_health_report(report: &HealthReport, error_checks: &[HealthCheck]) {
+    println!("components: {}", report.components);
+    for check in HealthCheck::ALL {
+        let count = report.count(check);
+        let style = match count {
+            0 => Style::new(),
+            _ if error_checks.contains(&check) => ERROR,
+            _ => WARNING,
+        };
+        anstream::println!("{style}{}: {}{style:#}", check, count);
+        for detail in report.details(check) {
+            println!("  {}", detail);
+        }
+    }
+}
+
+fn verify_architecture(args: &VerifyArgs) -> Result<()> {
+    let doc = fs::read_to_string(&args.doc)
+        .with_context(|| format!("Failed to read document: {}", args.doc.display()))?;
+    let base_dir = base_dir_from_pattern(&args.pattern);
+    let pattern = glob::Pattern::new(&args.pattern).context("Failed to read glob pattern")?;
+
+    let mut parsed = Vec::new();
+    let mut unparsed = Vec::new();
+    let mut errors = 0;
+    for result in components(&args.pattern, &base_dir)? {
+        match result {
+            Ok(component) => parsed.push(component),
+            Err(e) => {
+                anstream::eprintln!("{ERROR}Error:{ERROR:#} {:#}", e);
+                if let Some(error) = e.downcast_ref::<ComponentError>() {
+                    let path = error.path();
+                    unparsed.push(path.strip_prefix(&base_dir).unwrap_or(path).to_path_buf());
+                }
+                errors += 1;
+            }
+        }
+    }
+
+    let report = verify_document(&doc, &parsed, &unparsed, |path| {
+        pattern.matches_path(&base_dir.join(path))
+    });
+    for path in &report.missing_from_doc {
+        anstream::eprintln!(
+            "{WARNING}Warning:{WARNING:#} component missing from document: {}",
+            path.display()
+        );
+    }
+    for path in &report.unknown_in_doc {
+        anstream::eprintln!(
+            "{WARNING}Warning:{WARNING:#} document references unknown component: {}",
+            path.display()
+        );
+    }
+    for path in &report.unparsed_in_doc {
+        anstream::eprintln!(
+            "{WARNING}Warning:{WARNING:#} document references component that failed to parse: {}",
+            path.display()
+        );
+    }
+
+    let warnings =
+        report.missing_from_doc.len() + report.unknown_in_doc.len() + report.unparsed_in_doc.len();
+    if errors > 0 || (args.deny_warnings && warnings > 0) {
+        let message = format!(
+            "Verification failed with {} error(s) and {} warning(s)",
+            errors, warnings
+        );
+        return Err(if errors > 0 {
+            Failure::Parse(message)
+        } else {
+            Failure::CheckMismatch(message)
+        }
+        .into());
+    }
+
+    println!(
+        "Verified {} components against {}",
+        parsed.len(),
+        args.doc.display()
+    );
+    Ok(())
+}
+
+/// Parses the files matched by `pattern` and the config's `[[sources]]`, as
+/// `generate` would document them, with an error for each unreadable path.
+fn parse_sources(
+    pattern: &str,
+    config: &Config,
+    respect_gitignore: bool,
+) -> Result<Vec<Result<Component>>> {
+    let globbed = glob_patterns(
+        &with_sources(&[pattern], config),
+        &config.exclude,
+        respect_gitignore,
+        None,
+    )?;
+    Ok(globbed
+        .errors
+        .into_iter()
+        .map(Err)
+        .chain(
+            globbed
+                .files
+                .into_iter()
+                .map(|(file, base_dir)| parse_component_with(file, &base_dir, config)),
+        )
+        .collect())
+}
+
+fn find_markdown_files(
+    pattern: &str,
+    exclude: &[String],
+    respect_gitignore: bool,
+) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
 
-    for entry in glob(pattern).context("Failed to read glob pattern")? {
+    for entry in matching_files_with(pattern, exclude, respect_gitignore)? {
         match entry {
             Ok(path) => files.push(path),
-            Err(e) => eprintln!("Error reading path: {}", e),
+            Err(e) => eprintln!("Error reading path: {:#}", e),
         }
     }
 
     Ok(files)
 }
 
-fn get_base_dir_from_pattern(pattern: &str) -> PathBuf {
-    // Extract the base directory from the glob pattern
-    // e.g., "/path/to/fixtures/**/README.md" -> "/path/to/fixtures/"
-    let path = PathBuf::from(pattern);
+/// Lists the files changed between the git ref `since` and `HEAD`, as
+/// canonical paths, in the repository containing `dir`.
+fn changed_files(since: &str, dir: &Path) -> Result<HashSet<PathBuf>> {
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .context("Failed to run git (is it installed?)")?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let toplevel = git(&["rev-parse", "--show-toplevel"]).with_context(|| {
+        format!(
+            "--since requires a git repository, but {} is not inside one",
+            dir.display()
+        )
+    })?;
+    let toplevel = PathBuf::from(toplevel.trim_end());
+
+    let diff = git(&["diff", "--name-only", &format!("{}..HEAD", since)])
+        .with_context(|| format!("Failed to list files changed since '{}'", since))?;
+
+    // Files deleted since the ref can't be canonicalized, and can't match a glob either
+    Ok(diff
+        .lines()
+        .filter_map(|name| fs::canonicalize(toplevel.join(name)).ok())
+        .collect())
+}
+
+/// Counts the directories between the base dir and the file.
+fn directory_depth(file: &Path, base_dir: &Path) -> usize {
+    file.strip_prefix(base_dir)
+        .unwrap_or(file)
+        .parent()
+        .map_or(0, |parent| parent.components().count())
+}
+
+fn preview_component(args: &PreviewArgs) -> Result<()> {
+    let config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+
+    let mut content = String::new();
+    io::stdin()
+        .read_to_string(&mut content)
+        .context("Failed to read markdown from stdin")?;
+
+    let component = parse_component_content_with(&content, &args.path, Path::new(""), &config)?;
+    print!("{}", render_entry(&component, &config));
+    anstream::println!(
+        "Category: {CATEGORY}{}{CATEGORY:#}",
+        config.canonical_category(&component.category)
+    );
+    println!("Description: {}", component.description.joined());
+
+    Ok(())
+}
+
+fn explain_file(args: &ExplainArgs) -> Result<()> {
+    let config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    let content = fs::read_to_string(&args.path).map_err(|source| ComponentError::Io {
+        path: args.path.clone(),
+        source,
+    })?;
+
+    // Print the trace up to a failing step before reporting the failure
+    let mut trace = String::new();
+    let result = explain_component(&content, &args.path, &config, &mut trace);
+    print!("{}", trace);
+    result.map_err(|e| Failure::Parse(format!("{:#}", e)))?;
 
-    // Find the first component with wildcards
-    let mut base = PathBuf::new();
-    for component in path.components() {
-        let comp_str = component.as_os_str().to_string_lossy();
-        if comp_str.contains('*') || comp_str.contains('?') || comp_str.contains('[') {
-            break;
+    Ok(())
+}
+
+fn list_components(args: &ListArgs) -> Result<()> {
+    let mut config = load_config(args.config.as_deref(), args.profile.as_deref())?;
+    config.exclude.extend(args.exclude.iter().cloned());
+
+    let base_dir = base_dir_from_pattern(&args.pattern);
+    for result in components_with(&args.pattern, &base_dir, &config)? {
+        let component = result.map_err(|e| Failure::Parse(format!("{:#}", e)))?;
+        let category = config.canonical_category(&component.category);
+        if args.show_raw {
+            println!(
+                "{}\t{}\t{}",
+                component.path.display(),
+                component.raw_category.as_deref().unwrap_or("-"),
+                category
+            );
+        } else {
+            println!("{}\t{}", component.path.display(), category);
         }
-        base.push(component);
     }
 
-    base
+    Ok(())
+}
+
+fn watch_architecture(args: &WatchArgs) -> Result<()> {
+    let server = args
+        .serve
+        .map(|port| PreviewServer::bind(&format!("127.0.0.1:{}", port)))
+        .transpose()?;
+
+    thread::scope(|scope| {
+        if let Some(server) = &server {
+            println!(
+                "Serving preview at http://127.0.0.1:{}/",
+                server.port().unwrap_or_default()
+            );
+            scope.spawn(|| {
+                let result = server.serve_forever(|e| {
+                    anstream::eprintln!(
+                        "{WARNING}Warning:{WARNING:#} preview server failed to answer a request: {:#}",
+                        e
+                    )
+                });
+                if let Err(e) = result {
+                    eprintln!("Error: preview server stopped: {:#}", e);
+                }
+            });
+        }
+
+        // The scope waits for the server thread, so stop it before leaving
+        let result = watch_loop(args, server.as_ref());
+        if let Some(server) = &server {
+            server.shutdown();
+        }
+        result
+    })
+}
+
+/// Regenerates the output whenever the watched files change, until checking
+/// for changes fails.
+fn watch_loop(args: &WatchArgs, server: Option<&PreviewServer>) -> Result<()> {
+    let config_file = config_file(args.config.as_deref());
+    let mut last_snapshot = None;
+    loop {
+        let snapshot = watch_snapshot(args, &config_file)?;
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            last_snapshot = Some(snapshot);
+            match regenerate(args, server) {
+                Ok(count) => println!(
+                    "Regenerated {} with {} components",
+                    args.output.display(),
+                    count
+                ),
+                Err(e) => eprintln!("Error: {:#}", e),
+            }
+        }
+        thread::sleep(Duration::from_millis(args.interval));
+    }
+}
+
+/// The files matched by the pattern and the config's `[[sources]]`, and the
+/// config file, with their modification times, compared between polls to
+/// detect changes.
+fn watch_snapshot(
+    args: &WatchArgs,
+    config_file: &Path,
+) -> Result<Vec<(PathBuf, Option<SystemTime>)>> {
+    // A config that fails to load is reported when regenerating
+    let mut config = Config::load_profile(config_file, args.profile.as_deref()).unwrap_or_default();
+    config.exclude.extend(args.exclude.iter().cloned());
+
+    let globbed = glob_patterns(
+        &with_sources(&[&args.pattern], &config),
+        &config.exclude,
+        args.respect_gitignore,
+        None,
+    )?;
+    let mut files: Vec<PathBuf> = globbed.files.into_iter().map(|(file, _)| file).collect();
+    files.push(config_file.to_path_buf());
+
+    Ok(files
+        .into_iter()
+        .map(|file| {
+            let modified = fs::metadata(&file).and_then(|m| m.modified()).ok();
+            (file, modified)
+        })
+        .collect())
+}
+
+/// Runs `generate` as the watch arguments describe, updating the preview
+/// server's page, and returns the number of components documented.
+fn regenerate(args: &WatchArgs, server: Option<&PreviewServer>) -> Result<usize> {
+    let documented = generate_architecture(&GenerateArgs {
+        pattern: args.pattern.clone(),
+        output: args.output.clone(),
+        config: args.config.clone(),
+        profile: args.profile.clone(),
+        exclude: args.exclude.clone(),
+        respect_gitignore: args.respect_gitignore,
+        report: Some(ReportFormat::Human),
+        ..Default::default()
+    })?;
+    if let Some(server) = server {
+        server.update(generate_html(&documented.components, &documented.config));
+    }
+
+    Ok(documented.components.len())
 }
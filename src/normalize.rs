@@ -0,0 +1,171 @@
+//! Rewriting of README front matter into a canonical form.
+//!
+//! Used by the `normalize` subcommand as a migration aid for repositories
+//! whose front matter has drifted: only the front matter block is rewritten,
+//! and the markdown body after it is kept byte for byte.
+
+use anyhow::Result;
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::component::{parse_component_content_with, DescriptionSource};
+use crate::config::{Config, FrontMatterFormat};
+use crate::error::ComponentError;
+use crate::front_matter::{
+    deserialize_front_matter, opening_delimiter, resolve_format, split_front_matter,
+};
+
+/// Alternative spellings of front matter keys and their canonical spelling.
+const KEY_SPELLINGS: [(&str, &str); 4] = [
+    ("descriptionFrom", "description_from"),
+    ("description-from", "description_from"),
+    ("displayTitle", "display_title"),
+    ("display-title", "display_title"),
+];
+
+/// Returns `content` with its front matter rewritten in canonical form.
+///
+/// The canonical form has keys in sorted order and spelled in `snake_case`,
+/// string values trimmed, the category resolved through the config's aliases
+/// and configured spellings, and a `description` filled in from the first
+/// paragraph when the file has none. The front matter keeps its format (YAML
+/// or TOML) and its delimiter character, but not comments or the delimiter
+/// length; everything after the closing delimiter is preserved.
+///
+/// # Errors
+///
+/// Returns an error if `content` does not parse as a component.
+pub fn normalize_content(content: &str, path: &Path, config: &Config) -> Result<String> {
+    let component = parse_component_content_with(content, path, Path::new(""), config)?;
+    let Some((yaml, body)) = split_front_matter(content) else {
+        unreachable!("parsed components have front matter");
+    };
+
+    let format = resolve_format(config.front_matter_format_for(path), content);
+    let mut front_matter: BTreeMap<String, Value> = deserialize_front_matter(yaml, format)
+        .map_err(|e| ComponentError::InvalidFrontMatter {
+            path: path.to_path_buf(),
+            line: e.line.map(|line| line + 1),
+            message: e.message,
+        })?;
+    for (alias, canonical) in KEY_SPELLINGS {
+        if let Some(value) = front_matter.remove(alias) {
+            front_matter.insert(canonical.to_string(), value);
+        }
+    }
+    for value in front_matter.values_mut() {
+        trim_strings(value);
+    }
+
+    front_matter.insert(
+        "category".to_string(),
+        Value::String(config.canonical_category(&component.category).to_string()),
+    );
+    if component.description_source == DescriptionSource::FirstParagraph {
+        front_matter.insert(
+            "description".to_string(),
+            Value::String(component.description.joined()),
+        );
+    }
+
+    let text = match format {
+        FrontMatterFormat::Toml => toml::to_string(&front_matter)?,
+        _ => serde_yaml::to_string(&front_matter)?,
+    };
+    let delimiter = if opening_delimiter(content) == Some(b'+') {
+        "+++"
+    } else {
+        "---"
+    };
+    Ok(format!("{0}\n{1}{0}\n{2}", delimiter, text, body))
+}
+
+/// Trims surrounding whitespace from every string in `value`.
+fn trim_strings(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = s.trim().to_string(),
+        Value::Sequence(items) => items.iter_mut().for_each(trim_strings),
+        Value::Mapping(map) => map.values_mut().for_each(trim_strings),
+        Value::Tagged(tagged) => trim_strings(&mut tagged.value),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "\n# Core  \n\nCore utilities.\r\n\n- keep   this\n";
+
+    fn normalize(front_matter: &str, config: &Config) -> String {
+        let content = format!("---\n{}---\n{}", front_matter, BODY);
+        normalize_content(&content, Path::new("core/README.md"), config).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_category_alias_and_padding() {
+        let config: Config = toml::from_str(
+            "[aliases]\nutils = \"Utilities\"\n\n[[categories]]\ncategory = \"Utilities\"",
+        )
+        .unwrap();
+        let normalized = normalize(
+            "description: \"  Core lib \"\ncategory: \"  utils  \"\n",
+            &config,
+        );
+        assert_eq!(
+            normalized,
+            format!(
+                "---\ncategory: Utilities\ndescription: Core lib\n---\n{}",
+                BODY
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_sorts_keys_and_fills_description() {
+        let normalized = normalize(
+            "tags: [\" async \"]\ndisplayTitle: Core\ncategory: Core\n",
+            &Config::default(),
+        );
+        assert_eq!(
+            normalized,
+            format!(
+                "---\ncategory: Core\ndescription: Core utilities.\ndisplay_title: Core\n\
+                 tags:\n- async\n---\n{}",
+                BODY
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() {
+        let once = normalize(
+            "category: \" Core \"\nowner: platform\n",
+            &Config::default(),
+        );
+        let twice =
+            normalize_content(&once, Path::new("core/README.md"), &Config::default()).unwrap();
+        assert_eq!(once, twice);
+        assert!(twice.ends_with(BODY));
+    }
+
+    #[test]
+    fn test_normalize_keeps_toml_front_matter() {
+        let content = format!("+++\ncategory = \" Core \"\ntags = [\"a\"]\n+++\n{}", BODY);
+        let normalized =
+            normalize_content(&content, Path::new("core/README.md"), &Config::default()).unwrap();
+        assert_eq!(
+            normalized,
+            format!(
+                "+++\ncategory = \"Core\"\ndescription = \"Core utilities.\"\ntags = [\"a\"]\n+++\n{}",
+                BODY
+            )
+        );
+    }
+
+    #[test]
+    fn test_normalize_requires_front_matter() {
+        assert!(normalize_content("# Core\n", Path::new("README.md"), &Config::default()).is_err());
+    }
+}
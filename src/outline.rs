@@ -0,0 +1,122 @@
+//! Table of contents on its own, for embedding in a hand-maintained document.
+//!
+//! The outline lists the same sections as the full document's `toc`, in the
+//! same order and with the same anchors, but leaves out the entries.
+
+use std::fmt::Write;
+
+use crate::component::Component;
+use crate::config::Config;
+use crate::generator::{
+    category_anchor, category_sections, flat_sections, group_by_category, heading, order_categories,
+};
+use crate::slug::slugify;
+
+/// Generates the document title followed by a nested bulleted outline of its
+/// sections, each linked to its heading and followed by its component count.
+///
+/// A parent category counts the components of its subcategories too. With
+/// `group_by = "release"` or `"owner"`, the outline lists the release cohorts
+/// or owners instead.
+pub fn generate_outline(components: &[Component], config: &Config) -> String {
+    let mut outline = format!("{} {}\n", heading(1, config), config.title());
+    if components.is_empty() {
+        return outline;
+    }
+    outline.push('\n');
+
+    if let Some(sections) = flat_sections(components, config) {
+        for (title, comps) in sections {
+            writeln!(
+                outline,
+                "- [{}](#{}) ({})",
+                title,
+                slugify(&title),
+                comps.len()
+            )
+            .unwrap();
+        }
+        return outline;
+    }
+
+    let grouped = group_by_category(components, config);
+    for (depth, category) in category_sections(&order_categories(&grouped, config), config) {
+        let segments = config.category_segments(&category);
+        let count: usize = grouped
+            .iter()
+            .filter(|(name, _)| config.category_segments(name).starts_with(&segments))
+            .map(|(_, comps)| comps.len())
+            .sum();
+        writeln!(
+            outline,
+            "{}- [{}](#{}) ({})",
+            "  ".repeat(depth - 1),
+            config.display_title_for(&category),
+            category_anchor(&category, config),
+            count
+        )
+        .unwrap();
+    }
+
+    outline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn components_in(categories: &[&str]) -> Vec<Component> {
+        categories
+            .iter()
+            .enumerate()
+            .map(|(i, category)| Component {
+                path: PathBuf::from(format!("{}/README.md", i)),
+                description: format!("Description {}", i).into(),
+                category: category.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_outline_follows_resolved_order() {
+        let config: Config = toml::from_str(
+            r#"
+title = "Platform"
+description = "Everything we run."
+
+[[categories]]
+category = "Services"
+title = "Backend Services"
+"#,
+        )
+        .unwrap();
+        let components = components_in(&["Core", "Services", "Core", "CLI"]);
+
+        assert_eq!(
+            generate_outline(&components, &config),
+            "# Platform\n\n\
+             - [Backend Services](#backend-services) (1)\n\
+             - [CLI](#cli) (1)\n\
+             - [Core](#core) (2)\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_outline_nests_subcategories() {
+        let components = components_in(&["Backend/Storage", "Backend/Api", "Backend/Api", "Web"]);
+
+        let config: Config = toml::from_str("category_separator = \"/\"").unwrap();
+        let outline = generate_outline(&components, &config);
+        assert_eq!(
+            outline,
+            "# Architecture Documentation\n\n\
+             - [Backend](#backend) (3)\n  \
+             - [Api](#backend-api) (2)\n  \
+             - [Storage](#backend-storage) (1)\n\
+             - [Web](#web) (1)\n"
+        );
+        assert!(!outline.contains("Description"));
+    }
+}
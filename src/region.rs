@@ -0,0 +1,124 @@
+//! Managed regions: a generated section inside an otherwise hand-written file.
+//!
+//! The region is everything between a [`REGION_BEGIN`] line and the next
+//! [`REGION_END`] line. Regenerating replaces only the lines between them, so
+//! prose around the markers, and the markers themselves, are kept as written.
+
+use anyhow::{bail, Result};
+
+/// Comment line opening the managed region.
+pub const REGION_BEGIN: &str = "<!-- ARCH:BEGIN -->";
+
+/// Comment line closing the managed region.
+pub const REGION_END: &str = "<!-- ARCH:END -->";
+
+/// Returns `existing` with the lines between its region markers replaced by
+/// `generated`.
+///
+/// Fails if `existing` has no begin marker, no end marker after it, or more
+/// than one region.
+pub fn replace_managed_region(existing: &str, generated: &str) -> Result<String> {
+    let guidance = format!(
+        "add a '{}' line and a '{}' line around the generated section, \
+         or drop --managed-region to overwrite the whole file",
+        REGION_BEGIN, REGION_END
+    );
+    let Some(begin) = marker_line(existing, REGION_BEGIN, 0) else {
+        bail!("No managed region found: {}", guidance);
+    };
+    let Some(end) = marker_line(existing, REGION_END, begin) else {
+        bail!("Managed region is not closed: {}", guidance);
+    };
+    if marker_line(existing, REGION_BEGIN, end).is_some() {
+        bail!("Found more than one managed region; only one is supported");
+    }
+
+    // Keep the begin marker's line; the end marker's line follows it, so it
+    // ends in a newline
+    let after_begin = begin + existing[begin..].find('\n').map_or(0, |i| i + 1);
+    let mut merged = existing[..after_begin].to_string();
+    let generated = generated.trim_matches('\n');
+    if !generated.is_empty() {
+        merged.push('\n');
+        merged.push_str(generated);
+        merged.push_str("\n\n");
+    }
+    merged.push_str(&existing[end..]);
+    Ok(merged)
+}
+
+/// Returns the document without its leading title heading, if it starts with one.
+pub fn strip_title(doc: &str) -> &str {
+    if !doc.starts_with('#') {
+        return doc;
+    }
+    doc.split_once('\n').map_or("", |(_, rest)| rest)
+}
+
+/// Byte offset of the first line at or after `from` that is exactly `marker`,
+/// ignoring surrounding whitespace.
+fn marker_line(text: &str, marker: &str, from: usize) -> Option<usize> {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if offset >= from && line.trim() == marker {
+            return Some(offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_managed_region_preserves_surroundings() {
+        let existing = "# Platform\n\nHand-written intro.\n\n<!-- ARCH:BEGIN -->\nstale\n\
+                        <!-- ARCH:END -->\n\n## Appendix\n\nCurated notes.\n";
+
+        assert_eq!(
+            replace_managed_region(existing, "## Core\n\n- `core/README.md`: Core\n").unwrap(),
+            "# Platform\n\nHand-written intro.\n\n<!-- ARCH:BEGIN -->\n\n## Core\n\n\
+             - `core/README.md`: Core\n\n<!-- ARCH:END -->\n\n## Appendix\n\nCurated notes.\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_managed_region_is_idempotent() {
+        let existing = "Intro\n<!-- ARCH:BEGIN -->\n<!-- ARCH:END -->\nOutro";
+        let once = replace_managed_region(existing, "Generated\n").unwrap();
+        assert_eq!(
+            once,
+            "Intro\n<!-- ARCH:BEGIN -->\n\nGenerated\n\n<!-- ARCH:END -->\nOutro"
+        );
+        assert_eq!(replace_managed_region(&once, "Generated\n").unwrap(), once);
+    }
+
+    #[test]
+    fn test_replace_managed_region_requires_markers() {
+        let err = replace_managed_region("# Notes\n", "x").unwrap_err();
+        assert!(err.to_string().contains("No managed region"), "{}", err);
+
+        let err = replace_managed_region("<!-- ARCH:BEGIN -->\nx\n", "x").unwrap_err();
+        assert!(err.to_string().contains("not closed"), "{}", err);
+
+        // An end marker before the begin marker doesn't close it
+        let err =
+            replace_managed_region("<!-- ARCH:END -->\n<!-- ARCH:BEGIN -->\n", "x").unwrap_err();
+        assert!(err.to_string().contains("not closed"), "{}", err);
+
+        let twice = "<!-- ARCH:BEGIN -->\n<!-- ARCH:END -->\n".repeat(2);
+        assert!(replace_managed_region(&twice, "x").is_err());
+    }
+
+    #[test]
+    fn test_strip_title() {
+        assert_eq!(strip_title("# Architecture\n\n## Core\n"), "\n## Core\n");
+        assert_eq!(
+            strip_title("<!-- generated -->\n# Title\n"),
+            "<!-- generated -->\n# Title\n"
+        );
+        assert_eq!(strip_title("# Architecture"), "");
+    }
+}
@@ -0,0 +1,230 @@
+//! A minimal HTTP server previewing the generated HTML with auto-reload.
+//!
+//! The served page polls `/version` and reloads itself whenever the page is
+//! replaced via [`PreviewServer::update`], so a browser left open follows
+//! each regeneration in watch mode.
+
+use anyhow::{anyhow, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use tiny_http::{Header, Request, Response, Server};
+
+/// How often the served page checks for a new version, in milliseconds.
+const RELOAD_INTERVAL_MS: u64 = 1000;
+
+/// A preview server serving the latest generated HTML page.
+pub struct PreviewServer {
+    server: Server,
+    page: RwLock<Page>,
+    stopped: AtomicBool,
+}
+
+/// The current page and a version bumped on every update.
+#[derive(Default)]
+struct Page {
+    html: String,
+    version: u64,
+}
+
+impl PreviewServer {
+    /// Binds the server to `addr`, e.g. `127.0.0.1:8000`, initially serving an empty page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be bound.
+    pub fn bind(addr: &str) -> Result<Self> {
+        let server = Server::http(addr)
+            .map_err(|e| anyhow!("Failed to start preview server on {}: {}", addr, e))?;
+        Ok(PreviewServer {
+            server,
+            page: RwLock::default(),
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    /// The port the server is listening on.
+    pub fn port(&self) -> Option<u16> {
+        self.server.server_addr().to_ip().map(|addr| addr.port())
+    }
+
+    /// Replaces the served page, prompting open browsers to reload.
+    pub fn update(&self, html: String) {
+        let mut page = self.page.write().unwrap();
+        page.html = html;
+        page.version += 1;
+    }
+
+    /// Blocks until a request arrives and answers it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if receiving the request or writing the response fails.
+    pub fn handle_one(&self) -> Result<()> {
+        let request = self.server.recv()?;
+        self.respond(request)
+    }
+
+    /// Answers requests until [`shutdown`](Self::shutdown) is called or
+    /// receiving one fails.
+    ///
+    /// Failing to answer a request, as when a browser tab closes before the
+    /// response is written, is passed to `on_error` and doesn't stop the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if receiving a request fails.
+    pub fn serve_forever(&self, mut on_error: impl FnMut(anyhow::Error)) -> Result<()> {
+        while !self.stopped.load(Ordering::SeqCst) {
+            let request = match self.server.recv() {
+                Ok(request) => request,
+                Err(_) if self.stopped.load(Ordering::SeqCst) => break,
+                Err(e) => return Err(e.into()),
+            };
+            if let Err(e) = self.respond(request) {
+                on_error(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes [`serve_forever`](Self::serve_forever) return, once it is done
+    /// with any request it is answering.
+    pub fn shutdown(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.server.unblock();
+    }
+
+    fn respond(&self, request: Request) -> Result<()> {
+        let page = self.page.read().unwrap();
+        let (body, content_type) = if request.url() == "/version" {
+            (page.version.to_string(), "text/plain; charset=utf-8")
+        } else {
+            (
+                with_reload_script(&page.html, page.version),
+                "text/html; charset=utf-8",
+            )
+        };
+        drop(page);
+
+        let header = Header::from_bytes("Content-Type", content_type)
+            .map_err(|_| anyhow!("Invalid Content-Type header"))?;
+        request.respond(Response::from_string(body).with_header(header))?;
+        Ok(())
+    }
+}
+
+/// Inserts the auto-reload script before `</body>`, or appends it if there is none.
+fn with_reload_script(html: &str, version: u64) -> String {
+    let script = format!(
+        "<script>\n\
+         setInterval(function () {{\n  \
+           fetch(\"/version\").then(function (r) {{ return r.text(); }}).then(function (v) {{\n    \
+             if (v !== \"{}\") {{ location.reload(); }}\n  \
+           }});\n\
+         }}, {});\n\
+         </script>\n",
+        version, RELOAD_INTERVAL_MS
+    );
+
+    match html.rfind("</body>") {
+        Some(end) => format!("{}{}{}", &html[..end], script, &html[end..]),
+        None => format!("{}{}", html, script),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::config::Config;
+    use crate::html::generate_html;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    fn get(port: u16, path: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serves_page_with_reload_script() {
+        let server = PreviewServer::bind("127.0.0.1:0").unwrap();
+        let port = server.port().unwrap();
+        let components = vec![Component {
+            path: "core/README.md".into(),
+            description: "Core lib".into(),
+            category: "Utilities".to_string(),
+            ..Default::default()
+        }];
+        server.update(generate_html(&components, &Config::default()));
+
+        thread::scope(|scope| {
+            scope.spawn(|| server.handle_one().unwrap());
+            let response = get(port, "/");
+            assert!(response.starts_with("HTTP/1.1 200"));
+            assert!(response.contains("text/html"));
+            assert!(response.contains("<h2>Utilities</h2>"));
+            assert!(response.contains("if (v !== \"1\")"));
+            assert!(response.ends_with("</script>\n</body>\n</html>\n"));
+        });
+    }
+
+    #[test]
+    fn test_serves_version() {
+        let server = PreviewServer::bind("127.0.0.1:0").unwrap();
+        let port = server.port().unwrap();
+        server.update(String::new());
+        server.update(String::new());
+
+        thread::scope(|scope| {
+            scope.spawn(|| server.handle_one().unwrap());
+            assert!(get(port, "/version").ends_with("\r\n\r\n2"));
+        });
+    }
+
+    #[test]
+    fn test_shutdown_stops_serve_forever() {
+        let server = PreviewServer::bind("127.0.0.1:0").unwrap();
+        thread::scope(|scope| {
+            let serving = scope.spawn(|| server.serve_forever(|e| panic!("{e}")));
+            server.shutdown();
+            assert!(serving.join().unwrap().is_ok());
+        });
+    }
+
+    #[test]
+    fn test_serve_forever_survives_disconnected_client() {
+        let server = PreviewServer::bind("127.0.0.1:0").unwrap();
+        let port = server.port().unwrap();
+        // Large enough that writing it outlasts the socket buffers
+        server.update("x".repeat(16 << 20));
+
+        thread::scope(|scope| {
+            let serving = scope.spawn(|| server.serve_forever(|_| {}));
+
+            // A client that goes away in the middle of its response
+            let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            write!(stream, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            thread::sleep(std::time::Duration::from_millis(100));
+            drop(stream);
+
+            assert!(get(port, "/version").ends_with("\r\n\r\n1"));
+            server.shutdown();
+            assert!(serving.join().unwrap().is_ok());
+        });
+    }
+
+    #[test]
+    fn test_with_reload_script_without_body() {
+        assert!(with_reload_script("<p>hi</p>", 0).starts_with("<p>hi</p><script>"));
+    }
+}
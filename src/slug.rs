@@ -0,0 +1,41 @@
+//! Slug generation for file names and anchors.
+
+/// Converts text into a lowercase, hyphen-separated slug.
+///
+/// Alphanumeric characters are kept (lowercased) and every run of other
+/// characters becomes a single `-`, with no leading or trailing hyphens.
+pub(crate) fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_simple() {
+        assert_eq!(slugify("Core Utilities"), "core-utilities");
+    }
+
+    #[test]
+    fn test_slugify_collapses_punctuation() {
+        assert_eq!(slugify("  Backend / Storage!! "), "backend-storage");
+    }
+
+    #[test]
+    fn test_slugify_keeps_unicode_alphanumerics() {
+        assert_eq!(slugify("Über_Module2"), "über-module2");
+    }
+}
@@ -0,0 +1,310 @@
+//! Split output: an index page plus one page per category.
+//!
+//! Each category page is written next to the index as `<category-slug>.md`,
+//! with `-2`, `-3` and so on appended when categories slug the same, and ends
+//! with a footer linking to the previous and next categories (in the
+//! resolved category order) and back to the index.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::component::Component;
+use crate::config::Config;
+use crate::generator::{
+    category_anchor, category_heading, entry_anchor, group_by_category, heading, order_categories,
+    render_entry_in, RelatedLinks,
+};
+use crate::slug::slugify;
+
+/// A document split into an index and per-category pages.
+#[derive(Debug, Default, PartialEq)]
+pub struct SplitDocument {
+    /// The index page, linking to every category page.
+    pub index: String,
+    /// Category pages as (file name relative to the index, content), in category order.
+    pub pages: Vec<(PathBuf, String)>,
+}
+
+/// Returns the file name of a category's page, relative to the index.
+pub fn category_page_path(category: &str) -> PathBuf {
+    PathBuf::from(format!("{}.md", slugify(category)))
+}
+
+/// The page file names of `categories`, in category order: each category's
+/// [`category_page_path`], unless an earlier category took it, in which case
+/// the first free `-2`, `-3` and so on is appended to the slug.
+fn page_paths<'a>(categories: &[&'a str]) -> HashMap<&'a str, PathBuf> {
+    let mut taken = HashSet::new();
+    categories
+        .iter()
+        .map(|&category| {
+            let slug = slugify(category);
+            let mut name = slug.clone();
+            let mut suffix = 2;
+            while !taken.insert(name.clone()) {
+                name = format!("{}-{}", slug, suffix);
+                suffix += 1;
+            }
+            (category, PathBuf::from(format!("{}.md", name)))
+        })
+        .collect()
+}
+
+/// Returns the file name of a category's page among `pages`.
+fn page_path(category: &str, pages: &HashMap<&str, PathBuf>) -> PathBuf {
+    pages
+        .get(category)
+        .cloned()
+        .unwrap_or_else(|| category_page_path(category))
+}
+
+/// Returns the link target of a category's page, pointing at the page's
+/// heading anchor when `explicit_anchors` is set.
+fn category_page_link(category: &str, pages: &HashMap<&str, PathBuf>, config: &Config) -> String {
+    let page = page_path(category, pages).display().to_string();
+    if config.explicit_anchors {
+        format!("{}#{}", page, category_anchor(category, config))
+    } else {
+        page
+    }
+}
+
+/// Generates an index and one page per category.
+///
+/// `index_name` is the index's file name, used for the "back to index" link
+/// in each page's footer.
+pub fn generate_split(
+    components: &[Component],
+    config: &Config,
+    index_name: &str,
+) -> SplitDocument {
+    let grouped = group_by_category(components, config);
+    let categories = order_categories(&grouped, config);
+    let paths = page_paths(&categories);
+
+    let mut index = format!("{} {}\n", heading(1, config), config.title());
+    if let Some(desc) = &config.description {
+        writeln!(index, "\n{}", desc.trim_end()).unwrap();
+    }
+    if !categories.is_empty() {
+        index.push('\n');
+    }
+    for category in &categories {
+        writeln!(
+            index,
+            "- [{}]({}) ({})",
+            config.display_title_for(category),
+            category_page_link(category, &paths, config),
+            grouped[*category].len()
+        )
+        .unwrap();
+    }
+
+    let links = RelatedLinks::new(components);
+    let pages = categories
+        .iter()
+        .enumerate()
+        .map(|(i, category)| {
+            let mut page = format!("{}\n", category_heading(1, category, config));
+            if let Some(desc) = config
+                .get_category(category)
+                .and_then(|c| c.description.as_deref())
+            {
+                writeln!(page, "\n{}", desc.trim_end()).unwrap();
+            }
+
+            page.push('\n');
+            for comp in &grouped[*category] {
+                page.push_str(&render_entry_in(comp, &links, config, |target| {
+                    format!(
+                        "{}#{}",
+                        page_path(config.canonical_category(&target.category), &paths).display(),
+                        entry_anchor(target)
+                    )
+                }));
+            }
+
+            let previous = i.checked_sub(1).map(|i| categories[i]);
+            let next = categories.get(i + 1).copied();
+            page.push_str(&navigation_footer(
+                previous, next, index_name, &paths, config,
+            ));
+
+            (page_path(category, &paths), page)
+        })
+        .collect();
+
+    SplitDocument { index, pages }
+}
+
+/// Renders the footer linking to adjacent category pages and the index.
+fn navigation_footer(
+    previous: Option<&str>,
+    next: Option<&str>,
+    index_name: &str,
+    pages: &HashMap<&str, PathBuf>,
+    config: &Config,
+) -> String {
+    let link = |category: &str| {
+        format!(
+            "[{}]({})",
+            config.display_title_for(category),
+            category_page_link(category, pages, config)
+        )
+    };
+
+    let mut links = Vec::new();
+    if let Some(previous) = previous {
+        links.push(format!("← Previous: {}", link(previous)));
+    }
+    links.push(format!("[Index]({})", index_name));
+    if let Some(next) = next {
+        links.push(format!("Next: {} →", link(next)));
+    }
+
+    format!("\n---\n\n{}\n", links.join(" | "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split() -> SplitDocument {
+        split_with("")
+    }
+
+    fn split_with(options: &str) -> SplitDocument {
+        let config: Config = toml::from_str(&format!(
+            r#"{}
+
+[[categories]]
+category = "Core Utilities"
+
+[[categories]]
+category = "Services"
+
+[[categories]]
+category = "Interfaces"
+title = "User Interfaces"
+"#,
+            options
+        ))
+        .unwrap();
+        let components: Vec<Component> = ["Interfaces", "Services", "Core Utilities"]
+            .iter()
+            .map(|category| Component {
+                path: PathBuf::from(format!("{}/README.md", slugify(category))),
+                description: category.to_string().into(),
+                category: category.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        generate_split(&components, &config, "ARCHITECTURE.md")
+    }
+
+    #[test]
+    fn test_generate_split_index() {
+        assert_eq!(
+            split().index,
+            "# Architecture Documentation\n\n\
+             - [Core Utilities](core-utilities.md) (1)\n\
+             - [Services](services.md) (1)\n\
+             - [User Interfaces](interfaces.md) (1)\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_split_middle_page_links() {
+        let split = split();
+        let (path, page) = &split.pages[1];
+        assert_eq!(path, &PathBuf::from("services.md"));
+        assert!(page.starts_with("# Services\n\n- `services/README.md`: Services\n"));
+        assert!(page.ends_with(
+            "\n---\n\n← Previous: [Core Utilities](core-utilities.md) | \
+             [Index](ARCHITECTURE.md) | Next: [User Interfaces](interfaces.md) →\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_split_end_pages_omit_links() {
+        let split = split();
+        let (_, first) = &split.pages[0];
+        assert!(!first.contains("Previous:"));
+        assert!(first.ends_with("[Index](ARCHITECTURE.md) | Next: [Services](services.md) →\n"));
+
+        let (_, last) = &split.pages[2];
+        assert!(!last.contains("Next:"));
+        assert!(last.ends_with("← Previous: [Services](services.md) | [Index](ARCHITECTURE.md)\n"));
+    }
+
+    #[test]
+    fn test_generate_split_disambiguates_colliding_slugs() {
+        let components: Vec<Component> = ["C++", "C", "C--"]
+            .iter()
+            .enumerate()
+            .map(|(i, category)| Component {
+                path: PathBuf::from(format!("{}/README.md", i)),
+                description: "Desc".into(),
+                category: category.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        let split = generate_split(&components, &Config::default(), "ARCHITECTURE.md");
+        let paths: Vec<&PathBuf> = split.pages.iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, vec!["c.md", "c-2.md", "c-3.md"]);
+        assert_eq!(
+            split.index,
+            "# Architecture Documentation\n\n\
+             - [C](c.md) (1)\n\
+             - [C++](c-2.md) (1)\n\
+             - [C--](c-3.md) (1)\n"
+        );
+        assert!(split.pages[1].1.ends_with(
+            "← Previous: [C](c.md) | [Index](ARCHITECTURE.md) | Next: [C--](c-3.md) →\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_split_explicit_anchors() {
+        let split = split_with("explicit_anchors = true");
+        assert!(split
+            .index
+            .contains("- [Services](services.md#services) (1)\n"));
+
+        let (_, page) = &split.pages[1];
+        assert!(page.starts_with("<a name=\"services\"></a>\n# Services\n"));
+        assert!(page.contains("[Core Utilities](core-utilities.md#core-utilities)"));
+        assert!(page.contains("[User Interfaces](interfaces.md#user-interfaces)"));
+    }
+
+    #[test]
+    fn test_generate_split_related_links_to_other_page() {
+        let config: Config = toml::from_str("explicit_anchors = true").unwrap();
+        let components = vec![
+            Component {
+                path: PathBuf::from("cache/README.md"),
+                description: "Caching".into(),
+                category: "Core".to_string(),
+                related: vec!["store".to_string()],
+                ..Default::default()
+            },
+            Component {
+                path: PathBuf::from("store/README.md"),
+                description: "Storage".into(),
+                category: "Services".to_string(),
+                ..Default::default()
+            },
+        ];
+        let split = generate_split(&components, &config, "ARCHITECTURE.md");
+
+        assert!(split.pages[0]
+            .1
+            .contains("  See also: [store](services.md#component-store)\n"));
+        assert!(split.pages[1]
+            .1
+            .contains("- <a name=\"component-store\"></a>`store/README.md`: Storage\n"));
+    }
+}
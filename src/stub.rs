@@ -0,0 +1,91 @@
+//! Per-component stub page generation.
+//!
+//! Stub pages give each component its own markdown page, laid out as
+//! `<category-slug>/<component-slug>.md` under an output directory, so a
+//! docs site can link from the index to a page per component.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::component::Component;
+use crate::config::Config;
+use crate::slug::slugify;
+
+/// Returns the path of a component's stub page, relative to the output directory.
+pub fn stub_path(component: &Component) -> PathBuf {
+    PathBuf::from(slugify(&component.category)).join(format!("{}.md", slugify(&component.name())))
+}
+
+/// Generates the stub page for a single component.
+///
+/// The page contains the component's display title as its title, a category breadcrumb,
+/// the description, and a link to the source README at `source` (relative to
+/// the stub page's directory).
+pub fn generate_stub(component: &Component, config: &Config, source: &Path) -> String {
+    let title = component.title();
+    let mut page = format!("# {}\n", title);
+
+    writeln!(
+        page,
+        "\n{} › {}",
+        config.display_title_for(&component.category),
+        title
+    )
+    .unwrap();
+    for line in component.description.lines() {
+        writeln!(page, "\n{}", line).unwrap();
+    }
+    writeln!(
+        page,
+        "\nSource: [{}]({})",
+        component.path.display(),
+        source.display()
+    )
+    .unwrap();
+
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component() -> Component {
+        Component {
+            path: PathBuf::from("crates/core/README.md"),
+            description: "Core utilities".into(),
+            category: "Core Systems".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_stub_path() {
+        assert_eq!(
+            stub_path(&component()),
+            PathBuf::from("core-systems/core.md")
+        );
+    }
+
+    #[test]
+    fn test_generate_stub() {
+        let config: Config = toml::from_str(
+            r#"
+[[categories]]
+category = "Core Systems"
+title = "Core"
+"#,
+        )
+        .unwrap();
+
+        let page = generate_stub(
+            &component(),
+            &config,
+            Path::new("../../crates/core/README.md"),
+        );
+        assert_eq!(
+            page,
+            "# core\n\nCore › core\n\nCore utilities\n\nSource: [crates/core/README.md](../../crates/core/README.md)\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Flat CSV table of components, for review in a spreadsheet.
+//!
+//! Rows follow the document's structure: categories in the configured order,
+//! components within each category in entry order.
+
+use itertools::Itertools;
+
+use crate::component::Component;
+use crate::config::Config;
+use crate::generator::{entry_description, group_by_category, is_shown, order_categories};
+
+/// Generates a CSV table with a header row and one row per component.
+///
+/// The columns are `category`, `path` and `description`, followed by `tags`
+/// (joined with `;`) if any component has tags and `status` if any component
+/// has a status. Categories use their display titles, and multi-line
+/// descriptions are joined into one line.
+pub fn generate_csv(components: &[Component], config: &Config) -> String {
+    let mut shown = components.iter().filter(|c| is_shown(c, config));
+    let with_tags = shown.clone().any(|c| !c.tags.is_empty());
+    let with_status = shown.any(|c| c.status.is_some());
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut header = vec!["category", "path", "description"];
+    if with_tags {
+        header.push("tags");
+    }
+    if with_status {
+        header.push("status");
+    }
+    writer.write_record(header).expect("writing to memory");
+
+    let grouped = group_by_category(components, config);
+    for category in order_categories(&grouped, config) {
+        for comp in &grouped[category] {
+            let mut row = vec![
+                config.display_title_for(category).to_string(),
+                comp.path.display().to_string(),
+                entry_description(comp, config).joined(),
+            ];
+            if with_tags {
+                row.push(comp.tags.iter().join(";"));
+            }
+            if with_status {
+                row.push(comp.status.clone().unwrap_or_default());
+            }
+            writer.write_record(row).expect("writing to memory");
+        }
+    }
+
+    let bytes = writer.into_inner().expect("writing to memory");
+    String::from_utf8(bytes).expect("CSV built from strings is UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn component(path: &str, description: &str, category: &str) -> Component {
+        Component {
+            path: PathBuf::from(path),
+            description: description.into(),
+            category: category.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_csv_escapes_commas_and_quotes() {
+        let components = vec![
+            component("core/README.md", "Parses, validates, and stores", "Core"),
+            component("cli/README.md", "The \"main\" entry point", "Interfaces"),
+        ];
+
+        assert_eq!(
+            generate_csv(&components, &Config::default()),
+            "category,path,description\n\
+             Core,core/README.md,\"Parses, validates, and stores\"\n\
+             Interfaces,cli/README.md,\"The \"\"main\"\" entry point\"\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_csv_follows_document_order() {
+        let config: Config =
+            toml::from_str("[[categories]]\ncategory = \"Services\"\ntitle = \"Backend Services\"")
+                .unwrap();
+        let components = vec![
+            component("b/README.md", "B", "Core"),
+            component("a/README.md", "A", "Core"),
+            component("s/README.md", "S", "Services"),
+        ];
+
+        let csv = generate_csv(&components, &config);
+        let rows: Vec<&str> = csv.lines().skip(1).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "Backend Services,s/README.md,S",
+                "Core,a/README.md,A",
+                "Core,b/README.md,B",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_csv_optional_columns() {
+        let components = vec![
+            Component {
+                tags: vec!["async".to_string(), "io".to_string()],
+                status: Some("beta".to_string()),
+                ..component("net/README.md", "Networking", "Core")
+            },
+            component("fs/README.md", "Files", "Core"),
+        ];
+
+        assert_eq!(
+            generate_csv(&components, &Config::default()),
+            "category,path,description,tags,status\n\
+             Core,fs/README.md,Files,,\n\
+             Core,net/README.md,Networking,async;io,beta\n"
+        );
+    }
+}
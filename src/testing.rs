@@ -0,0 +1,54 @@
+//! In-process rendering of fixture directories for golden tests.
+//!
+//! Enabled by the `testing` feature. [`render_to_string`] generates a
+//! fixture's document the way `generate` does, without starting a process
+//! per fixture.
+
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::collect::Collector;
+use crate::config::Config;
+use crate::discovery::{glob_patterns, with_sources};
+use crate::generator::generate_document;
+
+/// Renders the document for the components under `fixture_dir`.
+///
+/// Does what `generate "<fixture_dir>/**/README.md"` does, with
+/// `--config <fixture_dir>/architecture.toml` if that file exists: the
+/// config's `[[sources]]` are matched too, files matching its `exclude`
+/// patterns are skipped, component paths are relative to `fixture_dir`, and
+/// files that can't be read or parsed are left out. Both share the same
+/// [`Collector`] steps.
+///
+/// # Errors
+///
+/// Returns an error if the config can't be loaded, a matched path can't be
+/// walked, or a component fails a check such as `strict_categories`.
+pub fn render_to_string(fixture_dir: &Path) -> Result<String> {
+    let config = Config::load(&fixture_dir.join("architecture.toml"))?;
+    let pattern = fixture_dir.join("**/README.md").display().to_string();
+
+    let globbed = glob_patterns(
+        &with_sources(&[&pattern], &config),
+        &config.exclude,
+        false,
+        None,
+    )?;
+    if let Some(err) = globbed.errors.into_iter().next() {
+        return Err(err);
+    }
+
+    let collected = Collector::read(globbed.files).parse(&config, |_| {});
+    if let Some(err) = collected.error {
+        return Err(err);
+    }
+    let components: Vec<_> = collected
+        .components
+        .into_iter()
+        .map(|(component, _)| component)
+        .collect();
+
+    Ok(generate_document(&components, &config))
+}
@@ -0,0 +1,670 @@
+//! Validation of parsed components against the config.
+
+use anyhow::Result;
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::component::{Component, DescriptionSource};
+use crate::config::{Config, SortOrder, CATEGORY_KEYS};
+use crate::dependencies::sort_by_dependencies;
+use crate::error::ComponentError;
+use crate::generator::{entry_link, group_by_category, RelatedLinks, DEEPEST_HEADING};
+
+/// Checks a parsed component against the config's validation rules.
+///
+/// With `strict_categories` enabled, the component's category (after alias
+/// resolution) must be declared in `[[categories]]`. A component in a
+/// category with `require_description` must take its description from the
+/// front matter `description` field, and with
+/// `require_front_matter_description` no component may take it from its
+/// first paragraph.
+///
+/// # Errors
+///
+/// Returns a [`ComponentError`] naming the component's path if a check fails.
+pub fn check_component(component: &Component, config: &Config) -> Result<()> {
+    if config.strict_categories && !config.is_known_category(&component.category) {
+        return Err(ComponentError::UnknownCategory {
+            path: component.path.clone(),
+            category: component.category.clone(),
+            suggestion: closest_category(&component.category, config).map(str::to_string),
+        }
+        .into());
+    }
+
+    if config.require_front_matter_description
+        && component.description_source == DescriptionSource::FirstParagraph
+    {
+        return Err(ComponentError::FallbackDescription {
+            path: component.path.clone(),
+        }
+        .into());
+    }
+
+    let category = config.canonical_category(&component.category);
+    let requires_description = config
+        .get_category(category)
+        .is_some_and(|c| c.require_description);
+    if requires_description && component.description_source != DescriptionSource::FrontMatter {
+        return Err(ComponentError::DescriptionRequired {
+            path: component.path.clone(),
+            category: category.to_string(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Finds the configured category that `category` is a near miss of: one it
+/// doesn't match exactly but equals ignoring case and whitespace, or is
+/// within an edit distance of a quarter of its length. Names under four
+/// characters only near-miss by case and whitespace, as they are a couple of
+/// edits away from most short names.
+pub fn near_miss_category<'a>(category: &str, config: &'a Config) -> Option<&'a str> {
+    if config.is_known_category(category) {
+        return None;
+    }
+    let name = normalize_category(config.canonical_category(category));
+    let configured = closest_category(&name, config)?;
+    let distance = levenshtein(&name, &normalize_category(configured));
+    (distance <= name.chars().count() / 4).then_some(configured)
+}
+
+/// Warns when a component's category is a near miss of a configured one.
+///
+/// These categories would otherwise render as a separate, near-duplicate section.
+pub fn check_near_miss_category(component: &Component, config: &Config) -> Option<Finding> {
+    let configured = near_miss_category(&component.category, config)?;
+    Some(Finding {
+        path: Some(component.path.clone()),
+        kind: "near_miss_category".to_string(),
+        message: format!(
+            "{}: {}",
+            component.path.display(),
+            near_miss_message(&component.category, configured)
+        ),
+        line: None,
+    })
+}
+
+/// Checks that each component's entry link, as rendered with `config`,
+/// resolves to a file, taking links relative to `doc_dir`, the directory of
+/// the generated document.
+///
+/// Links with a URL scheme, absolute links and `#anchor` links are not
+/// checked, and a link's `#fragment` or `?query` is ignored.
+pub fn check_links(components: &[Component], config: &Config, doc_dir: &Path) -> Vec<Finding> {
+    components
+        .iter()
+        .filter_map(|component| {
+            let link = entry_link(component, config)?;
+            let link = link.as_str();
+            let target = link.split(['#', '?']).next().unwrap_or_default();
+            if target.is_empty() || target.starts_with('/') || target.contains(':') {
+                return None;
+            }
+            if doc_dir.join(target).exists() {
+                return None;
+            }
+            Some(Finding {
+                path: Some(component.path.clone()),
+                kind: "broken_link".to_string(),
+                message: format!(
+                    "{}: link '{}' does not resolve to a file",
+                    component.path.display(),
+                    link
+                ),
+                line: None,
+            })
+        })
+        .collect()
+}
+
+/// Checks that each component's `related` references name another of
+/// `components`, by name, README path or directory.
+pub fn check_related(components: &[Component]) -> Vec<Finding> {
+    let links = RelatedLinks::new(components);
+    components
+        .iter()
+        .flat_map(|component| {
+            component
+                .related
+                .iter()
+                .filter(|id| links.resolve(id).is_none())
+                .map(|id| Finding {
+                    path: Some(component.path.clone()),
+                    kind: "unresolved_related".to_string(),
+                    message: format!(
+                        "{}: related component '{}' not found",
+                        component.path.display(),
+                        id
+                    ),
+                    line: None,
+                })
+        })
+        .collect()
+}
+
+/// Checks the extra keys of each `[[categories]]` entry for near misses of the
+/// keys it supports, like `titel` for `title`, which would otherwise be kept
+/// as template values without taking effect.
+pub fn check_category_keys(config: &Config) -> Vec<Finding> {
+    config
+        .categories
+        .iter()
+        .flat_map(|category| {
+            category.extra.keys().filter_map(move |key| {
+                let known = CATEGORY_KEYS.iter().find(|known| {
+                    let max = if known.len() >= 5 { 2 } else { 1 };
+                    levenshtein(key, known) <= max
+                })?;
+                Some(Finding {
+                    path: None,
+                    kind: "near_miss_category_key".to_string(),
+                    message: format!(
+                        "category '{}': key '{}' is close to '{}' — did you mean it?",
+                        category.category, key, known
+                    ),
+                    line: None,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Checks that `heading_offset` leaves room for every heading the document
+/// emits; deeper ones are clamped to `######`.
+pub fn check_heading_offset(config: &Config) -> Option<Finding> {
+    (config.heading_offset + DEEPEST_HEADING > 6).then(|| Finding {
+        path: None,
+        kind: "heading_offset_clamped".to_string(),
+        message: format!(
+            "heading_offset {} shifts headings past level 6, clamping them to ######",
+            config.heading_offset
+        ),
+        line: None,
+    })
+}
+
+/// Checks each category sorted by `dependencies` for a dependency cycle,
+/// which leaves the category in path order.
+pub fn check_dependency_order(components: &[Component], config: &Config) -> Vec<Finding> {
+    group_by_category(components, config)
+        .into_iter()
+        .filter(|(category, comps)| {
+            config.sort_for(category) == SortOrder::Dependencies
+                && sort_by_dependencies(comps).is_none()
+        })
+        .map(|(category, _)| Finding {
+            path: None,
+            kind: "dependency_cycle".to_string(),
+            message: format!(
+                "dependency cycle in category '{}', falling back to path order",
+                category
+            ),
+            line: None,
+        })
+        .collect()
+}
+
+/// The message for a category that is a near miss of a configured one.
+pub(crate) fn near_miss_message(category: &str, configured: &str) -> String {
+    format!(
+        "category '{}' is close to configured '{}' — did you mean it?",
+        category.trim(),
+        configured
+    )
+}
+
+/// The outcome of validating a set of component files.
+#[derive(Debug, Default, Serialize)]
+pub struct ValidationReport {
+    /// Number of files matched by the pattern.
+    pub total_files: usize,
+    /// Number of files that parsed and passed all checks.
+    pub parsed: usize,
+    /// Problems that fail validation.
+    pub failures: Vec<Finding>,
+    /// Problems that are reported but do not fail validation.
+    pub warnings: Vec<Finding>,
+}
+
+impl ValidationReport {
+    /// Whether validation passed, i.e. there are no failures.
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Files matched for a document but left out of it, and why.
+#[derive(Debug, Default, Serialize)]
+pub struct SkipReport {
+    /// Number of files matched by the pattern.
+    pub total_files: usize,
+    /// Number of files that parsed and passed all checks, before any
+    /// `--only` or tag filters.
+    pub parsed: usize,
+    /// Number of skipped files per reason, the kind of their finding.
+    pub reasons: BTreeMap<String, usize>,
+    /// One finding per skipped file.
+    pub skipped: Vec<Finding>,
+}
+
+impl SkipReport {
+    /// Records a skipped file, counting it under its finding's kind.
+    pub fn skip(&mut self, finding: Finding) {
+        *self.reasons.entry(finding.kind.clone()).or_default() += 1;
+        self.skipped.push(finding);
+    }
+}
+
+/// A single validation problem.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    /// The component file the problem is about, if it concerns a single file.
+    pub path: Option<PathBuf>,
+    /// Machine-friendly kind, e.g. `invalid_front_matter`.
+    pub kind: String,
+    /// Human-readable message, including the error's causes.
+    pub message: String,
+    /// Line in the file the problem refers to, if known.
+    pub line: Option<usize>,
+}
+
+impl Finding {
+    /// Builds a finding from an error, using its [`ComponentError`] details
+    /// when available and the generic kind `error` otherwise.
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        let component_error = err.downcast_ref::<ComponentError>();
+        Finding {
+            path: component_error.map(|e| e.path().to_path_buf()),
+            kind: component_error.map_or("error", |e| e.kind()).to_string(),
+            message: format!("{:#}", err),
+            line: component_error.and_then(|e| e.line()),
+        }
+    }
+}
+
+/// Finds the configured category closest to `name`, if any is close enough
+/// to be a plausible typo.
+///
+/// Names are compared ignoring case and runs of whitespace, so a case-only
+/// difference is always close enough.
+fn closest_category<'a>(name: &str, config: &'a Config) -> Option<&'a str> {
+    let name = normalize_category(name);
+    let max_distance = (name.chars().count() / 3).max(2);
+
+    config
+        .category_order()
+        .into_iter()
+        .map(|category| (levenshtein(&name, &normalize_category(category)), category))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, category)| category)
+}
+
+/// Lowercases a category name and collapses its whitespace to single spaces.
+fn normalize_category(name: &str) -> String {
+    name.split_whitespace().join(" ").to_lowercase()
+}
+
+/// Computes the Levenshtein edit distance between two strings.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn strict_config() -> Config {
+        toml::from_str(
+            r#"
+strict_categories = true
+
+[[categories]]
+category = "Utilities"
+
+[[categories]]
+category = "Services"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn component(category: &str) -> Component {
+        Component {
+            path: PathBuf::from("crates/core/README.md"),
+            description: "Core".into(),
+            category: category.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_check_component_known_category() {
+        assert!(check_component(&component("Utilities"), &strict_config()).is_ok());
+    }
+
+    #[test]
+    fn test_check_component_unknown_category_with_suggestion() {
+        let err = check_component(&component("Utilites"), &strict_config()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown category 'Utilites' in: crates/core/README.md (did you mean 'Utilities'?)"
+        );
+    }
+
+    #[test]
+    fn test_check_component_unknown_category_without_suggestion() {
+        let err = check_component(&component("Frontend"), &strict_config()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown category 'Frontend' in: crates/core/README.md"
+        );
+    }
+
+    #[test]
+    fn test_finding_from_component_error() {
+        let err = check_component(&component("Frontend"), &strict_config()).unwrap_err();
+        assert_eq!(
+            Finding::from_error(&err),
+            Finding {
+                path: Some(PathBuf::from("crates/core/README.md")),
+                kind: "unknown_category".to_string(),
+                message: "Unknown category 'Frontend' in: crates/core/README.md".to_string(),
+                line: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_finding_from_other_error() {
+        let finding = Finding::from_error(&anyhow::anyhow!("Something else"));
+        assert_eq!(finding.kind, "error");
+        assert_eq!(finding.path, None);
+    }
+
+    #[test]
+    fn test_near_miss_category_case_only() {
+        let config: Config = toml::from_str("[[categories]]\ncategory = \"CLI\"").unwrap();
+        let finding = check_near_miss_category(&component("Cli"), &config).unwrap();
+        assert_eq!(finding.kind, "near_miss_category");
+        assert_eq!(
+            finding.message,
+            "crates/core/README.md: category 'Cli' is close to configured 'CLI' — did you mean it?"
+        );
+    }
+
+    #[test]
+    fn test_near_miss_category_whitespace() {
+        let config: Config =
+            toml::from_str("[[categories]]\ncategory = \"Core Utilities\"").unwrap();
+        assert_eq!(
+            near_miss_category("core   utilities", &config),
+            Some("Core Utilities")
+        );
+    }
+
+    #[test]
+    fn test_near_miss_category_single_typo() {
+        assert_eq!(
+            near_miss_category("Servces", &strict_config()),
+            Some("Services")
+        );
+    }
+
+    #[test]
+    fn test_near_miss_category_exact_or_distant() {
+        assert_eq!(near_miss_category("Services", &strict_config()), None);
+        assert_eq!(near_miss_category("Frontend", &strict_config()), None);
+        assert_eq!(near_miss_category("Cli", &Config::default()), None);
+
+        // Short names are a couple of edits from each other without being typos
+        let config: Config = toml::from_str("[[categories]]\ncategory = \"CLI\"").unwrap();
+        assert_eq!(near_miss_category("UI", &config), None);
+        assert_eq!(near_miss_category("ui ", &config), None);
+
+        let config: Config = toml::from_str(
+            "case_insensitive_categories = true\n[[categories]]\ncategory = \"CLI\"",
+        )
+        .unwrap();
+        assert_eq!(near_miss_category("Cli", &config), None);
+    }
+
+    #[test]
+    fn test_check_component_require_description() {
+        let config: Config = toml::from_str(
+            r#"
+[aliases]
+api = "Services"
+
+[[categories]]
+category = "Services"
+require_description = true
+
+[[categories]]
+category = "Utilities"
+"#,
+        )
+        .unwrap();
+        let fallback = |category: &str| Component {
+            description_source: DescriptionSource::FirstParagraph,
+            ..component(category)
+        };
+
+        let err = check_component(&fallback("api"), &config).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Category 'Services' requires a description in front matter: crates/core/README.md"
+        );
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("description_required")
+        );
+        assert!(check_component(&component("Services"), &config).is_ok());
+        assert!(check_component(&fallback("Utilities"), &config).is_ok());
+    }
+
+    #[test]
+    fn test_check_component_require_front_matter_description() {
+        let config: Config = toml::from_str("require_front_matter_description = true").unwrap();
+        let with_source = |description_source| Component {
+            description_source,
+            ..component("Utilities")
+        };
+
+        let err =
+            check_component(&with_source(DescriptionSource::FirstParagraph), &config).unwrap_err();
+        assert!(
+            err.to_string().ends_with(": crates/core/README.md"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            err.downcast_ref::<ComponentError>().map(|e| e.kind()),
+            Some("fallback_description")
+        );
+        assert!(check_component(&with_source(DescriptionSource::FrontMatter), &config).is_ok());
+        assert!(check_component(
+            &with_source(DescriptionSource::FirstParagraph),
+            &Config::default()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_skip_report_tallies_reasons() {
+        let mut report = SkipReport::default();
+        for (path, error) in [
+            ("a/README.md", "no_front_matter"),
+            ("b/README.md", "no_description"),
+            ("c/README.md", "no_front_matter"),
+        ] {
+            report.skip(Finding {
+                path: Some(PathBuf::from(path)),
+                kind: error.to_string(),
+                message: format!("{}: {}", path, error),
+                line: None,
+            });
+        }
+
+        assert_eq!(report.skipped.len(), 3);
+        assert_eq!(
+            report.reasons,
+            BTreeMap::from([
+                ("no_description".to_string(), 1),
+                ("no_front_matter".to_string(), 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_check_component_not_strict() {
+        assert!(check_component(&component("Frontend"), &Config::default()).is_ok());
+    }
+
+    #[test]
+    fn test_check_links() {
+        let dir = std::env::temp_dir().join("rust-arch-check-links");
+        std::fs::create_dir_all(dir.join("core")).unwrap();
+        std::fs::write(dir.join("core/README.md"), "").unwrap();
+
+        let linked = |link: &str| Component {
+            path: PathBuf::from(link),
+            link: Some(link.to_string()),
+            ..component("Utilities")
+        };
+        let components = vec![
+            linked("core/README.md#usage"),
+            linked("gone/README.md"),
+            linked("https://example.com/docs"),
+            linked("#utilities"),
+            component("Utilities"),
+        ];
+
+        let findings = check_links(&components, &Config::default(), &dir);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, "broken_link");
+        assert_eq!(
+            findings[0].message,
+            "gone/README.md: link 'gone/README.md' does not resolve to a file"
+        );
+
+        // A path shown as its directory links to the file itself
+        let config: Config = toml::from_str("strip_filename = \"README.md\"").unwrap();
+        let at = |path: &str| Component {
+            path: PathBuf::from(path),
+            ..component("Utilities")
+        };
+        let findings = check_links(&[at("core/README.md"), at("lost/README.md")], &config, &dir);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "lost/README.md: link 'lost/README.md' does not resolve to a file"
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_check_related() {
+        let components = vec![
+            Component {
+                path: PathBuf::from("cache/README.md"),
+                related: vec!["store".to_string(), "missing".to_string()],
+                ..component("Utilities")
+            },
+            Component {
+                path: PathBuf::from("store/README.md"),
+                ..component("Utilities")
+            },
+        ];
+
+        let findings = check_related(&components);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, "unresolved_related");
+        assert_eq!(
+            findings[0].message,
+            "cache/README.md: related component 'missing' not found"
+        );
+    }
+
+    #[test]
+    fn test_check_category_keys() {
+        let config: Config = toml::from_str(
+            "[[categories]]\ncategory = \"Core\"\ntitel = \"Core Systems\"\nteam = \"platform\"\n\n\
+             [[categories]]\ncategory = \"Tools\"\npinn = \"top\"",
+        )
+        .unwrap();
+
+        let findings = check_category_keys(&config);
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "category 'Core': key 'titel' is close to 'title' — did you mean it?",
+                "category 'Tools': key 'pinn' is close to 'pin' — did you mean it?",
+            ]
+        );
+        assert!(findings.iter().all(|f| f.kind == "near_miss_category_key"));
+    }
+
+    #[test]
+    fn test_check_heading_offset() {
+        let config: Config = toml::from_str("heading_offset = 4").unwrap();
+        assert!(check_heading_offset(&config).is_none());
+
+        let config: Config = toml::from_str("heading_offset = 5").unwrap();
+        assert_eq!(
+            check_heading_offset(&config).unwrap().message,
+            "heading_offset 5 shifts headings past level 6, clamping them to ######"
+        );
+    }
+
+    #[test]
+    fn test_check_dependency_order() {
+        let component = |name: &str, dependency: &str| Component {
+            path: PathBuf::from(format!("{name}/README.md")),
+            category: "Test".to_string(),
+            dependencies: vec![dependency.to_string()],
+            ..Default::default()
+        };
+        let components = vec![component("a", "b"), component("b", "a")];
+
+        assert!(check_dependency_order(&components, &Config::default()).is_empty());
+
+        let config: Config = toml::from_str(r#"sort = "dependencies""#).unwrap();
+        let messages: Vec<String> = check_dependency_order(&components, &config)
+            .into_iter()
+            .map(|f| f.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["dependency cycle in category 'Test', falling back to path order"]
+        );
+    }
+}
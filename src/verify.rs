@@ -0,0 +1,170 @@
+//! Cross-checking a hand-written architecture document against components.
+//!
+//! Teams that maintain their architecture document by hand can still verify
+//! that it mentions every component and only components that exist. Paths are
+//! taken from inline code spans and link targets in the document.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::component::Component;
+
+/// Differences between a hand-written document and the parsed components.
+#[derive(Debug, Default, PartialEq)]
+pub struct VerifyReport {
+    /// Component paths that the document never mentions.
+    pub missing_from_doc: Vec<PathBuf>,
+    /// Paths the document mentions that are not parsed components.
+    pub unknown_in_doc: Vec<PathBuf>,
+    /// Paths the document mentions whose files matched but failed to parse.
+    pub unparsed_in_doc: Vec<PathBuf>,
+}
+
+impl VerifyReport {
+    /// Whether the document and the components agree.
+    pub fn is_clean(&self) -> bool {
+        self.missing_from_doc.is_empty()
+            && self.unknown_in_doc.is_empty()
+            && self.unparsed_in_doc.is_empty()
+    }
+}
+
+/// Compares the paths referenced in `doc` with the components' paths.
+///
+/// Only references for which `is_component_path` returns true (typically
+/// those matching the component glob) are considered, so links to other
+/// files in the document are not reported as unknown. References to
+/// `unparsed` paths, files that matched but failed to parse, are reported
+/// apart from unknown ones.
+pub fn verify_document(
+    doc: &str,
+    components: &[Component],
+    unparsed: &[PathBuf],
+    is_component_path: impl Fn(&Path) -> bool,
+) -> VerifyReport {
+    let referenced: BTreeSet<PathBuf> = referenced_paths(doc)
+        .into_iter()
+        .filter(|path| is_component_path(path))
+        .collect();
+    let known: BTreeSet<&Path> = components.iter().map(|c| c.path.as_path()).collect();
+    let unparsed: BTreeSet<&Path> = unparsed.iter().map(PathBuf::as_path).collect();
+
+    VerifyReport {
+        missing_from_doc: known
+            .iter()
+            .filter(|path| !referenced.contains(**path))
+            .map(|path| path.to_path_buf())
+            .collect(),
+        unknown_in_doc: referenced
+            .iter()
+            .filter(|path| !known.contains(path.as_path()) && !unparsed.contains(path.as_path()))
+            .cloned()
+            .collect(),
+        unparsed_in_doc: referenced
+            .iter()
+            .filter(|path| unparsed.contains(path.as_path()))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Collects the contents of inline code spans and link targets in markdown.
+///
+/// Link targets have any `#fragment` and leading `./` removed.
+fn referenced_paths(doc: &str) -> BTreeSet<PathBuf> {
+    let mut paths = BTreeSet::new();
+
+    for line in doc.lines() {
+        for span in line.split('`').skip(1).step_by(2) {
+            if !span.trim().is_empty() {
+                paths.insert(PathBuf::from(span.trim()));
+            }
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find("](") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find(')') else {
+                break;
+            };
+            let target = after[..end].split('#').next().unwrap_or_default().trim();
+            let target = target.strip_prefix("./").unwrap_or(target);
+            if !target.is_empty() {
+                paths.insert(PathBuf::from(target));
+            }
+            rest = &after[end + 1..];
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(path: &str) -> Component {
+        Component {
+            path: PathBuf::from(path),
+            ..Default::default()
+        }
+    }
+
+    fn is_readme(path: &Path) -> bool {
+        path.file_name().is_some_and(|name| name == "README.md")
+    }
+
+    #[test]
+    fn test_referenced_paths() {
+        let doc = "- `a/README.md`: A\n- [B](./b/README.md#usage) and [`c`](c/README.md)\n";
+        let paths: Vec<_> = referenced_paths(doc).into_iter().collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("a/README.md"),
+                PathBuf::from("b/README.md"),
+                PathBuf::from("c"),
+                PathBuf::from("c/README.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_document_clean() {
+        let doc =
+            "# Architecture\n\n- `a/README.md`: A\n- [B](b/README.md)\n- See [docs](guide.md)\n";
+        let components = vec![component("a/README.md"), component("b/README.md")];
+
+        assert!(verify_document(doc, &components, &[], is_readme).is_clean());
+    }
+
+    #[test]
+    fn test_verify_document_mismatches() {
+        let doc = "- `a/README.md`: A\n- `gone/README.md`: Gone\n";
+        let components = vec![component("a/README.md"), component("b/README.md")];
+
+        assert_eq!(
+            verify_document(doc, &components, &[], is_readme),
+            VerifyReport {
+                missing_from_doc: vec![PathBuf::from("b/README.md")],
+                unknown_in_doc: vec![PathBuf::from("gone/README.md")],
+                unparsed_in_doc: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_document_unparsed() {
+        let doc = "- `a/README.md`: A\n- `broken/README.md`: Broken\n";
+        let components = vec![component("a/README.md")];
+        let unparsed = vec![PathBuf::from("broken/README.md")];
+
+        assert_eq!(
+            verify_document(doc, &components, &unparsed, is_readme),
+            VerifyReport {
+                unparsed_in_doc: vec![PathBuf::from("broken/README.md")],
+                ..Default::default()
+            }
+        );
+    }
+}
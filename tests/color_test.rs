@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn get_fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// Runs `validate` on a fixture with a parse error, so an error is printed.
+fn run_validate(global_args: &[&str], envs: &[(&str, &str)]) -> Output {
+    let pattern = get_fixtures_dir().join("doctor-mixed/**/README.md");
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_rust-architecture"));
+    command
+        .args(global_args)
+        .arg("validate")
+        .arg(pattern.display().to_string())
+        .env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .envs(envs.iter().copied());
+    command.output().expect("Failed to execute command")
+}
+
+fn has_color(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stderr).contains("\x1b[")
+}
+
+#[test]
+fn test_color_always() {
+    let output = run_validate(&["--color", "always"], &[]);
+    assert!(!output.status.success());
+    assert!(has_color(&output));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("\x1b[1m\x1b[31mError:"));
+}
+
+#[test]
+fn test_color_never() {
+    let output = run_validate(&["--color", "never"], &[]);
+    assert!(!output.status.success());
+    assert!(!has_color(&output));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Error: "));
+
+    let output = run_validate(&["--color", "always", "--no-color"], &[]);
+    assert!(!has_color(&output));
+}
+
+#[test]
+fn test_color_auto_without_tty() {
+    // Output is captured, so stderr isn't a terminal
+    let output = run_validate(&[], &[]);
+    assert!(!output.status.success());
+    assert!(!has_color(&output));
+}
+
+#[test]
+fn test_color_auto_honors_no_color() {
+    // CLICOLOR_FORCE stands in for a terminal, which NO_COLOR still overrides
+    let output = run_validate(&["--color", "auto"], &[("CLICOLOR_FORCE", "1")]);
+    assert!(has_color(&output));
+
+    let output = run_validate(
+        &["--color", "auto"],
+        &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")],
+    );
+    assert!(!has_color(&output));
+}
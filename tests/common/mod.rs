@@ -0,0 +1,24 @@
+//! Helpers shared by the CLI tests.
+
+// Each test binary uses only some of the helpers
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+
+pub fn get_fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// An empty scratch directory for the test called `name`, unique to the test
+/// binary so test files can reuse names.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "rust-arch-{}-{}",
+        env!("CARGO_CRATE_NAME").trim_end_matches("_test"),
+        name
+    ));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
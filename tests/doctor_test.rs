@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn get_fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn run_doctor(fixture_name: &str, extra_args: &[&str]) -> Output {
+    let fixture_path = get_fixtures_dir().join(fixture_name);
+    let pattern = fixture_path.join("**/README.md");
+    let config = fixture_path.join("architecture.toml");
+
+    Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("doctor")
+        .arg(pattern.display().to_string())
+        .arg("--config")
+        .arg(config)
+        .args(extra_args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_doctor_reports_metrics() {
+    let output = run_doctor("doctor-mixed", &[]);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let metrics: Vec<&str> = stdout.lines().filter(|l| !l.starts_with(' ')).collect();
+    assert_eq!(
+        metrics,
+        vec![
+            "components: 3",
+            "parse-errors: 1",
+            "fallback-descriptions: 1",
+            "short-descriptions: 1",
+            "unknown-categories: 1",
+            "near-miss-categories: 0",
+            "duplicate-paths: 0",
+            "orphans: 0",
+        ]
+    );
+    assert!(stdout.contains("fallback-descriptions: 1\n  crates/api/README.md\n"));
+    assert!(stdout.contains("short-descriptions: 1\n  crates/cli/README.md\n"));
+    assert!(stdout.contains("unknown-categories: 1\n  'Interfaces': crates/cli/README.md\n"));
+
+    // Parse errors fail the run by default
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("parse-errors"));
+}
+
+#[test]
+fn test_doctor_fail_on_configures_error_checks() {
+    let output = run_doctor("doctor-mixed", &["--fail-on", "duplicate-paths"]);
+    assert!(output.status.success());
+
+    let output = run_doctor("doctor-mixed", &["--fail-on", "short-descriptions"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("short-descriptions"));
+}
+
+#[test]
+fn test_doctor_find_orphans() {
+    let dir = std::env::temp_dir().join("rust-arch-doctor-orphans");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(dir.join("crates/core")).unwrap();
+    fs::create_dir_all(dir.join("tools/lint")).unwrap();
+    let readme = "---\ncategory: Core\ndescription: A component with enough words\n---\n";
+    fs::write(dir.join("crates/core/README.md"), readme).unwrap();
+    fs::write(dir.join("tools/lint/README.md"), readme).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("doctor")
+        .arg(dir.join("crates/**/README.md").display().to_string())
+        .arg("--find-orphans")
+        .arg(&dir)
+        .args(["--fail-on", "orphans"])
+        .output()
+        .expect("Failed to execute command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("components: 1\n"));
+    assert!(
+        stdout.contains(&format!(
+            "orphans: 1\n  {}\n",
+            dir.join("tools/lint/README.md").display()
+        )),
+        "{}",
+        stdout
+    );
+    assert!(!output.status.success());
+
+    fs::remove_dir_all(&dir).ok();
+}
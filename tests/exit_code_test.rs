@@ -0,0 +1,122 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::{get_fixtures_dir, temp_dir};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+fn generate_args(fixture_name: &str, output: &Path) -> Vec<String> {
+    let pattern = get_fixtures_dir().join(fixture_name).join("**/README.md");
+    vec![
+        "generate".to_string(),
+        pattern.display().to_string(),
+        output.display().to_string(),
+    ]
+}
+
+fn run_generate(fixture_name: &str, output: &Path, extra_args: &[&str]) -> Output {
+    let mut args = generate_args(fixture_name, output);
+    args.extend(extra_args.iter().map(|arg| arg.to_string()));
+    run(&args.iter().map(String::as_str).collect::<Vec<_>>())
+}
+
+#[test]
+fn test_exit_code_success() {
+    let output_path = temp_dir("success").join("ARCHITECTURE.md");
+    let output = run_generate("single-file", &output_path, &[]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_exit_code_strict_parse_failure() {
+    let output_path = temp_dir("strict").join("ARCHITECTURE.md");
+    let config = get_fixtures_dir().join("strict-categories/architecture.toml");
+    let output = run_generate(
+        "strict-categories",
+        &output_path,
+        &["--config", config.to_str().unwrap()],
+    );
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown category"));
+}
+
+#[test]
+fn test_exit_code_strict_empty_category() {
+    let dir = temp_dir("strict-empty");
+    fs::create_dir_all(dir.join("core")).unwrap();
+    fs::write(
+        dir.join("core/README.md"),
+        "---\ncategory: \"\"\ndescription: Core\n---\n",
+    )
+    .unwrap();
+    fs::write(dir.join("architecture.toml"), "strict_categories = true\n").unwrap();
+
+    let output = run(&[
+        "generate",
+        &dir.join("**/README.md").display().to_string(),
+        &dir.join("ARCHITECTURE.md").display().to_string(),
+        "--config",
+        &dir.join("architecture.toml").display().to_string(),
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Empty category"));
+    assert!(!dir.join("ARCHITECTURE.md").exists());
+}
+
+#[test]
+fn test_exit_code_check_mismatch() {
+    let output_path = temp_dir("check").join("ARCHITECTURE.md");
+
+    let output = run_generate("multiple-categories", &output_path, &["--check"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(!output_path.exists());
+
+    run_generate("multiple-categories", &output_path, &[]);
+    let output = run_generate("multiple-categories", &output_path, &["--check"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    fs::write(&output_path, "# Stale\n").unwrap();
+    let output = run_generate("multiple-categories", &output_path, &["--check"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Output is out of date"));
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), "# Stale\n");
+}
+
+#[test]
+fn test_exit_code_fail_on_empty() {
+    let output_path = temp_dir("empty").join("ARCHITECTURE.md");
+
+    let output = run_generate("empty-dir", &output_path, &[]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_generate("empty-dir", &output_path, &["--fail-on-empty"]);
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn test_json_errors() {
+    let output_path = temp_dir("json-errors").join("ARCHITECTURE.md");
+    let mut args = vec!["--json-errors".to_string()];
+    args.extend(generate_args("multiple-categories", &output_path));
+    args.push("--check".to_string());
+
+    let output = run(&args.iter().map(String::as_str).collect::<Vec<_>>());
+    assert_eq!(output.status.code(), Some(3));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(error["code"], 3);
+    assert!(error["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Output is out of date: "));
+}
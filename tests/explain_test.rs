@@ -0,0 +1,63 @@
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn test_explain_names_description_source() {
+    let dir = std::env::temp_dir().join("rust-arch-explain");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(dir.join("core")).unwrap();
+    fs::write(
+        dir.join("core/README.md"),
+        "---\ncategory: \"Core\"\n---\n\n# Core\n\nParses input.\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["explain", "core/README.md"])
+        .current_dir(&dir)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "explain failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Front matter: yaml between `---` lines\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Description source: first_paragraph\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.ends_with("Entry:\n- `core/README.md`: Parses input.\n"),
+        "{}",
+        stdout
+    );
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_explain_parse_failure() {
+    let dir = std::env::temp_dir().join("rust-arch-explain-failure");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("README.md"), "---\ncategory: [\n---\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["explain", "README.md"])
+        .current_dir(&dir)
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(2));
+    // The raw front matter is still shown
+    assert!(String::from_utf8_lossy(&output.stdout).contains("  category: [\n"));
+
+    fs::remove_dir_all(&dir).ok();
+}
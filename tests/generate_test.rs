@@ -0,0 +1,1306 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use common::{get_fixtures_dir, temp_dir};
+
+fn run_generate(fixture_name: &str, output: &Path, extra_args: &[&str]) -> Output {
+    let pattern = get_fixtures_dir().join(fixture_name).join("**/README.md");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(pattern.display().to_string())
+        .arg(output)
+        .args(extra_args)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Generate command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn test_generate_output_dir_stubs() {
+    let temp_dir = temp_dir("output-dir");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let stubs_dir = temp_dir.join("components");
+
+    run_generate(
+        "multiple-categories",
+        &output_path,
+        &["--output-dir", stubs_dir.to_str().unwrap()],
+    );
+
+    let mut stubs: Vec<String> = glob::glob(&format!("{}/**/*.md", stubs_dir.display()))
+        .unwrap()
+        .map(|p| {
+            p.unwrap()
+                .strip_prefix(&stubs_dir)
+                .unwrap()
+                .display()
+                .to_string()
+        })
+        .collect();
+    stubs.sort();
+    assert_eq!(
+        stubs,
+        vec!["interfaces/cli.md", "services/api.md", "utilities/core.md"]
+    );
+
+    let core = fs::read_to_string(stubs_dir.join("utilities/core.md")).unwrap();
+    assert!(core.contains("Utilities › core"));
+    assert!(core.contains("Core utilities for the project"));
+    let api = fs::read_to_string(stubs_dir.join("services/api.md")).unwrap();
+    assert!(api.contains("Services › api"));
+    let cli = fs::read_to_string(stubs_dir.join("interfaces/cli.md")).unwrap();
+    assert!(cli.contains("Interfaces › cli"));
+
+    let index = fs::read_to_string(&output_path).unwrap();
+    assert!(index.contains("(components/utilities/core.md)"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_output_dir_stub_collision() {
+    let temp_dir = temp_dir("output-dir-collision");
+    for dir in ["services/core", "tools/core"] {
+        fs::create_dir_all(temp_dir.join(dir)).unwrap();
+        fs::write(
+            temp_dir.join(dir).join("README.md"),
+            "---\ncategory: \"Core\"\n---\n\nComponent.\n",
+        )
+        .unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args([
+            "generate",
+            "**/README.md",
+            "ARCHITECTURE.md",
+            "--output-dir",
+            "stubs",
+        ])
+        .current_dir(&temp_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("services/core/README.md and tools/core/README.md would both be written"),
+        "{}",
+        stderr
+    );
+    assert!(!temp_dir.join("stubs").exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_timings_report() {
+    let temp_dir = temp_dir("timings");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+
+    let output = run_generate("multiple-categories", &output_path, &["--timings"]);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for label in ["Timings:", "glob", "read", "parse", "render", "write", "total"] {
+        assert!(stderr.contains(label), "Missing '{}' in: {}", label, stderr);
+    }
+    assert!(stderr.contains("Read 3 files"));
+    assert!(!stderr.contains("stubs"));
+
+    // Stubs get a phase of their own, apart from writing the output
+    let stubs_dir = temp_dir.join("stubs");
+    let output = run_generate(
+        "multiple-categories",
+        &output_path,
+        &["--timings", "--output-dir", stubs_dir.to_str().unwrap()],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("\n  stubs "), "{}", stderr);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_max_depth() {
+    let temp_dir = temp_dir("max-depth");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+
+    run_generate("max-depth", &output_path, &["--max-depth", "2"]);
+
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.contains("`one/README.md`"));
+    assert!(!doc.contains("two/three/four"));
+    assert!(!doc.contains("five/six/seven/eight/nine"));
+
+    run_generate("max-depth", &output_path, &["--max-depth", "3"]);
+
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.contains("`one/README.md`"));
+    assert!(doc.contains("`two/three/four/README.md`"));
+    assert!(!doc.contains("five/six/seven/eight/nine"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_set_overrides_config() {
+    let temp_dir = temp_dir("set");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let config = get_fixtures_dir().join("with-config/architecture.toml");
+
+    run_generate(
+        "with-config",
+        &output_path,
+        &[
+            "--config",
+            config.to_str().unwrap(),
+            "--set",
+            "title=Nightly Arch",
+            "--set",
+            "order=count",
+        ],
+    );
+
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.starts_with("# Nightly Arch\n"));
+    assert!(doc.contains("This document describes the system components."));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_set_unknown_key_fails() {
+    let temp_dir = temp_dir("set-unknown");
+    let pattern = get_fixtures_dir().join("single-file/**/README.md");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(pattern.display().to_string())
+        .arg(temp_dir.join("ARCHITECTURE.md"))
+        .args(["--set", "titel=Oops"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown config key: titel"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_require_descriptions() {
+    let temp_dir = temp_dir("require-descriptions");
+    for (name, front_matter) in [
+        ("api", "category: \"Services\"\ndescription: \"The API\"\n"),
+        ("core", "category: \"Core\"\n"),
+    ] {
+        fs::create_dir_all(temp_dir.join(name)).unwrap();
+        fs::write(
+            temp_dir.join(name).join("README.md"),
+            format!("---\n{}---\n\nFirst paragraph.\n", front_matter),
+        )
+        .unwrap();
+    }
+    let pattern = temp_dir.join("**/README.md").display().to_string();
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let generate = |extra_args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("generate")
+            .arg(&pattern)
+            .arg(&output_path)
+            .args(extra_args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = generate(&["--require-descriptions"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("core/README.md"), "{}", stderr);
+    assert!(!stderr.contains("api/README.md"), "{}", stderr);
+    assert!(!output_path.exists());
+
+    assert!(generate(&[]).status.success());
+    assert!(fs::read_to_string(&output_path)
+        .unwrap()
+        .contains("`core/README.md`: First paragraph."));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_report_tallies_skip_reasons() {
+    let temp_dir = temp_dir("skip-report");
+    let files: [(&str, &[u8]); 6] = [
+        ("valid", b"---\ncategory: \"Core\"\n---\n\nParses input.\n"),
+        ("plain", b"# Plain\n\nNo front matter.\n"),
+        ("broken", b"---\ncategory: [\n---\n\nBroken.\n"),
+        ("empty", b"---\ncategory: \"Core\"\n---\n"),
+        ("binary", b"---\ncategory: \"Core\"\n---\n\xff\xfe\n"),
+        ("typo", b"---\ncategory: \"Utilites\"\n---\n\nTypo.\n"),
+    ];
+    for (name, content) in files {
+        fs::create_dir_all(temp_dir.join("src").join(name)).unwrap();
+        fs::write(temp_dir.join("src").join(name).join("README.md"), content).unwrap();
+    }
+    let config = temp_dir.join("architecture.toml");
+    fs::write(
+        &config,
+        "strict_categories = true\n\n[[categories]]\ncategory = \"Core\"\n",
+    )
+    .unwrap();
+
+    let pattern = temp_dir.join("src/**/README.md");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let generate = |format: &str| {
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("generate")
+            .arg(pattern.display().to_string())
+            .arg(&output_path)
+            .arg("--config")
+            .arg(&config)
+            .args(["--report", format])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // The unknown category still fails the run, after the report
+    let output = generate("json");
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_files"], 6);
+    assert_eq!(report["parsed"], 1);
+    assert_eq!(
+        report["reasons"],
+        serde_json::json!({
+            "invalid_front_matter": 1,
+            "io_error": 1,
+            "no_description": 1,
+            "no_front_matter": 1,
+            "unknown_category": 1,
+        })
+    );
+    let skipped = report["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 5);
+    assert!(skipped.iter().all(|finding| finding["path"].is_string()));
+
+    let output = generate("human");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Skipped 5 of 6 files\n"), "{}", stderr);
+    assert!(stderr.contains("  no_front_matter"), "{}", stderr);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_max_files_aborts_before_writing() {
+    let temp_dir = temp_dir("max-files");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let pattern = get_fixtures_dir().join("multiple-categories/**/README.md");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(pattern.display().to_string())
+        .arg(&output_path)
+        .args(["--max-files", "1"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("more than --max-files 1 files"),
+        "{}",
+        stderr
+    );
+    assert!(stderr.contains("narrow the pattern"));
+    assert!(!output_path.exists());
+
+    // A cap at or above the match count changes nothing
+    run_generate("multiple-categories", &output_path, &["--max-files", "100"]);
+    assert!(output_path.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_verify_links_reports_broken_links() {
+    let temp_dir = temp_dir("verify-links");
+    for name in ["core", "store"] {
+        fs::create_dir_all(temp_dir.join("src").join(name)).unwrap();
+        fs::write(
+            temp_dir.join("src").join(name).join("README.md"),
+            "---\ncategory: \"Core\"\n---\n\nComponent.\n",
+        )
+        .unwrap();
+    }
+    // Entries show their directory and link to the README
+    fs::write(
+        temp_dir.join("architecture.toml"),
+        "strip_filename = \"README.md\"",
+    )
+    .unwrap();
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let pattern = temp_dir.join("src/**/README.md").display().to_string();
+    let generate = |args: &[&str]| {
+        fs::remove_file(&output_path).ok();
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .args(["generate", &pattern])
+            .arg(&output_path)
+            .arg("--config")
+            .arg(temp_dir.join("architecture.toml"))
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let relative_to = temp_dir.display().to_string();
+    let output = generate(&["--verify-links", "--relative-to", &relative_to]);
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Warning"));
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.contains("- [`src/core`](src/core/README.md): Component.\n"));
+
+    // Paths relative to the pattern's base don't resolve from the document
+    let output = generate(&["--verify-links"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr
+            .contains("Warning: core/README.md: link 'core/README.md' does not resolve to a file"),
+        "{}",
+        stderr
+    );
+    assert!(output_path.exists());
+
+    // Strict mode fails instead, before writing the document
+    let output = generate(&["--strict-links"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error: store/README.md: link 'store/README.md' does not resolve"));
+    assert!(stderr.contains("2 entry link(s) don't resolve from"));
+    assert!(!output_path.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_overlapping_patterns_document_each_file_once() {
+    let temp_dir = temp_dir("overlapping-patterns");
+    for dir in ["repo/services/api", "repo/tools"] {
+        fs::create_dir_all(temp_dir.join(dir)).unwrap();
+        fs::write(
+            temp_dir.join(dir).join("README.md"),
+            "---\ncategory: \"Core\"\n---\n\nComponent.\n",
+        )
+        .unwrap();
+    }
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+
+    // Whichever pattern comes first, the file under both takes its path
+    // from the more specific base
+    for patterns in [
+        ["repo/**/README.md", "repo/services/**/README.md"],
+        ["repo/services/**/README.md", "repo/**/README.md"],
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .args([
+                "generate",
+                patterns[0],
+                "ARCHITECTURE.md",
+                "--pattern",
+                patterns[1],
+            ])
+            .current_dir(&temp_dir)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Generate command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let doc = fs::read_to_string(&output_path).unwrap();
+        let entries: Vec<&str> = doc.lines().filter(|l| l.starts_with("- ")).collect();
+        assert_eq!(
+            entries,
+            vec![
+                "- `api/README.md`: Component.",
+                "- `tools/README.md`: Component."
+            ]
+        );
+    }
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_sources_with_different_front_matter_formats() {
+    let temp_dir = temp_dir("sources-formats");
+    fs::create_dir_all(temp_dir.join("services/api")).unwrap();
+    fs::create_dir_all(temp_dir.join("legacy/db")).unwrap();
+    fs::write(
+        temp_dir.join("services/api/README.md"),
+        "---\ncategory: \"Core\"\ndescription: \"The API\"\n---\n",
+    )
+    .unwrap();
+    // TOML between `---` delimiters, which would otherwise be parsed as YAML
+    fs::write(
+        temp_dir.join("legacy/db/README.md"),
+        "---\ncategory = \"Core\"\ndescription = \"The database\"\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.join("architecture.toml"),
+        r#"
+[[sources]]
+pattern = "legacy/**/README.md"
+front_matter_format = "toml"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args([
+            "generate",
+            "services/**/README.md",
+            "ARCHITECTURE.md",
+            "--config",
+            "architecture.toml",
+        ])
+        .current_dir(&temp_dir)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Generate command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let doc = fs::read_to_string(temp_dir.join("ARCHITECTURE.md")).unwrap();
+    let entries: Vec<&str> = doc.lines().filter(|l| l.starts_with("- ")).collect();
+    assert_eq!(
+        entries,
+        vec![
+            "- `api/README.md`: The API",
+            "- `db/README.md`: The database"
+        ]
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_sources_resolve_from_config_dir_for_generate_and_validate() {
+    let temp_dir = temp_dir("sources-config-dir");
+    fs::create_dir_all(temp_dir.join("services/api")).unwrap();
+    fs::create_dir_all(temp_dir.join("legacy/db")).unwrap();
+    fs::create_dir_all(temp_dir.join("docs")).unwrap();
+    fs::write(
+        temp_dir.join("services/api/README.md"),
+        "---\ncategory: \"Core\"\ndescription: \"The API\"\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.join("legacy/db/README.md"),
+        "---\ncategory = \"Core\"\ndescription = \"The database\"\n---\n",
+    )
+    .unwrap();
+    // Relative to the config file, not the working directory
+    fs::write(
+        temp_dir.join("docs/architecture.toml"),
+        r#"
+[[sources]]
+pattern = "../legacy/**/README.md"
+front_matter_format = "toml"
+"#,
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .args(args)
+            .args(["--config", "docs/architecture.toml"])
+            .current_dir(&temp_dir)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&["generate", "services/**/README.md", "ARCHITECTURE.md"]);
+    assert!(
+        output.status.success(),
+        "Generate command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let doc = fs::read_to_string(temp_dir.join("ARCHITECTURE.md")).unwrap();
+    assert!(doc.contains("- `db/README.md`: The database"));
+
+    // validate and doctor check the source's files too, with its format
+    let output = run(&["validate", "services/**/README.md", "--report", "json"]);
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_files"], 2);
+    assert_eq!(report["parsed"], 2);
+
+    let output = run(&["doctor", "services/**/README.md"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("components: 2\n"));
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_is_reproducible_across_runs() {
+    let temp_dir = temp_dir("reproducible");
+    // Many equally sized categories, each with a dependency cycle, so any
+    // order taken from a hash map would show in the document or the warnings
+    for i in 0..40 {
+        for (name, other) in [("a", "b"), ("b", "a")] {
+            let dir = temp_dir.join(format!("repo/c{i}{name}"));
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("README.md"),
+                format!(
+                    "---\ncategory: \"Category {i}\"\ntags: [\"t{i}\"]\n\
+                     dependencies: [\"c{i}{other}\"]\n---\n\nComponent {i}{name}.\n"
+                ),
+            )
+            .unwrap();
+        }
+    }
+    fs::write(
+        temp_dir.join("architecture.toml"),
+        "order = \"count\"\nsort = \"dependencies\"\ntoc = true\nindex = true\nshow_tags = true\n",
+    )
+    .unwrap();
+
+    let run = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .args([
+                "generate",
+                "repo/**/README.md",
+                "ARCHITECTURE.md",
+                "--config",
+                "architecture.toml",
+            ])
+            .current_dir(&temp_dir)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Generate command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let doc = fs::read_to_string(temp_dir.join("ARCHITECTURE.md")).unwrap();
+        (doc, String::from_utf8_lossy(&output.stderr).into_owned())
+    };
+
+    // Each process hashes with different random keys
+    let first = run();
+    assert!(first.1.contains("dependency cycle"), "{}", first.1);
+    for _ in 0..3 {
+        assert_eq!(run(), first);
+    }
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_managed_region() {
+    let temp_dir = temp_dir("managed-region");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let intro = "# Platform\n\nHand-written intro.\n\n<!-- ARCH:BEGIN -->\n";
+    let appendix = "<!-- ARCH:END -->\n\n## Appendix\n\nCurated notes.\n";
+    fs::write(&output_path, format!("{}old entries\n{}", intro, appendix)).unwrap();
+
+    let output_arg = output_path.display().to_string();
+    run_generate("multiple-categories", &output_path, &["--managed-region"]);
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.starts_with(intro), "{}", doc);
+    assert!(doc.ends_with(appendix), "{}", doc);
+    assert!(!doc.contains("old entries"), "{}", doc);
+    assert!(doc.contains("`crates/core/README.md`"), "{}", doc);
+    // The generated title is left out in favour of the file's own
+    assert_eq!(
+        doc.lines().filter(|line| line.starts_with("# ")).count(),
+        1,
+        "{}",
+        doc
+    );
+
+    // Regenerating leaves the file as it is
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(get_fixtures_dir().join("multiple-categories/**/README.md"))
+        .args([&output_arg, "--managed-region", "--check"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_managed_region_requires_markers() {
+    let temp_dir = temp_dir("managed-region-missing");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    fs::write(&output_path, "# Notes\n\nNo markers here.\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(get_fixtures_dir().join("multiple-categories/**/README.md"))
+        .arg(&output_path)
+        .arg("--managed-region")
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("<!-- ARCH:BEGIN -->"), "{}", stderr);
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "# Notes\n\nNo markers here.\n"
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_deprecated_components() {
+    let temp_dir = temp_dir("deprecated");
+    for (dir, deprecated) in [("api", false), ("legacy", true)] {
+        fs::create_dir_all(temp_dir.join(dir)).unwrap();
+        fs::write(
+            temp_dir.join(dir).join("README.md"),
+            format!("---\ncategory: \"Core\"\ndeprecated: {deprecated}\n---\n\nThe {dir}.\n"),
+        )
+        .unwrap();
+    }
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let pattern = temp_dir.join("**/README.md").display().to_string();
+    let generate = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("generate")
+            .arg(&pattern)
+            .arg(&output_path)
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        fs::read_to_string(&output_path).unwrap()
+    };
+
+    let doc = generate(&[]);
+    assert!(
+        doc.ends_with("## Deprecated\n\n- ~~`legacy/README.md`~~: The legacy.\n"),
+        "{}",
+        doc
+    );
+
+    let doc = generate(&["--set", "show_deprecated=false"]);
+    assert!(!doc.contains("legacy"), "{}", doc);
+    assert!(doc.contains("- `api/README.md`: The api.\n"), "{}", doc);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_llms_format() {
+    let temp_dir = temp_dir("llms-format");
+    let output_path = temp_dir.join("llms.txt");
+
+    run_generate("multiple-categories", &output_path, &["--format", "llms"]);
+
+    let text = fs::read_to_string(&output_path).unwrap();
+    assert!(text.contains("crates/core/README.md \u{2014} "), "{}", text);
+    assert!(!text.contains('`') && !text.contains("\n- "), "{}", text);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_toc_format() {
+    let temp_dir = temp_dir("toc-format");
+    let output_path = temp_dir.join("TOC.md");
+
+    run_generate("multiple-categories", &output_path, &["--format", "toc"]);
+
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "# Architecture Documentation\n\n\
+         - [Interfaces](#interfaces) (1)\n\
+         - [Services](#services) (1)\n\
+         - [Utilities](#utilities) (1)\n"
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_json_format() {
+    let temp_dir = temp_dir("json-format");
+    let output_path = temp_dir.join("architecture.json");
+
+    run_generate("multiple-categories", &output_path, &["--format", "json"]);
+
+    let doc: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+    let ids: Vec<&str> = doc["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            "crates/api/README.md",
+            "crates/cli/README.md",
+            "crates/core/README.md"
+        ]
+    );
+    assert_eq!(doc["sections"][0]["title"], "Interfaces");
+    assert_eq!(
+        doc["sections"][0]["components"],
+        serde_json::json!(["crates/cli/README.md"])
+    );
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_json_fails_on_colliding_ids() {
+    let temp_dir = temp_dir("json-colliding-ids");
+    for dir in ["services/db", "legacy/db"] {
+        fs::create_dir_all(temp_dir.join(dir)).unwrap();
+        fs::write(
+            temp_dir.join(dir).join("README.md"),
+            "---\ncategory: \"Core\"\n---\n\nComponent.\n",
+        )
+        .unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args([
+            "generate",
+            "services/**/README.md",
+            "architecture.json",
+            "--pattern",
+            "legacy/**/README.md",
+            "--format",
+            "json",
+        ])
+        .current_dir(&temp_dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("'db/README.md'"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!temp_dir.join("architecture.json").exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_emit_stats() {
+    let temp_dir = temp_dir("emit-stats");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let stats_path = temp_dir.join("stats.json");
+
+    run_generate(
+        "multiple-categories",
+        &output_path,
+        &["--emit-stats", stats_path.to_str().unwrap()],
+    );
+
+    let stats: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&stats_path).unwrap()).unwrap();
+    assert_eq!(stats["components"], 3);
+    assert_eq!(stats["categories"], 3);
+    assert_eq!(stats["with_front_matter_description"], 3);
+    assert_eq!(stats["fallback_description"], 0);
+    assert_eq!(stats["missing"], 0);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_jobs_one_matches_default() {
+    let temp_dir = temp_dir("jobs");
+    let default_output = temp_dir.join("DEFAULT.md");
+    run_generate("multiple-categories", &default_output, &[]);
+
+    let serial_output = temp_dir.join("SERIAL.md");
+    let pattern = get_fixtures_dir().join("multiple-categories/**/README.md");
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["--jobs", "1", "generate"])
+        .arg(pattern.display().to_string())
+        .arg(&serial_output)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Generate command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let expected = fs::read_to_string(&default_output).unwrap();
+    assert!(expected.matches("\n- ").count() > 1, "{}", expected);
+    assert_eq!(fs::read_to_string(&serial_output).unwrap(), expected);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_final_newline() {
+    let temp_dir = temp_dir("final-newline");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+
+    run_generate("multiple-categories", &output_path, &[]);
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.ends_with('\n') && !doc.ends_with("\n\n"), "{:?}", doc);
+
+    // Every format goes through the same normalization when written
+    for format in ["markdown", "csv", "llms"] {
+        run_generate(
+            "multiple-categories",
+            &output_path,
+            &["--format", format, "--set", "final_newline=false"],
+        );
+        let doc = fs::read_to_string(&output_path).unwrap();
+        assert!(!doc.ends_with('\n'), "{}: {:?}", format, doc);
+    }
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_respect_gitignore() {
+    let temp_dir = temp_dir("respect-gitignore");
+    for dir in ["repo/core", "repo/target/vendored"] {
+        fs::create_dir_all(temp_dir.join(dir)).unwrap();
+        fs::write(
+            temp_dir.join(dir).join("README.md"),
+            "---\ncategory: \"Core\"\n---\n\nComponent.\n",
+        )
+        .unwrap();
+    }
+    fs::write(temp_dir.join("repo/.gitignore"), "/target\n").unwrap();
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let pattern = temp_dir.join("repo/**/README.md").display().to_string();
+
+    let generate = |extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("generate")
+            .arg(&pattern)
+            .arg(&output_path)
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        fs::read_to_string(&output_path).unwrap()
+    };
+
+    let doc = generate(&[]);
+    assert!(doc.contains("`target/vendored/README.md`"), "{}", doc);
+
+    let doc = generate(&["--respect-gitignore"]);
+    assert!(doc.contains("`core/README.md`"), "{}", doc);
+    assert!(!doc.contains("target/"), "{}", doc);
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_creates_missing_output_dirs_by_default() {
+    let temp_dir = temp_dir("create-dirs");
+    let output_path = temp_dir.join("nested/docs/ARCHITECTURE.md");
+
+    run_generate("single-file", &output_path, &[]);
+
+    assert!(output_path.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_no_create_dirs_errors_on_missing_parent() {
+    let temp_dir = temp_dir("no-create-dirs");
+    let output_path = temp_dir.join("nested/docs/ARCHITECTURE.md");
+    let pattern = get_fixtures_dir().join("single-file/**/README.md");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(pattern.display().to_string())
+        .arg(&output_path)
+        .arg("--no-create-dirs")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Output directory does not exist"));
+    assert!(!temp_dir.join("nested").exists());
+
+    // An existing parent directory is fine
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    run_generate("single-file", &output_path, &["--no-create-dirs"]);
+    assert!(output_path.exists());
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .expect("Failed to run git");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+fn write_readme(dir: &Path, name: &str, description: &str) {
+    let readme_dir = dir.join("crates").join(name);
+    fs::create_dir_all(&readme_dir).unwrap();
+    fs::write(
+        readme_dir.join("README.md"),
+        format!(
+            "---\ndescription: \"{}\"\ncategory: \"Crates\"\n---\n\n# {}\n",
+            description, name
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_generate_since_git_ref() {
+    let repo = temp_dir("since-repo");
+    write_readme(&repo, "core", "Core utilities");
+    write_readme(&repo, "cli", "Command-line interface");
+    git(&repo, &["init", "-q"]);
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "-q", "-m", "Initial"]);
+    git(&repo, &["tag", "v1"]);
+
+    write_readme(&repo, "core", "Core utilities, now faster");
+    git(&repo, &["commit", "-q", "-am", "Update core"]);
+
+    let output_path = temp_dir("since-output").join("ARCHITECTURE.md");
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(repo.join("**/README.md").display().to_string())
+        .arg(&output_path)
+        .args(["--since", "v1"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "Generate command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.contains("- `crates/core/README.md`: Core utilities, now faster\n"));
+    assert!(!doc.contains("crates/cli/README.md"));
+}
+
+#[test]
+fn test_generate_since_outside_git_repo() {
+    let dir = temp_dir("since-no-repo");
+    write_readme(&dir, "core", "Core utilities");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(dir.join("**/README.md").display().to_string())
+        .arg(dir.join("ARCHITECTURE.md"))
+        .args(["--since", "v1"])
+        .env("GIT_CEILING_DIRECTORIES", std::env::temp_dir())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--since requires a git repository"),
+        "Unexpected stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_generate_profile() {
+    let temp_dir = temp_dir("profile");
+    let config_path = temp_dir.join("architecture.toml");
+    fs::write(
+        &config_path,
+        r#"
+title = "Public Architecture"
+
+[profiles.internal]
+title = "Internal Architecture"
+
+[[profiles.internal.categories]]
+category = "Services"
+title = "Internal Services"
+"#,
+    )
+    .unwrap();
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let config_arg = config_path.to_str().unwrap();
+
+    run_generate("multiple-categories", &output_path, &["-c", config_arg]);
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.starts_with("# Public Architecture\n"));
+    assert!(doc.contains("\n## Services\n"));
+
+    run_generate(
+        "multiple-categories",
+        &output_path,
+        &["-c", config_arg, "--profile", "internal"],
+    );
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.starts_with("# Internal Architecture\n"));
+    assert!(doc.contains("\n## Internal Services\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_generate_symlinked_readme_matched_twice() {
+    use std::os::unix::fs::symlink;
+
+    let dir = temp_dir("symlinks");
+    let crates = dir.join("crates");
+    write_readme(&dir, "shared", "Shared library");
+    for name in ["alpha", "beta"] {
+        fs::create_dir_all(crates.join(name)).unwrap();
+        symlink("../shared/README.md", crates.join(name).join("README.md")).unwrap();
+    }
+    symlink("..", crates.join("shared").join("parent")).unwrap();
+
+    let run = |extra_args: &[&str]| {
+        let output_path = dir.join("ARCHITECTURE.md");
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("generate")
+            .arg(crates.join("**/README.md").display().to_string())
+            .arg(&output_path)
+            .args(extra_args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Generate command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        fs::read_to_string(&output_path).unwrap()
+    };
+
+    let doc = run(&[]);
+    assert_eq!(doc.matches("Shared library").count(), 1, "{}", doc);
+    assert!(doc.contains("- `alpha/README.md`: Shared library\n"));
+
+    let doc = run(&["--follow-symlinks"]);
+    assert_eq!(doc.matches("Shared library").count(), 1, "{}", doc);
+    assert!(doc.contains("- `shared/README.md`: Shared library\n"));
+}
+
+#[test]
+fn test_generate_split_pages() {
+    let temp_dir = temp_dir("split");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+
+    run_generate("multiple-categories", &output_path, &["--split"]);
+
+    let index = fs::read_to_string(&output_path).unwrap();
+    let mut pages: Vec<String> = fs::read_dir(&temp_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name != "ARCHITECTURE.md")
+        .collect();
+    pages.sort();
+    assert_eq!(pages, vec!["interfaces.md", "services.md", "utilities.md"]);
+    for page in &pages {
+        assert!(index.contains(&format!("]({})", page)), "{}", index);
+        let content = fs::read_to_string(temp_dir.join(page)).unwrap();
+        assert!(content.contains("[Index](ARCHITECTURE.md)"));
+    }
+}
+
+#[test]
+fn test_generate_rejects_group_by_without_sections() {
+    let temp_dir = temp_dir("group-by-unsupported");
+    let pattern = get_fixtures_dir().join("multiple-categories/**/README.md");
+
+    for args in [&["--split"][..], &["--format", "csv"]] {
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("generate")
+            .arg(pattern.display().to_string())
+            .arg(temp_dir.join("ARCHITECTURE.md"))
+            .args(["--set", "group_by=release"])
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "{:?}", args);
+        assert!(
+            String::from_utf8_lossy(&output.stderr)
+                .contains("group_by = \"release\" is not supported"),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(!temp_dir.join("ARCHITECTURE.md").exists());
+    }
+
+    fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+fn test_generate_html_format() {
+    let temp_dir = temp_dir("html");
+    let output_path = temp_dir.join("architecture.html");
+
+    run_generate("multiple-categories", &output_path, &["--format", "html"]);
+
+    let html = fs::read_to_string(&output_path).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains("<h1>Architecture Documentation</h1>"));
+    assert!(html.contains("<h2>Services</h2>"));
+}
+
+#[test]
+fn test_generate_relative_to() {
+    let temp_dir = temp_dir("relative-to");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let fixture = get_fixtures_dir().join("multiple-categories");
+
+    let run = |root: &Path| {
+        run_generate(
+            "multiple-categories",
+            &output_path,
+            &["--relative-to", root.to_str().unwrap()],
+        );
+        fs::read_to_string(&output_path).unwrap()
+    };
+
+    let doc = run(&fixture.join("crates"));
+    assert!(doc.contains("- `api/README.md`: "), "{}", doc);
+
+    let doc = run(&get_fixtures_dir());
+    assert!(
+        doc.contains("- `multiple-categories/crates/api/README.md`: "),
+        "{}",
+        doc
+    );
+}
+
+#[test]
+fn test_generate_tag_filters() {
+    let temp_dir = temp_dir("tags");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let generate = |args: &[&str]| {
+        run_generate("tags", &output_path, args);
+        fs::read_to_string(&output_path).unwrap()
+    };
+
+    let doc = generate(&["--tag", "async"]);
+    assert!(doc.contains("crates/core/README.md"));
+    assert!(doc.contains("crates/net/README.md"));
+    assert!(!doc.contains("crates/admin/README.md"));
+
+    let doc = generate(&["--not-tag", "internal"]);
+    assert!(doc.contains("crates/core/README.md"));
+    assert!(!doc.contains("crates/net/README.md"));
+    assert!(doc.contains("crates/admin/README.md"));
+
+    let doc = generate(&[
+        "--tag",
+        "async",
+        "--not-tag",
+        "internal",
+        "--only",
+        "Services",
+    ]);
+    assert_eq!(doc.trim(), "# Architecture Documentation");
+}
+
+#[test]
+fn test_generate_common_base() {
+    let temp_dir = temp_dir("common-base");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+
+    // Every match is under crates/, so paths are shown relative to it
+    run_generate("multiple-categories", &output_path, &["--common-base"]);
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.contains("- `api/README.md`: "), "{}", doc);
+    assert!(doc.contains("- `core/README.md`: "), "{}", doc);
+}
+
+#[test]
+fn test_render_emitted_components_matches_generate() {
+    let temp_dir = temp_dir("emit-components");
+    let generated = temp_dir.join("ARCHITECTURE.md");
+    let components = temp_dir.join("components.json");
+    let rendered = temp_dir.join("rendered/ARCHITECTURE.md");
+
+    run_generate(
+        "multiple-categories",
+        &generated,
+        &["--emit-components", components.to_str().unwrap()],
+    );
+
+    for format in ["markdown", "html"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("render")
+            .arg(&components)
+            .arg(&rendered)
+            .args(["--format", format])
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "Render command failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        run_generate("multiple-categories", &generated, &["--format", format]);
+        assert_eq!(
+            fs::read_to_string(&rendered).unwrap(),
+            fs::read_to_string(&generated).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_generate_literal_pattern() {
+    let temp_dir = temp_dir("literal-pattern");
+    let output_path = temp_dir.join("ARCHITECTURE.md");
+    let readme = get_fixtures_dir().join("multiple-categories/crates/core/README.md");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("generate")
+        .arg(&readme)
+        .arg(&output_path)
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    // The file's directory is the base, so the path is shown as just the file name
+    let doc = fs::read_to_string(&output_path).unwrap();
+    assert!(doc.contains("- `README.md`: "), "{}", doc);
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::process::{Command, Output};
+
+fn run_list(dir: &std::path::Path, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["list", "**/README.md"])
+        .args(extra)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_list_show_raw_category() {
+    let dir = std::env::temp_dir().join("rust-arch-list");
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(dir.join("cli")).unwrap();
+    fs::create_dir_all(dir.join("misc")).unwrap();
+    fs::write(
+        dir.join("architecture.toml"),
+        "default_category = \"Other\"\n\n[aliases]\ncli = \"Interfaces\"\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("cli/README.md"),
+        "---\ncategory: \"cli\"\ndescription: \"Entry point\"\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("misc/README.md"),
+        "---\ncategory: \"\"\ndescription: \"Odds and ends\"\n---\n",
+    )
+    .unwrap();
+
+    let output = run_list(&dir, &[]);
+    assert!(
+        output.status.success(),
+        "list failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "cli/README.md\tInterfaces\nmisc/README.md\tOther\n"
+    );
+
+    let output = run_list(&dir, &["--show-raw"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "cli/README.md\tcli\tInterfaces\nmisc/README.md\t-\tOther\n"
+    );
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use common::temp_dir;
+
+const BODY: &str = "\n# Core\n\nCore utilities.\n";
+
+/// A scratch directory with an aliased, padded category to normalize.
+fn component_dir(name: &str) -> PathBuf {
+    let dir = temp_dir(name);
+    fs::create_dir_all(dir.join("core")).unwrap();
+    fs::write(
+        dir.join("core/README.md"),
+        format!("---\ncategory: \"  utils \"\n---\n{}", BODY),
+    )
+    .unwrap();
+    fs::write(
+        dir.join("architecture.toml"),
+        "[aliases]\nutils = \"Utilities\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+fn run_normalize(dir: &Path, extra_args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["--color", "never", "normalize"])
+        .arg(dir.join("**/README.md").display().to_string())
+        .arg("--config")
+        .arg(dir.join("architecture.toml"))
+        .args(extra_args)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Normalize command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn test_normalize_dry_run_prints_diff() {
+    let dir = component_dir("dry-run");
+    let before = fs::read_to_string(dir.join("core/README.md")).unwrap();
+
+    let output = run_normalize(&dir, &["--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("-category: \"  utils \"\n"), "{}", stdout);
+    assert!(stdout.contains("+category: Utilities\n+description: Core utilities.\n"));
+    assert!(stdout.contains("1 of 1 files would change"));
+
+    assert_eq!(
+        fs::read_to_string(dir.join("core/README.md")).unwrap(),
+        before
+    );
+}
+
+#[test]
+fn test_normalize_rewrites_front_matter_only() {
+    let dir = component_dir("rewrite");
+
+    run_normalize(&dir, &[]);
+    assert_eq!(
+        fs::read_to_string(dir.join("core/README.md")).unwrap(),
+        format!(
+            "---\ncategory: Utilities\ndescription: Core utilities.\n---\n{}",
+            BODY
+        )
+    );
+
+    // Already canonical files are left alone
+    let output = run_normalize(&dir, &[]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Normalized 0 of 1 files"));
+}
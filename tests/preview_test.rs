@@ -0,0 +1,67 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run_preview(content: &str, extra_args: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("preview")
+        .args(extra_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(content.as_bytes())
+        .unwrap();
+    child.wait_with_output().expect("Failed to wait on command")
+}
+
+#[test]
+fn test_preview_renders_entry() {
+    let output = run_preview(
+        "---\ncategory: \" Core \"\n---\n\n# Core\n\nShared utilities.\n",
+        &["--path", "crates/core/README.md"],
+    );
+
+    assert!(
+        output.status.success(),
+        "Preview failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "- `crates/core/README.md`: Shared utilities.\n\
+         Category: Core\n\
+         Description: Shared utilities.\n"
+    );
+}
+
+#[test]
+fn test_preview_without_front_matter_fails() {
+    let output = run_preview("# Core\n\nShared utilities.\n", &[]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No front matter found in: README.md"));
+}
+
+#[test]
+fn test_preview_truncates_fallback_description() {
+    let dir = std::env::temp_dir().join("rust-arch-preview-truncate");
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("architecture.toml");
+    std::fs::write(&config, "description_sentences = 1\n").unwrap();
+
+    let output = run_preview(
+        "---\ncategory: Core\n---\n\nShared utilities. Used everywhere.\n",
+        &["--config", &config.display().to_string()],
+    );
+
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stdout).starts_with("- `README.md`: Shared utilities. …\n")
+    );
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use std::fs;
+use std::process::{Command, Output};
+
+use common::temp_dir;
+
+fn run_print_config(args: &[&str], env: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("print-config")
+        .args(args)
+        .envs(env.iter().copied())
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_print_config_resolves_env_profile_and_overrides() {
+    let dir = temp_dir("layers");
+    fs::write(dir.join("base.toml"), "toc = true\n").unwrap();
+    fs::write(
+        dir.join("architecture.toml"),
+        r#"extends = "base.toml"
+title = "${ARCH_TEAM} Architecture"
+
+[[categories]]
+category = "Core"
+owner = "platform"
+
+[profiles.internal]
+show_tags = true
+"#,
+    )
+    .unwrap();
+    let config = dir.join("architecture.toml").display().to_string();
+
+    let output = run_print_config(
+        &[
+            "--config",
+            &config,
+            "--profile",
+            "internal",
+            "--set",
+            "separator= - ",
+        ],
+        &[("ARCH_TEAM", "Payments")],
+    );
+    assert!(
+        output.status.success(),
+        "print-config failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let printed: toml::Table = toml::from_str(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    assert_eq!(printed["title"].as_str(), Some("Payments Architecture"));
+    assert_eq!(printed["toc"].as_bool(), Some(true));
+    assert_eq!(printed["show_tags"].as_bool(), Some(true));
+    assert_eq!(printed["separator"].as_str(), Some(" - "));
+    assert_eq!(printed["categories"][0]["owner"].as_str(), Some("platform"));
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_print_config_json() {
+    let dir = temp_dir("json");
+    fs::write(dir.join("architecture.toml"), "title = \"Docs\"\n").unwrap();
+    let config = dir.join("architecture.toml").display().to_string();
+
+    let output = run_print_config(&["--config", &config, "--format", "json"], &[]);
+    assert!(output.status.success());
+
+    let printed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(printed["title"], "Docs");
+    assert_eq!(printed["order"], "config");
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_print_config_unset_env_variable() {
+    let dir = temp_dir("unset");
+    fs::write(
+        dir.join("architecture.toml"),
+        "title = \"${RUST_ARCH_SURELY_UNSET}\"\n",
+    )
+    .unwrap();
+    let config = dir.join("architecture.toml").display().to_string();
+
+    let output = run_print_config(&["--config", &config], &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("RUST_ARCH_SURELY_UNSET"));
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_verbose_logs_config_source() {
+    let dir = temp_dir("verbose");
+    let config = dir.join("architecture.toml");
+    let run = || {
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .args(["--verbose", "print-config", "--config"])
+            .arg(&config)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Using config: built-in defaults\n"));
+
+    fs::write(&config, "").unwrap();
+    let output = run();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains(&format!("Using config: {}\n", config.display())));
+
+    fs::remove_dir_all(&dir).ok();
+}
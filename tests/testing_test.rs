@@ -0,0 +1,71 @@
+mod common;
+
+use std::fs;
+
+use common::get_fixtures_dir;
+use rust_architecture::render_to_string;
+
+#[test]
+fn test_render_to_string_matches_expected() {
+    for fixture in ["multiple-categories", "with-config"] {
+        let fixture_dir = get_fixtures_dir().join(fixture);
+        let expected = fs::read_to_string(fixture_dir.join("expected.md")).unwrap();
+
+        let actual = render_to_string(&fixture_dir).unwrap();
+        assert_eq!(
+            actual.trim(),
+            expected.trim(),
+            "Fixture '{}' output doesn't match expected",
+            fixture
+        );
+    }
+}
+
+#[test]
+fn test_render_to_string_strict_categories() {
+    assert!(render_to_string(&get_fixtures_dir().join("strict-categories")).is_err());
+}
+
+#[test]
+fn test_render_to_string_includes_sources() {
+    let dir = common::temp_dir("sources");
+    fs::create_dir_all(dir.join("core")).unwrap();
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(
+        dir.join("core/README.md"),
+        "---\ncategory: \"Core\"\n---\n\nThe core.\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("docs/guide.md"),
+        "+++\ncategory = \"Docs\"\n+++\n\nThe guide.\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("architecture.toml"),
+        "[[sources]]\npattern = \"docs/*.md\"\nfront_matter_format = \"toml\"\n",
+    )
+    .unwrap();
+
+    let doc = render_to_string(&dir).unwrap();
+    assert!(doc.contains("- `core/README.md`: The core."), "{}", doc);
+    assert!(doc.contains("- `guide.md`: The guide."), "{}", doc);
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_render_to_string_strict_categories_empty_category() {
+    let dir = common::temp_dir("empty-category");
+    fs::create_dir_all(dir.join("core")).unwrap();
+    fs::write(
+        dir.join("core/README.md"),
+        "---\ncategory: \"\"\n---\n\nThe core.\n",
+    )
+    .unwrap();
+    fs::write(dir.join("architecture.toml"), "strict_categories = true\n").unwrap();
+
+    assert!(render_to_string(&dir).is_err());
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,295 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn get_fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn run_validate(fixture_name: &str, extra_args: &[&str]) -> Output {
+    let pattern = get_fixtures_dir().join(fixture_name).join("**/README.md");
+
+    Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("validate")
+        .arg(pattern.display().to_string())
+        .args(extra_args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_validate_check_cycles_acyclic() {
+    let output = run_validate("dependency-acyclic", &["--check-cycles"]);
+
+    assert!(
+        output.status.success(),
+        "Validate failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_validate_check_cycles_three_node_cycle() {
+    let output = run_validate("dependency-cycle", &["--check-cycles"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("dependency cycle detected: a -> b -> c -> a"),
+        "Unexpected stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_validate_without_check_cycles_ignores_cycle() {
+    let output = run_validate("dependency-cycle", &[]);
+
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_validate_strict_categories_unknown_category() {
+    let config = get_fixtures_dir().join("strict-categories/architecture.toml");
+    let output = run_validate("strict-categories", &["--config", config.to_str().unwrap()]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "Unknown category 'Utilites' in: crates/core/README.md (did you mean 'Utilities'?)"
+        ),
+        "Unexpected stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_validate_report_json() {
+    let output = run_validate("no-front-matter", &["--report", "json"]);
+
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_files"], 2);
+    assert_eq!(report["parsed"], 1);
+    assert_eq!(report["warnings"], serde_json::json!([]));
+
+    let failures = report["failures"].as_array().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0]["kind"], "no_front_matter");
+    assert!(failures[0]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("no-front-matter/docs/README.md"));
+    assert_eq!(failures[0]["line"], serde_json::Value::Null);
+    assert!(failures[0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("No front matter found in: "));
+}
+
+#[test]
+fn test_validate_emit_stats() {
+    let stats_file = std::env::temp_dir().join("rust-arch-validate-stats.json");
+    fs::remove_file(&stats_file).ok();
+    let config = get_fixtures_dir().join("doctor-mixed/architecture.toml");
+
+    // docs/README.md has no front matter, so validation fails but stats are
+    // still written
+    let output = run_validate(
+        "doctor-mixed",
+        &[
+            "--config",
+            config.to_str().unwrap(),
+            "--emit-stats",
+            stats_file.to_str().unwrap(),
+        ],
+    );
+    assert!(!output.status.success());
+
+    let stats: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&stats_file).unwrap()).unwrap();
+    assert_eq!(
+        stats,
+        serde_json::json!({
+            "components": 3,
+            "categories": 3,
+            "with_front_matter_description": 2,
+            "fallback_description": 1,
+            "missing": 0,
+            "avg_word_count": 3.67,
+        })
+    );
+
+    fs::remove_file(&stats_file).ok();
+}
+
+/// Writes two components under `dir/src`, with a config whose entries link
+/// to their READMEs.
+fn write_linked_components(dir: &Path) {
+    fs::remove_dir_all(dir).ok();
+    for name in ["core", "store"] {
+        fs::create_dir_all(dir.join("src").join(name)).unwrap();
+        fs::write(
+            dir.join("src").join(name).join("README.md"),
+            format!("---\ncategory: \"Core\"\n---\n\n{} component.\n", name),
+        )
+        .unwrap();
+    }
+    fs::write(
+        dir.join("architecture.toml"),
+        "strip_filename = \"README.md\"",
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_validate_verify_links() {
+    let dir = std::env::temp_dir().join("rust-arch-validate-links");
+    write_linked_components(&dir);
+
+    let validate = |doc: &Path| {
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .arg("validate")
+            .arg(dir.join("src/**/README.md").display().to_string())
+            .arg("--config")
+            .arg(dir.join("architecture.toml"))
+            .arg("--verify-links")
+            .arg(doc)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // Links are relative to the pattern's base, where this document would sit
+    let output = validate(&dir.join("src/ARCHITECTURE.md"));
+    assert!(
+        output.status.success(),
+        "Validate failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = validate(&dir.join("ARCHITECTURE.md"));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("store/README.md: link 'store/README.md' does not resolve to a file"),
+        "Unexpected stderr: {}",
+        stderr
+    );
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_validate_require_description() {
+    let dir = std::env::temp_dir().join("rust-arch-validate-require-description");
+    fs::remove_dir_all(&dir).ok();
+    for (name, category) in [("api", "Services"), ("core", "Utilities")] {
+        fs::create_dir_all(dir.join(name)).unwrap();
+        fs::write(
+            dir.join(name).join("README.md"),
+            format!("---\ncategory: \"{}\"\n---\n\nFirst paragraph.\n", category),
+        )
+        .unwrap();
+    }
+    fs::write(
+        dir.join("architecture.toml"),
+        "[[categories]]\ncategory = \"Services\"\nrequire_description = true\n\n\
+         [[categories]]\ncategory = \"Utilities\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["validate", "**/README.md", "--report", "json"])
+        .current_dir(&dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["parsed"], 1);
+    let failures = report["failures"].as_array().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0]["kind"], "description_required");
+    assert_eq!(failures[0]["path"], "api/README.md");
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_validate_front_matter_schema() {
+    let dir = std::env::temp_dir().join("rust-arch-validate-schema");
+    fs::remove_dir_all(&dir).ok();
+    for (name, team) in [("api", "team: \"web\"\n"), ("core", "")] {
+        fs::create_dir_all(dir.join(name)).unwrap();
+        fs::write(
+            dir.join(name).join("README.md"),
+            format!("---\ncategory: \"Core\"\n{}---\n\nA component.\n", team),
+        )
+        .unwrap();
+    }
+    fs::write(
+        dir.join("schema.json"),
+        r#"{"required": ["team"], "properties": {"team": {"type": "string"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("architecture.toml"),
+        "front_matter_schema = \"schema.json\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["validate", "**/README.md", "--report", "json"])
+        .current_dir(&dir)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["parsed"], 1);
+    let failures = report["failures"].as_array().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0]["kind"], "schema_violation");
+    assert_eq!(failures[0]["path"], "core/README.md");
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_validate_respect_gitignore() {
+    let dir = std::env::temp_dir().join("rust-arch-validate-gitignore");
+    fs::remove_dir_all(&dir).ok();
+    for (name, front_matter) in [("core", "category: \"Core\"\n"), ("target/gen", "")] {
+        fs::create_dir_all(dir.join(name)).unwrap();
+        fs::write(
+            dir.join(name).join("README.md"),
+            format!("---\n{}---\n\nA component.\n", front_matter),
+        )
+        .unwrap();
+    }
+    fs::write(dir.join(".gitignore"), "/target\n").unwrap();
+
+    let validate = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+            .args(["validate", "**/README.md", "--report", "json"])
+            .args(extra)
+            .current_dir(&dir)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // The generated README without a category fails unless it is skipped
+    assert!(!validate(&[]).status.success());
+
+    let output = validate(&["--respect-gitignore"]);
+    assert!(
+        output.status.success(),
+        "Validate failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_files"], 1);
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn get_fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn run_verify(doc: &str, extra_args: &[&str]) -> Output {
+    let fixture_path = get_fixtures_dir().join("verify");
+    let pattern = fixture_path.join("**/README.md");
+
+    Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("verify")
+        .arg(fixture_path.join(doc))
+        .arg(pattern.display().to_string())
+        .args(extra_args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+#[test]
+fn test_verify_complete_doc() {
+    let output = run_verify("complete.md", &["--deny-warnings"]);
+
+    assert!(
+        output.status.success(),
+        "Verify failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Verified 3 components"));
+}
+
+#[test]
+fn test_verify_doc_missing_component() {
+    let output = run_verify("missing-component.md", &[]);
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Warning: component missing from document: crates/cli/README.md"),
+        "Unexpected stderr: {}",
+        stderr
+    );
+
+    let output = run_verify("missing-component.md", &["--deny-warnings"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_verify_doc_references_nonexistent_path() {
+    let output = run_verify("nonexistent-path.md", &["--deny-warnings"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Warning: document references unknown component: crates/legacy/README.md"),
+        "Unexpected stderr: {}",
+        stderr
+    );
+    assert!(!stderr.contains("missing from document"));
+}
+
+#[test]
+fn test_verify_doc_references_unparsable_component() {
+    let fixture_path = get_fixtures_dir().join("verify-unparsed");
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .arg("verify")
+        .arg(fixture_path.join("doc.md"))
+        .arg(fixture_path.join("**/README.md").display().to_string())
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "Warning: document references component that failed to parse: crates/broken/README.md"
+        ),
+        "Unexpected stderr: {}",
+        stderr
+    );
+    assert!(!stderr.contains("unknown component"));
+}
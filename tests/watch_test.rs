@@ -0,0 +1,37 @@
+mod common;
+
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::temp_dir;
+
+#[test]
+fn test_watch_with_server_exits_on_error() {
+    let output = temp_dir("invalid-pattern").join("ARCHITECTURE.md");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust-architecture"))
+        .args(["watch", "["])
+        .arg(&output)
+        .args(["--serve", "0"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+
+    // The preview server must not keep the process alive after the error
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() > deadline {
+            child.kill().unwrap();
+            panic!("watch kept running after failing to read its pattern");
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    assert!(!status.success());
+    let output = child.wait_with_output().unwrap();
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to read glob pattern"));
+}